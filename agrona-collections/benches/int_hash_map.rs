@@ -1,11 +1,12 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use agrona_collections::IntHashMap;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 fn benchmark_int_hash_map(c: &mut Criterion) {
     let mut group = c.benchmark_group("int_hash_map");
 
-    for size in [1000, 10000, 100000].iter() {
+    for size in [1000usize, 10000, 100000].iter() {
         group.bench_with_input(BenchmarkId::new("agrona_insert", size), size, |b, &size| {
             b.iter(|| {
                 let mut map = IntHashMap::new();
@@ -38,7 +39,7 @@ fn benchmark_int_hash_map(c: &mut Criterion) {
             b.iter(|| {
                 let mut sum = 0;
                 for i in 0..size {
-                    if let Some(&value) = agrona_map.get(&(i as i32)) {
+                    if let Some(&value) = agrona_map.get(i as i32) {
                         sum += value;
                     }
                 }
@@ -62,5 +63,50 @@ fn benchmark_int_hash_map(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_int_hash_map);
+/// Inserts `count` keys into a map, returning the total and the slowest single insert.
+fn insert_latency(count: usize, incremental: bool) -> (Duration, Duration) {
+    let mut map = IntHashMap::new();
+    map.set_incremental_resize(incremental);
+    let mut max_latency = Duration::ZERO;
+
+    let start = Instant::now();
+    for i in 0..count {
+        let insert_start = Instant::now();
+        map.insert(black_box(i as i32), black_box(i as i32));
+        max_latency = max_latency.max(insert_start.elapsed());
+    }
+    let total = start.elapsed();
+
+    black_box(map);
+    (total, max_latency)
+}
+
+fn benchmark_insert_latency(c: &mut Criterion) {
+    const KEYS: usize = 1_000_000;
+
+    let mut group = c.benchmark_group("int_hash_map_insert_latency");
+    group.sample_size(10);
+
+    for (name, incremental) in [("immediate_resize", false), ("incremental_resize", true)] {
+        let mut max_latency = Duration::ZERO;
+        group.bench_function(name, |b| {
+            b.iter_custom(|iterations| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iterations {
+                    let (elapsed, latency) = insert_latency(KEYS, incremental);
+                    total += elapsed;
+                    max_latency = max_latency.max(latency);
+                }
+                total
+            })
+        });
+        // Criterion only reports throughput, so the tail is printed alongside it. With incremental
+        // resizing the worst insert is the one that allocates and clears the doubled table
+        println!("{}: max single insert latency {:?}", name, max_latency);
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_int_hash_map, benchmark_insert_latency);
 criterion_main!(benches);
\ No newline at end of file
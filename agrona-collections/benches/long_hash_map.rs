@@ -0,0 +1,152 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use agrona_collections::{Long2LongHashMap, Long2ObjectHashMap, LongHashSet};
+use std::collections::{HashMap, HashSet};
+
+// Spread keys over the high bits, as order and correlation ids are
+const KEY_STRIDE: i64 = (1 << 32) + 7;
+
+fn benchmark_long2long_hash_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long2long_hash_map");
+
+    for size in [1000, 10000, 100000].iter() {
+        group.bench_with_input(BenchmarkId::new("agrona_insert", size), size, |b, &size| {
+            b.iter(|| {
+                let mut map = Long2LongHashMap::new(-1);
+                for i in 0..size {
+                    map.put(black_box(i * KEY_STRIDE), black_box(i)).unwrap();
+                }
+                black_box(map);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("std_insert", size), size, |b, &size| {
+            b.iter(|| {
+                let mut map = HashMap::new();
+                for i in 0..size {
+                    map.insert(black_box(i * KEY_STRIDE), black_box(i));
+                }
+                black_box(map);
+            })
+        });
+
+        let mut agrona_map = Long2LongHashMap::new(-1);
+        let mut std_map = HashMap::new();
+        for i in 0..*size {
+            agrona_map.put(i * KEY_STRIDE, i).unwrap();
+            std_map.insert(i * KEY_STRIDE, i);
+        }
+
+        group.bench_with_input(BenchmarkId::new("agrona_lookup", size), size, |b, &size| {
+            b.iter(|| {
+                let mut sum = 0;
+                for i in 0..size {
+                    sum += agrona_map.get_or_default(i * KEY_STRIDE, 0);
+                }
+                black_box(sum);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("std_lookup", size), size, |b, &size| {
+            b.iter(|| {
+                let mut sum = 0;
+                for i in 0..size {
+                    if let Some(&value) = std_map.get(&(i * KEY_STRIDE)) {
+                        sum += value;
+                    }
+                }
+                black_box(sum);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_long2object_hash_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long2object_hash_map");
+
+    for size in [1000, 10000, 100000].iter() {
+        let mut agrona_map = Long2ObjectHashMap::new();
+        let mut std_map = HashMap::new();
+        for i in 0..*size {
+            agrona_map.insert(i * KEY_STRIDE, i.to_string());
+            std_map.insert(i * KEY_STRIDE, i.to_string());
+        }
+
+        group.bench_with_input(BenchmarkId::new("agrona_lookup", size), size, |b, &size| {
+            b.iter(|| {
+                let mut total_length = 0;
+                for i in 0..size {
+                    if let Some(value) = agrona_map.get(i * KEY_STRIDE) {
+                        total_length += value.len();
+                    }
+                }
+                black_box(total_length);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("std_lookup", size), size, |b, &size| {
+            b.iter(|| {
+                let mut total_length = 0;
+                for i in 0..size {
+                    if let Some(value) = std_map.get(&(i * KEY_STRIDE)) {
+                        total_length += value.len();
+                    }
+                }
+                black_box(total_length);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("agrona_iterate", size), size, |b, _| {
+            b.iter(|| black_box(agrona_map.values().map(String::len).sum::<usize>()))
+        });
+
+        group.bench_with_input(BenchmarkId::new("std_iterate", size), size, |b, _| {
+            b.iter(|| black_box(std_map.values().map(String::len).sum::<usize>()))
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_long_hash_set(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long_hash_set");
+
+    for size in [1000, 10000, 100000].iter() {
+        group.bench_with_input(BenchmarkId::new("agrona_insert_remove", size), size, |b, &size| {
+            b.iter(|| {
+                let mut set = LongHashSet::new();
+                for i in 0..size {
+                    set.insert(black_box(i * KEY_STRIDE));
+                }
+                for i in 0..size {
+                    set.remove(black_box(i * KEY_STRIDE));
+                }
+                black_box(set);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("std_insert_remove", size), size, |b, &size| {
+            b.iter(|| {
+                let mut set = HashSet::new();
+                for i in 0..size {
+                    set.insert(black_box(i * KEY_STRIDE));
+                }
+                for i in 0..size {
+                    set.remove(&black_box(i * KEY_STRIDE));
+                }
+                black_box(set);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_long2long_hash_map,
+    benchmark_long2object_hash_map,
+    benchmark_long_hash_set
+);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+use agrona_core::error::{AgronaError, Result};
+
+pub(crate) const MIN_LOAD_FACTOR: f32 = 0.1;
+pub(crate) const MAX_LOAD_FACTOR: f32 = 0.9;
+
+/// Errors unless `load_factor` is within `[MIN_LOAD_FACTOR, MAX_LOAD_FACTOR]`, which keeps
+/// probe chains bounded and guarantees an empty slot in every table.
+pub(crate) fn validate_load_factor(load_factor: f32) -> Result<()> {
+    if !(MIN_LOAD_FACTOR..=MAX_LOAD_FACTOR).contains(&load_factor) {
+        return Err(AgronaError::IllegalArgument(format!(
+            "load factor must be in the range {}..={}: {}",
+            MIN_LOAD_FACTOR, MAX_LOAD_FACTOR, load_factor
+        )));
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn resize_threshold(capacity: usize, load_factor: f32) -> usize {
+    (capacity as f32 * load_factor) as usize
+}
+
+/// Smallest power-of-two capacity, at least `min_capacity`, that holds `required` entries
+/// without exceeding `load_factor`.
+///
+/// Panics with "capacity overflow" if no such capacity fits in a `usize`, as std collections do.
+pub(crate) fn capacity_for(required: usize, load_factor: f32, min_capacity: usize) -> usize {
+    let mut capacity = min_capacity.checked_next_power_of_two().expect("capacity overflow");
+    while resize_threshold(capacity, load_factor) < required {
+        capacity = capacity.checked_mul(2).expect("capacity overflow");
+    }
+    capacity
+}
+
+/// Whether, after the slot at `deleted_index` is emptied, the entry at `current_index` (whose hash
+/// places it at `ideal_index`) must shift back into the gap to stay reachable from its probe start.
+#[inline]
+pub(crate) fn should_move_entry(deleted_index: usize, current_index: usize, ideal_index: usize) -> bool {
+    if deleted_index < current_index {
+        ideal_index <= deleted_index || ideal_index > current_index
+    } else {
+        ideal_index <= deleted_index && ideal_index > current_index
+    }
+}
+
+pub(crate) fn resize_disabled_error(capacity: usize, len: usize) -> AgronaError {
+    AgronaError::IllegalState(format!(
+        "resizing is disabled and the table is at its load limit: capacity={}, len={}",
+        capacity, len
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_for() {
+        assert_eq!(capacity_for(0, 0.5, 8), 8);
+        assert_eq!(capacity_for(9, 0.5, 8), 32);
+        assert_eq!(capacity_for(12, 0.75, 10), 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_capacity_for_overflow() {
+        capacity_for(usize::MAX, 0.9, 8);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::hashing::{fast_int_hash, mix_hash};
+
+pub(crate) const MISSING_KEY: i32 = i32::MIN;
+
+/// Slots migrated from the old table per mutating operation while an incremental resize is in
+/// progress, unless configured otherwise.
+pub(crate) const DEFAULT_MIGRATION_BATCH: usize = 64;
+
+/// An `i32`-keyed table being drained into its larger replacement a batch of slots at a time.
+///
+/// Slots are never compacted while draining, since that could pull an unmigrated entry behind
+/// the migration cursor. Moved or removed entries are instead flagged so that probes skip past
+/// them.
+pub(crate) struct Migration<V> {
+    pub(crate) keys: Vec<i32>,
+    pub(crate) values: Vec<V>,
+    moved: Vec<u64>,
+    mask: usize,
+    pub(crate) next_index: usize,
+}
+
+impl<V> Migration<V> {
+    pub(crate) fn new(keys: Vec<i32>, values: Vec<V>) -> Self {
+        let capacity = keys.len();
+        Self {
+            keys,
+            values,
+            moved: vec![0; capacity.div_ceil(64)],
+            mask: capacity - 1,
+            next_index: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_complete(&self) -> bool {
+        self.next_index == self.keys.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_live(&self, index: usize) -> bool {
+        self.keys[index] != MISSING_KEY && self.moved[index >> 6] & (1 << (index & 63)) == 0
+    }
+
+    #[inline]
+    pub(crate) fn mark_moved(&mut self, index: usize) {
+        self.moved[index >> 6] |= 1 << (index & 63);
+    }
+
+    /// Separates the values so they can be moved out while liveness is still checked.
+    pub(crate) fn split_values(self) -> (Migration<()>, Vec<V>) {
+        let liveness = Migration {
+            values: vec![(); self.keys.len()],
+            keys: self.keys,
+            moved: self.moved,
+            mask: self.mask,
+            next_index: self.next_index,
+        };
+        (liveness, self.values)
+    }
+
+    /// Index of the live entry for `key`, if it has not been migrated or removed yet.
+    pub(crate) fn find(&self, key: i32) -> Option<usize> {
+        let mut index = mix_hash(fast_int_hash(key)) as usize & self.mask;
+
+        loop {
+            let existing_key = self.keys[index];
+            if existing_key == MISSING_KEY {
+                return None;
+            }
+            if existing_key == key && self.is_live(index) {
+                return Some(index);
+            }
+            index = (index + 1) & self.mask;
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{fast_int_hash, mix_hash};
+use agrona_core::error::{AgronaError, Result};
+
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing map from `i32` to `i32` with keys and values interleaved in one array.
+///
+/// A caller-chosen `missing_value` marks empty slots and is returned for absent keys, so it
+/// cannot be stored as a value, but every `i32` is a valid key.
+#[derive(Clone, Debug)]
+pub struct Int2IntHashMap {
+    entries: Vec<i32>,
+    missing_value: i32,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl Int2IntHashMap {
+    pub fn new(missing_value: i32) -> Self {
+        Self::with_capacity(MIN_CAPACITY, missing_value)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, missing_value: i32) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            entries: vec![missing_value; capacity * 2],
+            missing_value,
+            size: 0,
+            resize_threshold: (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn missing_value(&self) -> i32 {
+        self.missing_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+
+    #[inline]
+    fn hash_key(key: i32) -> usize {
+        mix_hash(fast_int_hash(key)) as usize
+    }
+
+    #[inline]
+    fn is_occupied(&self, index: usize) -> bool {
+        self.entries[index * 2 + 1] != self.missing_value
+    }
+
+    #[inline]
+    fn find_index(&self, key: i32) -> (usize, bool) {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            if !self.is_occupied(index) {
+                return (index, false);
+            }
+            if self.entries[index * 2] == key {
+                return (index, true);
+            }
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    /// Value for `key`, or the missing value if there is none.
+    #[inline]
+    pub fn get(&self, key: i32) -> i32 {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.entries[index * 2 + 1]
+        } else {
+            self.missing_value
+        }
+    }
+
+    #[inline]
+    pub fn get_or_default(&self, key: i32, default_value: i32) -> i32 {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.entries[index * 2 + 1]
+        } else {
+            default_value
+        }
+    }
+
+    pub fn contains_key(&self, key: i32) -> bool {
+        self.find_index(key).1
+    }
+
+    pub fn contains_value(&self, value: i32) -> bool {
+        value != self.missing_value && self.values().any(|existing| existing == value)
+    }
+
+    /// Inserts `value` for `key`, returning the previous value or the missing value.
+    ///
+    /// Errors with `IllegalArgument` if `value` is the missing value.
+    pub fn put(&mut self, key: i32, value: i32) -> Result<i32> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        if found {
+            return Ok(core::mem::replace(&mut self.entries[index * 2 + 1], value));
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Inserts `value` only if `key` is absent, returning the existing value or the missing
+    /// value when it inserted.
+    pub fn put_if_absent(&mut self, key: i32, value: i32) -> Result<i32> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        if found {
+            return Ok(self.entries[index * 2 + 1]);
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Removes `key`, returning its value or the missing value.
+    pub fn remove(&mut self, key: i32) -> i32 {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        self.remove_at(index)
+    }
+
+    /// Sets `key` to `remapping(key, current)`, where `current` is the missing value when the key
+    /// is absent, and returns the new value. Returning the missing value removes the key.
+    pub fn compute<F>(&mut self, key: i32, remapping: F) -> i32
+    where
+        F: FnOnce(i32, i32) -> i32,
+    {
+        let (index, found) = self.find_index(key);
+        let old_value = if found { self.entries[index * 2 + 1] } else { self.missing_value };
+        let new_value = remapping(key, old_value);
+
+        self.update_at(index, found, key, new_value);
+        new_value
+    }
+
+    /// Inserts `mapping(key)` if `key` is absent, returning the resulting value. Nothing is
+    /// inserted if `mapping` returns the missing value.
+    pub fn compute_if_absent<F>(&mut self, key: i32, mapping: F) -> i32
+    where
+        F: FnOnce(i32) -> i32,
+    {
+        let (index, found) = self.find_index(key);
+        if found {
+            return self.entries[index * 2 + 1];
+        }
+
+        let new_value = mapping(key);
+        self.update_at(index, false, key, new_value);
+        new_value
+    }
+
+    /// Replaces the value of a present `key` with `remapping(key, value)`, returning the new
+    /// value. Returning the missing value removes the key.
+    pub fn compute_if_present<F>(&mut self, key: i32, remapping: F) -> i32
+    where
+        F: FnOnce(i32, i32) -> i32,
+    {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        let new_value = remapping(key, self.entries[index * 2 + 1]);
+        self.update_at(index, true, key, new_value);
+        new_value
+    }
+
+    /// Inserts `value` if `key` is absent, otherwise replaces the value with
+    /// `remapping(old, value)`. Returns the new value; the missing value removes the key.
+    pub fn merge<F>(&mut self, key: i32, value: i32, remapping: F) -> Result<i32>
+    where
+        F: FnOnce(i32, i32) -> i32,
+    {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        let new_value = if found {
+            remapping(self.entries[index * 2 + 1], value)
+        } else {
+            value
+        };
+
+        self.update_at(index, found, key, new_value);
+        Ok(new_value)
+    }
+
+    /// Adds `delta` to the value for `key`, treating an absent key as zero, and returns the new
+    /// value. A result equal to the missing value removes the key.
+    pub fn increment(&mut self, key: i32, delta: i32) -> i32 {
+        let missing_value = self.missing_value;
+        self.compute(key, |_, old_value| {
+            let base = if old_value == missing_value { 0 } else { old_value };
+            base.wrapping_add(delta)
+        })
+    }
+
+    pub fn clear(&mut self) {
+        if self.size > 0 {
+            self.entries.fill(self.missing_value);
+            self.size = 0;
+        }
+    }
+
+    pub fn iter(&self) -> Int2IntHashMapIter<'_> {
+        Int2IntHashMapIter {
+            entries: self.entries.chunks_exact(2),
+            missing_value: self.missing_value,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = i32> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = i32> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn check_value(&self, value: i32) -> Result<()> {
+        if value == self.missing_value {
+            return Err(AgronaError::IllegalArgument(format!(
+                "cannot store the missing value {}",
+                value
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Stores the outcome of a compute-style update at the slot `find_index` returned.
+    #[inline]
+    fn update_at(&mut self, index: usize, found: bool, key: i32, new_value: i32) {
+        if new_value == self.missing_value {
+            if found {
+                self.remove_at(index);
+            }
+        } else if found {
+            self.entries[index * 2 + 1] = new_value;
+        } else {
+            self.insert_at(index, key, new_value);
+        }
+    }
+
+    #[inline]
+    fn insert_at(&mut self, index: usize, key: i32, value: i32) {
+        self.entries[index * 2] = key;
+        self.entries[index * 2 + 1] = value;
+        self.size += 1;
+
+        if self.size > self.resize_threshold {
+            self.resize();
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> i32 {
+        let old_value = self.entries[index * 2 + 1];
+        self.entries[index * 2 + 1] = self.missing_value;
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        old_value
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = self.capacity() * 2;
+        let old_entries = core::mem::replace(&mut self.entries, vec![self.missing_value; new_capacity * 2]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        for entry in old_entries.chunks_exact(2) {
+            if entry[1] != self.missing_value {
+                let (index, _) = self.find_index(entry[0]);
+                self.entries[index * 2] = entry[0];
+                self.entries[index * 2 + 1] = entry[1];
+            }
+        }
+    }
+
+    fn compact_chain(&mut self, mut deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while self.is_occupied(index) {
+            let key = self.entries[index * 2];
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.entries[deleted_index * 2] = key;
+                self.entries[deleted_index * 2 + 1] = self.entries[index * 2 + 1];
+                self.entries[index * 2 + 1] = self.missing_value;
+                deleted_index = index;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+}
+
+pub struct Int2IntHashMapIter<'a> {
+    entries: core::slice::ChunksExact<'a, i32>,
+    missing_value: i32,
+}
+
+impl<'a> Iterator for Int2IntHashMapIter<'a> {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let missing_value = self.missing_value;
+        self.entries
+            .find(|entry| entry[1] != missing_value)
+            .map(|entry| (entry[0], entry[1]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.entries.len()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Int2IntHashMap {
+    type Item = (i32, i32);
+    type IntoIter = Int2IntHashMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_get_remove_with_any_key() {
+        let mut map = Int2IntHashMap::new(0);
+
+        assert_eq!(map.put(i32::MIN, -5).unwrap(), 0);
+        assert_eq!(map.put(-1, 7).unwrap(), 0);
+        assert_eq!(map.put(-1, 8).unwrap(), 7);
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get(i32::MIN), -5);
+        assert_eq!(map.get(42), 0);
+        assert_eq!(map.get_or_default(42, -1), -1);
+        assert!(map.contains_value(8));
+        assert!(matches!(map.put(1, 0), Err(AgronaError::IllegalArgument(_))));
+
+        assert_eq!(map.remove(-1), 8);
+        assert_eq!(map.remove(-1), 0);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_put_if_absent_and_compute() {
+        let mut map = Int2IntHashMap::new(i32::MIN);
+
+        assert_eq!(map.put_if_absent(1, -10).unwrap(), i32::MIN);
+        assert_eq!(map.put_if_absent(1, 20).unwrap(), -10);
+
+        assert_eq!(map.compute(1, |_, value| value * 2), -20);
+        assert_eq!(map.compute_if_absent(2, |key| key + 1), 3);
+        assert_eq!(map.compute_if_absent(2, |_| 99), 3);
+        assert_eq!(map.compute_if_present(3, |_, _| 1), i32::MIN);
+        assert!(!map.contains_key(3));
+
+        assert_eq!(map.merge(2, 4, |old, new| old + new).unwrap(), 7);
+        assert_eq!(map.compute(2, |_, _| i32::MIN), i32::MIN);
+        assert!(!map.contains_key(2));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_increment_removes_at_missing_value() {
+        let mut map = Int2IntHashMap::new(0);
+
+        assert_eq!(map.increment(100, -30), -30);
+        assert_eq!(map.increment(100, 10), -20);
+        assert_eq!(map.increment(100, 20), 0);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_resize_and_removal_keep_entries_reachable() {
+        let mut map = Int2IntHashMap::with_capacity(4, -1);
+
+        for key in 0..1000 {
+            map.put(key, key * 3).unwrap();
+        }
+        for key in (0..1000).step_by(2) {
+            assert_eq!(map.remove(key), key * 3);
+        }
+
+        assert_eq!(map.len(), 500);
+        for key in 0..1000 {
+            assert_eq!(map.get(key), if key % 2 == 0 { -1 } else { key * 3 });
+        }
+
+        let mut entries: Vec<_> = (&map).into_iter().collect();
+        entries.sort();
+        assert_eq!(entries, (0..1000).filter(|key| key % 2 == 1).map(|key| (key, key * 3)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::hashing::fast_int_hash;
+use agrona_core::error::{AgronaError, Result};
+
+/// Fixed-size, set-associative cache from `i32` keys to `V`.
+///
+/// A key hashes to one of `num_sets` sets of `set_size` slots. Entries within a set are kept
+/// newest first, so inserting into a full set evicts its least-recently-inserted entry. Every
+/// evicted or replaced value is passed to the eviction consumer, which can release whatever it
+/// holds. The cache never grows beyond `num_sets * set_size` entries.
+pub struct Int2ObjectCache<V, E: FnMut(V)> {
+    keys: Vec<i32>,
+    values: Vec<Option<V>>,
+    sets_mask: usize,
+    set_size: usize,
+    set_size_shift: u32,
+    size: usize,
+    eviction_consumer: E,
+    cache_hits: u64,
+    cache_misses: u64,
+    cache_puts: u64,
+    cache_evictions: u64,
+}
+
+impl<V, E: FnMut(V)> Int2ObjectCache<V, E> {
+    /// Cache of `num_sets` sets of `set_size` slots, both of which must be powers of two.
+    pub fn new(num_sets: usize, set_size: usize, eviction_consumer: E) -> Result<Self> {
+        if !num_sets.is_power_of_two() {
+            return Err(AgronaError::IllegalArgument(format!(
+                "number of sets must be a power of two: {}",
+                num_sets
+            )));
+        }
+        if !set_size.is_power_of_two() {
+            return Err(AgronaError::IllegalArgument(format!(
+                "set size must be a power of two: {}",
+                set_size
+            )));
+        }
+
+        let capacity = num_sets
+            .checked_mul(set_size)
+            .ok_or(AgronaError::InvalidCapacity { capacity: usize::MAX })?;
+
+        Ok(Self {
+            keys: vec![0; capacity],
+            values: (0..capacity).map(|_| None).collect(),
+            sets_mask: num_sets - 1,
+            set_size,
+            set_size_shift: set_size.trailing_zeros(),
+            size: 0,
+            eviction_consumer,
+            cache_hits: 0,
+            cache_misses: 0,
+            cache_puts: 0,
+            cache_evictions: 0,
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn num_sets(&self) -> usize {
+        self.sets_mask + 1
+    }
+
+    #[inline]
+    pub fn set_size(&self) -> usize {
+        self.set_size
+    }
+
+    #[inline]
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits
+    }
+
+    #[inline]
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses
+    }
+
+    #[inline]
+    pub fn cache_puts(&self) -> u64 {
+        self.cache_puts
+    }
+
+    /// Entries pushed out of a full set to make room for a new one.
+    #[inline]
+    pub fn cache_evictions(&self) -> u64 {
+        self.cache_evictions
+    }
+
+    pub fn reset_counters(&mut self) {
+        self.cache_hits = 0;
+        self.cache_misses = 0;
+        self.cache_puts = 0;
+        self.cache_evictions = 0;
+    }
+
+    /// Slot range of the set `key` belongs to.
+    #[inline]
+    fn set_range(&self, key: i32) -> core::ops::Range<usize> {
+        let set_number = fast_int_hash(key) as usize & self.sets_mask;
+        let set_begin = set_number << self.set_size_shift;
+        set_begin..set_begin + self.set_size
+    }
+
+    /// Index of `key` within its set. Occupied slots are packed at the start of a set, so the
+    /// scan stops at the first empty one.
+    #[inline]
+    fn find_index(&self, key: i32) -> Option<usize> {
+        self.set_range(key)
+            .take_while(|&index| self.values[index].is_some())
+            .find(|&index| self.keys[index] == key)
+    }
+
+    /// Looks up `key`, counting a hit or a miss.
+    pub fn get(&mut self, key: i32) -> Option<&V> {
+        match self.find_index(key) {
+            Some(index) => {
+                self.cache_hits += 1;
+                self.values[index].as_ref()
+            }
+            None => {
+                self.cache_misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Looks up `key` without affecting the counters.
+    pub fn contains_key(&self, key: i32) -> bool {
+        self.find_index(key).is_some()
+    }
+
+    /// Returns the cached value for `key`, inserting the result of `mapping_function` on a miss.
+    pub fn compute_if_absent<F: FnOnce(i32) -> V>(&mut self, key: i32, mapping_function: F) -> &V {
+        let index = match self.find_index(key) {
+            Some(index) => {
+                self.cache_hits += 1;
+                index
+            }
+            None => {
+                self.cache_misses += 1;
+                self.insert_entry(key, mapping_function(key))
+            }
+        };
+
+        self.values[index].as_ref().expect("slot was just found or filled")
+    }
+
+    /// Caches `value` for `key`. A value already cached for `key` is replaced in place and passed
+    /// to the eviction consumer.
+    pub fn put(&mut self, key: i32, value: V) {
+        match self.find_index(key) {
+            Some(index) => {
+                let old_value = self.values[index].replace(value).expect("slot is occupied");
+                self.cache_puts += 1;
+                (self.eviction_consumer)(old_value);
+            }
+            None => {
+                self.insert_entry(key, value);
+            }
+        }
+    }
+
+    /// Removes `key`, handing its value back to the caller rather than the eviction consumer.
+    pub fn remove(&mut self, key: i32) -> Option<V> {
+        let index = self.find_index(key)?;
+        let value = self.values[index].take();
+        self.size -= 1;
+
+        // Close the gap so the set stays packed
+        let set_end = self.set_range(key).end;
+        self.keys[index..set_end].rotate_left(1);
+        self.values[index..set_end].rotate_left(1);
+
+        value
+    }
+
+    /// Evicts every entry through the eviction consumer.
+    pub fn clear(&mut self) {
+        for value in self.values.iter_mut() {
+            if let Some(value) = value.take() {
+                (self.eviction_consumer)(value);
+            }
+        }
+        self.size = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &V)> + '_ {
+        self.keys
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(&key, value)| value.as_ref().map(|value| (key, value)))
+    }
+
+    /// Inserts at the front of the set, shifting older entries back and evicting the oldest if
+    /// the set is full. Returns the slot now holding `value`.
+    fn insert_entry(&mut self, key: i32, value: V) -> usize {
+        let set = self.set_range(key);
+        let set_begin = set.start;
+
+        self.keys[set.clone()].rotate_right(1);
+        self.values[set].rotate_right(1);
+
+        match self.values[set_begin].replace(value) {
+            Some(evicted) => {
+                self.cache_evictions += 1;
+                (self.eviction_consumer)(evicted);
+            }
+            None => self.size += 1,
+        }
+        self.keys[set_begin] = key;
+        self.cache_puts += 1;
+
+        set_begin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_validates_dimensions() {
+        assert!(Int2ObjectCache::<i32, _>::new(3, 4, drop).is_err());
+        assert!(Int2ObjectCache::<i32, _>::new(4, 0, drop).is_err());
+
+        let cache = Int2ObjectCache::<i32, _>::new(8, 4, drop).unwrap();
+        assert_eq!(cache.capacity(), 32);
+        assert_eq!(cache.num_sets(), 8);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_inserted_within_a_set() {
+        let evicted = RefCell::new(Vec::new());
+        // A single set makes every key collide
+        let mut cache = Int2ObjectCache::new(1, 2, |value: String| evicted.borrow_mut().push(value)).unwrap();
+
+        cache.put(1, "one".to_string());
+        cache.put(2, "two".to_string());
+        assert_eq!(cache.get(1).map(String::as_str), Some("one"));
+
+        cache.put(3, "three".to_string());
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains_key(1));
+        assert_eq!(cache.get(1), None);
+
+        // Replacing passes the old value to the consumer without counting as an eviction
+        cache.put(2, "deux".to_string());
+        assert_eq!(cache.compute_if_absent(2, |_| unreachable!()), "deux");
+        assert_eq!(cache.compute_if_absent(4, |key| key.to_string()), "4");
+
+        // Inserting 4 pushed out 2, the older of the two
+        assert_eq!(cache.remove(2), None);
+        assert_eq!(cache.remove(3), Some("three".to_string()));
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(4, &"4".to_string())]);
+
+        assert_eq!(cache.cache_hits(), 2);
+        assert_eq!(cache.cache_misses(), 2);
+        assert_eq!(cache.cache_puts(), 5);
+        assert_eq!(cache.cache_evictions(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        drop(cache);
+        assert_eq!(*evicted.borrow(), vec!["one", "two", "deux", "4"]);
+    }
+}
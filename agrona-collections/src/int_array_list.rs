@@ -0,0 +1,273 @@
+use crate::int_hash_set::IntHashSet;
+
+pub const DEFAULT_INT_NULL_VALUE: i32 = i32::MIN;
+const MIN_CAPACITY: usize = 8;
+
+/// Growable list of `i32` with Agrona's semantics: a `null_value` is returned where Agrona
+/// would return null, e.g. when popping an empty list.
+#[derive(Clone, Debug)]
+pub struct IntArrayList {
+    elements: Vec<i32>,
+    null_value: i32,
+}
+
+impl IntArrayList {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY, DEFAULT_INT_NULL_VALUE)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, null_value: i32) -> Self {
+        Self {
+            elements: Vec::with_capacity(initial_capacity.max(MIN_CAPACITY)),
+            null_value,
+        }
+    }
+
+    /// Wraps existing elements without copying them.
+    pub fn wrap(elements: Vec<i32>, null_value: i32) -> Self {
+        Self { elements, null_value }
+    }
+
+    #[inline]
+    pub fn null_value(&self) -> i32 {
+        self.null_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+
+    /// Element at `index`, panicking if it is out of bounds.
+    #[inline]
+    pub fn get_int(&self, index: usize) -> i32 {
+        self.elements[index]
+    }
+
+    /// Replaces the element at `index`, returning the previous one.
+    #[inline]
+    pub fn set_int(&mut self, index: usize, value: i32) -> i32 {
+        core::mem::replace(&mut self.elements[index], value)
+    }
+
+    #[inline]
+    pub fn add_int(&mut self, value: i32) {
+        self.elements.push(value);
+    }
+
+    pub fn add_int_at(&mut self, index: usize, value: i32) {
+        self.elements.insert(index, value);
+    }
+
+    /// Pushes onto the end, treating the list as a stack.
+    #[inline]
+    pub fn push_int(&mut self, value: i32) {
+        self.elements.push(value);
+    }
+
+    /// Pops from the end, or returns the null value if empty.
+    #[inline]
+    pub fn pop_int(&mut self) -> i32 {
+        self.elements.pop().unwrap_or(self.null_value)
+    }
+
+    /// Last element, or the null value if empty.
+    #[inline]
+    pub fn peek_int(&self) -> i32 {
+        self.elements.last().copied().unwrap_or(self.null_value)
+    }
+
+    /// Removes the element at `index`, shifting later elements down.
+    pub fn remove_at(&mut self, index: usize) -> i32 {
+        self.elements.remove(index)
+    }
+
+    /// Removes the element at `index` in O(1) by moving the last element into its place.
+    #[inline]
+    pub fn fast_unordered_remove(&mut self, index: usize) -> i32 {
+        self.elements.swap_remove(index)
+    }
+
+    /// Removes the first occurrence of `value`, preserving order.
+    pub fn remove_int(&mut self, value: i32) -> bool {
+        match self.index_of(value) {
+            Some(index) => {
+                self.elements.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the first occurrence of `value` without preserving order.
+    pub fn fast_unordered_remove_int(&mut self, value: i32) -> bool {
+        match self.index_of(value) {
+            Some(index) => {
+                self.elements.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub fn contains_int(&self, value: i32) -> bool {
+        self.elements.contains(&value)
+    }
+
+    pub fn index_of(&self, value: i32) -> Option<usize> {
+        self.elements.iter().position(|&element| element == value)
+    }
+
+    pub fn last_index_of(&self, value: i32) -> Option<usize> {
+        self.elements.iter().rposition(|&element| element == value)
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn ensure_capacity(&mut self, required_capacity: usize) {
+        self.elements.reserve(required_capacity.saturating_sub(self.elements.len()));
+    }
+
+    pub fn trim_to_size(&mut self) {
+        self.elements.shrink_to(MIN_CAPACITY);
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[i32] {
+        &self.elements
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, i32>> {
+        self.elements.iter().copied()
+    }
+
+    pub fn into_vec(self) -> Vec<i32> {
+        self.elements
+    }
+}
+
+impl Default for IntArrayList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for IntArrayList {
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl Eq for IntArrayList {}
+
+impl From<Vec<i32>> for IntArrayList {
+    fn from(elements: Vec<i32>) -> Self {
+        Self::wrap(elements, DEFAULT_INT_NULL_VALUE)
+    }
+}
+
+impl From<&IntHashSet> for IntArrayList {
+    fn from(set: &IntHashSet) -> Self {
+        let mut list = Self::with_capacity(set.len(), DEFAULT_INT_NULL_VALUE);
+        list.extend(set.iter());
+        list
+    }
+}
+
+impl From<&IntArrayList> for IntHashSet {
+    fn from(list: &IntArrayList) -> Self {
+        let mut set = IntHashSet::with_capacity(list.len() * 2);
+        for value in list.iter() {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl FromIterator<i32> for IntArrayList {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<i32> for IntArrayList {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for &'a IntArrayList {
+    type Item = i32;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, i32>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_operations_return_null_value() {
+        let mut list = IntArrayList::with_capacity(4, -1);
+
+        assert_eq!(list.pop_int(), -1);
+        assert_eq!(list.peek_int(), -1);
+
+        list.push_int(1);
+        list.push_int(2);
+        assert_eq!(list.peek_int(), 2);
+        assert_eq!(list.pop_int(), 2);
+        assert_eq!(list.pop_int(), 1);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_removal() {
+        let mut list: IntArrayList = vec![10, 20, 30, 40, 20].into();
+
+        assert_eq!(list.fast_unordered_remove(0), 10);
+        assert_eq!(list.as_slice(), &[20, 20, 30, 40]);
+
+        assert!(list.remove_int(20));
+        assert_eq!(list.as_slice(), &[20, 30, 40]);
+        assert!(list.fast_unordered_remove_int(20));
+        assert_eq!(list.as_slice(), &[40, 30]);
+        assert!(!list.remove_int(20));
+
+        assert!(list.contains_int(30));
+        assert_eq!(list.index_of(30), Some(1));
+        assert_eq!(list.set_int(1, 35), 30);
+        assert_eq!(list.remove_at(0), 40);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![35]);
+    }
+
+    #[test]
+    fn test_hash_set_conversions() {
+        let list: IntArrayList = [3, 1, 3, i32::MIN].into_iter().collect();
+
+        let set = IntHashSet::from(&list);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(i32::MIN));
+
+        let mut round_trip = IntArrayList::from(&set).into_vec();
+        round_trip.sort();
+        assert_eq!(round_trip, vec![i32::MIN, 1, 3]);
+    }
+}
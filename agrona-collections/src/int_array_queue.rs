@@ -0,0 +1,271 @@
+use crate::int_array_list::DEFAULT_INT_NULL_VALUE;
+use crate::int_hash_set::IntHashSet;
+
+const MIN_CAPACITY: usize = 8;
+
+/// Double-ended queue of `i32` over a power-of-two ring buffer that doubles when full.
+///
+/// Polling or peeking an empty queue returns the `null_value`.
+#[derive(Clone, Debug)]
+pub struct IntArrayQueue {
+    elements: Vec<i32>,
+    head: usize,
+    size: usize,
+    mask: usize,
+    null_value: i32,
+}
+
+impl IntArrayQueue {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY, DEFAULT_INT_NULL_VALUE)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, null_value: i32) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            elements: vec![null_value; capacity],
+            head: 0,
+            size: 0,
+            mask: capacity - 1,
+            null_value,
+        }
+    }
+
+    #[inline]
+    pub fn null_value(&self) -> i32 {
+        self.null_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Adds `value` to the tail.
+    pub fn offer_int(&mut self, value: i32) {
+        if self.size == self.elements.len() {
+            self.grow();
+        }
+
+        let index = (self.head + self.size) & self.mask;
+        self.elements[index] = value;
+        self.size += 1;
+    }
+
+    /// Adds `value` to the head.
+    pub fn offer_first_int(&mut self, value: i32) {
+        if self.size == self.elements.len() {
+            self.grow();
+        }
+
+        self.head = self.head.wrapping_sub(1) & self.mask;
+        self.elements[self.head] = value;
+        self.size += 1;
+    }
+
+    /// Removes and returns the head, or the null value if empty.
+    pub fn poll_int(&mut self) -> i32 {
+        if self.size == 0 {
+            return self.null_value;
+        }
+
+        let value = self.elements[self.head];
+        self.head = (self.head + 1) & self.mask;
+        self.size -= 1;
+        value
+    }
+
+    /// Removes and returns the tail, or the null value if empty.
+    pub fn poll_last_int(&mut self) -> i32 {
+        if self.size == 0 {
+            return self.null_value;
+        }
+
+        self.size -= 1;
+        self.elements[(self.head + self.size) & self.mask]
+    }
+
+    #[inline]
+    pub fn peek_int(&self) -> i32 {
+        if self.size == 0 {
+            self.null_value
+        } else {
+            self.elements[self.head]
+        }
+    }
+
+    #[inline]
+    pub fn peek_last_int(&self) -> i32 {
+        if self.size == 0 {
+            self.null_value
+        } else {
+            self.elements[(self.head + self.size - 1) & self.mask]
+        }
+    }
+
+    /// Element `index` places from the head, or the null value if out of range.
+    #[inline]
+    pub fn get_int(&self, index: usize) -> i32 {
+        if index < self.size {
+            self.elements[(self.head + index) & self.mask]
+        } else {
+            self.null_value
+        }
+    }
+
+    pub fn contains_int(&self, value: i32) -> bool {
+        self.iter().any(|element| element == value)
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.size = 0;
+    }
+
+    /// Iterates from head to tail without allocating.
+    pub fn iter(&self) -> IntArrayQueueIter<'_> {
+        IntArrayQueueIter {
+            queue: self,
+            index: 0,
+        }
+    }
+
+    fn grow(&mut self) {
+        let old_capacity = self.elements.len();
+        let mut elements = vec![self.null_value; old_capacity * 2];
+
+        let (wrapped, front) = self.elements.split_at(self.head);
+        elements[..front.len()].copy_from_slice(front);
+        elements[front.len()..old_capacity].copy_from_slice(wrapped);
+
+        self.elements = elements;
+        self.head = 0;
+        self.mask = old_capacity * 2 - 1;
+    }
+}
+
+impl Default for IntArrayQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct IntArrayQueueIter<'a> {
+    queue: &'a IntArrayQueue,
+    index: usize,
+}
+
+impl<'a> Iterator for IntArrayQueueIter<'a> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.queue.size {
+            return None;
+        }
+
+        let value = self.queue.elements[(self.queue.head + self.index) & self.queue.mask];
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.queue.size - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IntArrayQueueIter<'_> {}
+
+impl<'a> IntoIterator for &'a IntArrayQueue {
+    type Item = i32;
+    type IntoIter = IntArrayQueueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<i32> for IntArrayQueue {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl Extend<i32> for IntArrayQueue {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        for value in iter {
+            self.offer_int(value);
+        }
+    }
+}
+
+impl From<&IntArrayQueue> for IntHashSet {
+    fn from(queue: &IntArrayQueue) -> Self {
+        let mut set = IntHashSet::with_capacity(queue.len() * 2);
+        for value in queue {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_and_null_value() {
+        let mut queue = IntArrayQueue::with_capacity(4, -1);
+
+        assert_eq!(queue.poll_int(), -1);
+        assert_eq!(queue.peek_int(), -1);
+
+        queue.offer_int(1);
+        queue.offer_int(2);
+        queue.offer_first_int(0);
+        assert_eq!(queue.peek_int(), 0);
+        assert_eq!(queue.peek_last_int(), 2);
+        assert_eq!(queue.get_int(1), 1);
+        assert_eq!(queue.get_int(3), -1);
+
+        assert_eq!(queue.poll_int(), 0);
+        assert_eq!(queue.poll_last_int(), 2);
+        assert_eq!(queue.poll_int(), 1);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_grows_when_wrapped() {
+        let mut queue = IntArrayQueue::with_capacity(8, 0);
+
+        for value in 1..=6 {
+            queue.offer_int(value);
+        }
+        for _ in 0..4 {
+            queue.poll_int();
+        }
+        // Head is now mid-buffer, so growing has to unwrap the elements
+        for value in 7..=20 {
+            queue.offer_int(value);
+        }
+
+        assert_eq!(queue.capacity(), 16);
+        assert_eq!(queue.iter().len(), 16);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), (5..=20).collect::<Vec<_>>());
+        assert!(queue.contains_int(20));
+        assert_eq!(IntHashSet::from(&queue).len(), 16);
+    }
+}
@@ -1,49 +1,107 @@
-use crate::hashing::{fast_int_hash, mix_hash};
+use crate::collection_util::{
+    capacity_for, resize_disabled_error, resize_threshold, should_move_entry, validate_load_factor,
+};
+use crate::hashing::{fast_int_hash, fast_long_hash, mix_hash, FastHasher};
+use crate::incremental_resize::{Migration, DEFAULT_MIGRATION_BATCH};
+use agrona_core::error::Result;
+use core::hash::{Hash, Hasher};
 use core::mem;
 
 const MISSING_VALUE: i32 = i32::MIN;
+/// Index used by entries for the out-of-band `MISSING_VALUE` key.
+const MISSING_KEY_INDEX: usize = usize::MAX;
 const MIN_CAPACITY: usize = 8;
 const DEFAULT_LOAD_FACTOR: f32 = 0.67;
 
+/// Open-addressing map from `i32` keys to `V`.
+///
+/// `i32::MIN` marks empty slots in the table, so an entry for that key is held out-of-band.
+///
+/// The table doubles when it reaches its load factor unless resizing is disabled with
+/// [`set_resize_enabled`](Self::set_resize_enabled), in which case [`try_insert`](Self::try_insert)
+/// errors instead so latency-critical code never pays for a rehash.
+///
+/// With [`set_incremental_resize`](Self::set_incremental_resize) the table instead grows by
+/// moving a bounded batch of entries per mutating operation, so no single call pays for the
+/// whole rehash, though one still pays to allocate the doubled table. Lookups consult both
+/// tables until the migration completes.
 pub struct IntHashMap<V> {
     keys: Vec<i32>,
     values: Vec<V>,
+    missing_key_value: Option<V>,
+    migration: Option<Migration<V>>,
     size: usize,
+    load_factor: f32,
     resize_threshold: usize,
+    resize_enabled: bool,
+    incremental_resize: bool,
+    migration_batch: usize,
     mask: usize,
 }
 
-impl<V: Clone + Default> IntHashMap<V> {
-    pub fn new() -> Self {
-        Self::with_capacity(MIN_CAPACITY)
+impl<V> IntHashMap<V> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size + self.missing_key_value.is_some() as usize
     }
 
-    pub fn with_capacity(initial_capacity: usize) -> Self {
-        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
-        let resize_threshold = (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-        Self {
-            keys: vec![MISSING_VALUE; capacity],
-            values: vec![V::default(); capacity],
-            size: 0,
-            resize_threshold,
-            mask: capacity - 1,
-        }
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
     }
 
     #[inline]
-    pub fn len(&self) -> usize {
-        self.size
+    pub fn load_factor(&self) -> f32 {
+        self.load_factor
     }
 
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.size == 0
+    pub fn is_resize_enabled(&self) -> bool {
+        self.resize_enabled
+    }
+
+    /// Enables or disables automatic growth. Explicit [`reserve`](Self::reserve) and
+    /// [`shrink_to_fit`](Self::shrink_to_fit) calls still resize.
+    pub fn set_resize_enabled(&mut self, resize_enabled: bool) {
+        self.resize_enabled = resize_enabled;
     }
 
     #[inline]
-    pub fn capacity(&self) -> usize {
-        self.keys.len()
+    pub fn is_incremental_resize(&self) -> bool {
+        self.incremental_resize
+    }
+
+    /// Grows by migrating a bounded number of slots per mutating operation instead of
+    /// rehashing everything at once.
+    ///
+    /// Only the rehash is spread out. The doubled table is still allocated and filled with empty
+    /// slots by the operation that crosses the load factor, so that call costs a write per new
+    /// slot, which is far cheaper than rehashing every entry but is not free.
+    pub fn set_incremental_resize(&mut self, incremental_resize: bool) {
+        self.incremental_resize = incremental_resize;
+    }
+
+    #[inline]
+    pub fn migration_batch(&self) -> usize {
+        self.migration_batch
+    }
+
+    /// Slots of the old table migrated per mutating operation during an incremental resize, at
+    /// least one. Smaller batches bound each operation more tightly but keep both tables alive
+    /// for longer.
+    pub fn set_migration_batch(&mut self, migration_batch: usize) {
+        self.migration_batch = migration_batch.max(1);
+    }
+
+    /// Whether entries are still being migrated from a smaller table.
+    #[inline]
+    pub fn is_migrating(&self) -> bool {
+        self.migration.is_some()
     }
 
     #[inline]
@@ -51,6 +109,68 @@ impl<V: Clone + Default> IntHashMap<V> {
         mix_hash(fast_int_hash(key)) as usize
     }
 
+    /// Entry at flat `position`, which covers the table, then any table still being migrated,
+    /// then the out-of-band key. Advances `position` past the entry returned.
+    fn next_entry(&self, position: &mut usize) -> Option<(i32, &V)> {
+        let table_length = self.keys.len();
+        while *position < table_length {
+            let index = *position;
+            *position += 1;
+            if self.keys[index] != MISSING_VALUE {
+                return Some((self.keys[index], &self.values[index]));
+            }
+        }
+
+        let old_length = self.migration.as_ref().map_or(0, Migration::capacity);
+        while *position < table_length + old_length {
+            let index = *position - table_length;
+            *position += 1;
+            let migration = self.migration.as_ref().expect("migration in progress");
+            if migration.is_live(index) {
+                return Some((migration.keys[index], &migration.values[index]));
+            }
+        }
+
+        if *position == table_length + old_length {
+            *position += 1;
+            return self.missing_key_value.as_ref().map(|value| (MISSING_VALUE, value));
+        }
+        None
+    }
+}
+
+impl<V: Clone + Default> IntHashMap<V> {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY)
+    }
+
+    pub fn with_capacity(initial_capacity: usize) -> Self {
+        Self::with_table(initial_capacity.max(MIN_CAPACITY).next_power_of_two(), DEFAULT_LOAD_FACTOR)
+    }
+
+    /// Map sized to hold `initial_capacity` entries without resizing at the given load factor,
+    /// which must be within `0.1..=0.9`.
+    pub fn with_capacity_and_load_factor(initial_capacity: usize, load_factor: f32) -> Result<Self> {
+        validate_load_factor(load_factor)?;
+        Ok(Self::with_table(capacity_for(initial_capacity, load_factor, MIN_CAPACITY), load_factor))
+    }
+
+    fn with_table(capacity: usize, load_factor: f32) -> Self {
+        Self {
+            keys: vec![MISSING_VALUE; capacity],
+            values: vec![V::default(); capacity],
+            missing_key_value: None,
+            migration: None,
+            size: 0,
+            load_factor,
+            resize_threshold: resize_threshold(capacity, load_factor),
+            resize_enabled: true,
+            incremental_resize: false,
+            migration_batch: DEFAULT_MIGRATION_BATCH,
+            mask: capacity - 1,
+        }
+    }
+
     #[inline]
     fn find_index(&self, key: i32) -> (usize, bool) {
         let mut index = Self::hash_key(key) & self.mask;
@@ -68,59 +188,104 @@ impl<V: Clone + Default> IntHashMap<V> {
     }
 
     pub fn get(&self, key: i32) -> Option<&V> {
+        if key == MISSING_VALUE {
+            return self.missing_key_value.as_ref();
+        }
+
         let (index, found) = self.find_index(key);
         if found {
-            Some(&self.values[index])
-        } else {
-            None
+            return Some(&self.values[index]);
         }
+
+        let migration = self.migration.as_ref()?;
+        migration.find(key).map(|index| &migration.values[index])
     }
 
     pub fn get_mut(&mut self, key: i32) -> Option<&mut V> {
+        if key == MISSING_VALUE {
+            return self.missing_key_value.as_mut();
+        }
+
         let (index, found) = self.find_index(key);
         if found {
-            Some(&mut self.values[index])
-        } else {
-            None
+            return Some(&mut self.values[index]);
         }
+
+        let migration = self.migration.as_mut()?;
+        let index = migration.find(key)?;
+        Some(&mut migration.values[index])
     }
 
+    /// Inserts `value` for `key`, returning the previous value.
+    ///
+    /// Panics if a new key would need a resize while resizing is disabled; use
+    /// [`try_insert`](Self::try_insert) to handle that case.
     pub fn insert(&mut self, key: i32, value: V) -> Option<V> {
-        if self.size >= self.resize_threshold {
-            self.resize();
+        match self.try_insert(key, value) {
+            Ok(old_value) => old_value,
+            Err(error) => panic!("{}", error),
         }
+    }
 
-        let (index, found) = self.find_index(key);
+    /// Inserts `value` for `key`, returning the previous value, or errors with `IllegalState` if
+    /// a new key would need a resize while resizing is disabled.
+    pub fn try_insert(&mut self, key: i32, value: V) -> Result<Option<V>> {
+        if key == MISSING_VALUE {
+            return Ok(self.missing_key_value.replace(value));
+        }
 
+        self.migrate(self.migration_batch);
+        if let Some(migration) = self.migration.as_mut() {
+            if let Some(index) = migration.find(key) {
+                return Ok(Some(mem::replace(&mut migration.values[index], value)));
+            }
+        }
+
+        let (mut index, found) = self.find_index(key);
         if found {
-            let old_value = mem::replace(&mut self.values[index], value);
-            Some(old_value)
-        } else {
-            self.keys[index] = key;
-            self.values[index] = value;
-            self.size += 1;
-            None
+            return Ok(Some(mem::replace(&mut self.values[index], value)));
+        }
+
+        if self.size >= self.resize_threshold {
+            self.grow()?;
+            index = self.find_index(key).0;
         }
+
+        self.keys[index] = key;
+        self.values[index] = value;
+        self.size += 1;
+        Ok(None)
     }
 
     pub fn remove(&mut self, key: i32) -> Option<V> {
+        if key == MISSING_VALUE {
+            return self.missing_key_value.take();
+        }
+
+        self.migrate(self.migration_batch);
+        if let Some(migration) = self.migration.as_mut() {
+            if let Some(index) = migration.find(key) {
+                migration.mark_moved(index);
+                self.size -= 1;
+                return Some(mem::take(&mut migration.values[index]));
+            }
+        }
+
         let (index, found) = self.find_index(key);
 
         if !found {
             return None;
         }
 
-        let old_value = mem::take(&mut self.values[index]);
-        self.keys[index] = MISSING_VALUE;
-        self.size -= 1;
-
-        self.compact_chain(index);
-
-        Some(old_value)
+        Some(self.remove_at(index))
     }
 
     pub fn contains_key(&self, key: i32) -> bool {
-        self.find_index(key).1
+        if key == MISSING_VALUE {
+            return self.missing_key_value.is_some();
+        }
+
+        self.find_index(key).1 || self.migration.as_ref().is_some_and(|migration| migration.find(key).is_some())
     }
 
     pub fn clear(&mut self) {
@@ -128,28 +293,103 @@ impl<V: Clone + Default> IntHashMap<V> {
         for value in &mut self.values {
             *value = V::default();
         }
+        self.missing_key_value = None;
+        self.migration = None;
         self.size = 0;
     }
 
-    fn resize(&mut self) {
-        let old_keys = mem::take(&mut self.keys);
-        let old_values = mem::take(&mut self.values);
-        let old_size = self.size;
+    /// Grows the table so `additional` more entries fit without a resize.
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = capacity_for(self.size.saturating_add(additional), self.load_factor, self.capacity());
+        if capacity != self.capacity() {
+            self.rehash(capacity);
+        }
+    }
+
+    /// Shrinks the table to the smallest capacity that holds the current entries within the
+    /// load factor.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = capacity_for(self.size, self.load_factor, MIN_CAPACITY);
+        if capacity != self.capacity() {
+            self.rehash(capacity);
+        }
+    }
+
+    /// Rehashes into a table sized for the current entries, as Agrona's `compact` does.
+    pub fn compact(&mut self) {
+        self.rehash(capacity_for(self.size, self.load_factor, MIN_CAPACITY));
+    }
+
+    fn grow(&mut self) -> Result<()> {
+        if !self.resize_enabled {
+            return Err(resize_disabled_error(self.capacity(), self.len()));
+        }
+
+        self.expand();
+        Ok(())
+    }
+
+    /// Doubles the table, either at once or by starting an incremental migration. An unfinished
+    /// migration is completed first, which only happens if the table filled up faster than it
+    /// drained.
+    fn expand(&mut self) {
+        self.finish_migration();
 
-        let new_capacity = old_keys.len() * 2;
-        self.keys = vec![MISSING_VALUE; new_capacity];
-        self.values = vec![V::default(); new_capacity];
+        let new_capacity = self.capacity() * 2;
+        if !self.incremental_resize {
+            self.rehash(new_capacity);
+            return;
+        }
+
+        let old_keys = mem::replace(&mut self.keys, vec![MISSING_VALUE; new_capacity]);
+        let old_values = mem::replace(&mut self.values, vec![V::default(); new_capacity]);
+        self.migration = Some(Migration::new(old_keys, old_values));
         self.mask = new_capacity - 1;
-        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
-        self.size = 0;
+        self.resize_threshold = resize_threshold(new_capacity, self.load_factor);
+    }
 
-        for (key, value) in old_keys.into_iter().zip(old_values.into_iter()) {
-            if key != MISSING_VALUE {
-                self.insert(key, value);
+    /// Moves the live entries in up to `slots` slots of the old table into the current one.
+    fn migrate(&mut self, slots: usize) {
+        let Some(mut migration) = self.migration.take() else {
+            return;
+        };
+
+        let end = migration.next_index.saturating_add(slots).min(migration.capacity());
+        for old_index in migration.next_index..end {
+            if migration.is_live(old_index) {
+                let key = migration.keys[old_index];
+                let (index, _) = self.find_index(key);
+                self.keys[index] = key;
+                self.values[index] = mem::take(&mut migration.values[old_index]);
+                migration.mark_moved(old_index);
             }
         }
+        migration.next_index = end;
+
+        if !migration.is_complete() {
+            self.migration = Some(migration);
+        }
+    }
 
-        debug_assert_eq!(self.size, old_size);
+    fn finish_migration(&mut self) {
+        self.migrate(usize::MAX);
+    }
+
+    fn rehash(&mut self, new_capacity: usize) {
+        self.finish_migration();
+
+        let old_keys = mem::replace(&mut self.keys, vec![MISSING_VALUE; new_capacity]);
+        let old_values = mem::replace(&mut self.values, vec![V::default(); new_capacity]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = resize_threshold(new_capacity, self.load_factor);
+
+        for (key, value) in old_keys.into_iter().zip(old_values) {
+            if key != MISSING_VALUE {
+                let (index, _) = self.find_index(key);
+                self.keys[index] = key;
+                self.values[index] = value;
+            }
+        }
     }
 
     fn compact_chain(&mut self, deleted_index: usize) {
@@ -159,7 +399,7 @@ impl<V: Clone + Default> IntHashMap<V> {
             let key = self.keys[index];
             let ideal_index = Self::hash_key(key) & self.mask;
 
-            if self.should_move_entry(deleted_index, index, ideal_index) {
+            if should_move_entry(deleted_index, index, ideal_index) {
                 self.keys[deleted_index] = key;
                 self.values[deleted_index] = mem::take(&mut self.values[index]);
                 self.keys[index] = MISSING_VALUE;
@@ -172,15 +412,6 @@ impl<V: Clone + Default> IntHashMap<V> {
         }
     }
 
-    #[inline]
-    fn should_move_entry(&self, deleted_index: usize, current_index: usize, ideal_index: usize) -> bool {
-        if deleted_index < current_index {
-            ideal_index <= deleted_index || ideal_index > current_index
-        } else {
-            ideal_index <= deleted_index && ideal_index > current_index
-        }
-    }
-
     pub fn iter(&self) -> IntHashMapIter<'_, V> {
         IntHashMapIter {
             map: self,
@@ -201,6 +432,114 @@ impl<V: Clone + Default> IntHashMap<V> {
             index: 0,
         }
     }
+
+    pub fn iter_mut(&mut self) -> IntHashMapIterMut<'_, V> {
+        self.finish_migration();
+        IntHashMapIterMut {
+            keys: self.keys.iter(),
+            values: self.values.iter_mut(),
+            missing_key_value: self.missing_key_value.as_mut(),
+        }
+    }
+
+    /// Entry for `key`, for inspecting and updating it with a single probe.
+    pub fn entry(&mut self, key: i32) -> IntHashMapEntry<'_, V> {
+        if key == MISSING_VALUE {
+            return if self.missing_key_value.is_some() {
+                IntHashMapEntry::Occupied(IntHashMapOccupiedEntry { map: self, key, index: MISSING_KEY_INDEX })
+            } else {
+                IntHashMapEntry::Vacant(IntHashMapVacantEntry { map: self, key, index: MISSING_KEY_INDEX })
+            };
+        }
+
+        // Resize up front so a vacant slot stays valid until it is filled. Without resizing the
+        // vacant entry checks the limit itself when inserting.
+        if self.size >= self.resize_threshold && self.resize_enabled {
+            self.expand();
+        }
+
+        self.migrate(self.migration_batch);
+        self.promote(key);
+
+        let (index, found) = self.find_index(key);
+        if found {
+            IntHashMapEntry::Occupied(IntHashMapOccupiedEntry { map: self, key, index })
+        } else {
+            IntHashMapEntry::Vacant(IntHashMapVacantEntry { map: self, key, index })
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns `true`, visiting each entry exactly once.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(i32, &mut V) -> bool,
+    {
+        let mut cursor = self.cursor();
+        while let Some(key) = cursor.next() {
+            let value = cursor.value_mut().expect("cursor is positioned on an entry");
+            if !f(key, value) {
+                cursor.remove();
+            }
+        }
+    }
+
+    /// Iterates keys in table order with access to the current value and the ability to
+    /// [`remove`](IntHashMapCursor::remove) the entry, visiting each entry exactly once.
+    pub fn cursor(&mut self) -> IntHashMapCursor<'_, V> {
+        self.finish_migration();
+
+        // Start just after an empty slot so no chain wraps past the starting point. Removal then
+        // only pulls entries back from further along the sweep, so a refilled slot is re-examined
+        // rather than skipped.
+        let (index, remaining) = match self.keys.iter().position(|&key| key == MISSING_VALUE) {
+            Some(empty_index) if self.size > 0 => ((empty_index + 1) & self.mask, self.capacity() - 1),
+            _ => (0, 0),
+        };
+
+        IntHashMapCursor {
+            yield_missing_key: self.missing_key_value.is_some(),
+            map: self,
+            index,
+            remaining,
+            current: None,
+        }
+    }
+
+    /// Removes and yields every entry, leaving the map empty but keeping its capacity.
+    pub fn drain(&mut self) -> IntHashMapDrain<'_, V> {
+        self.finish_migration();
+        IntHashMapDrain {
+            missing_key_value: self.missing_key_value.take(),
+            map: self,
+            index: 0,
+        }
+    }
+
+    /// Moves `key` out of a table still being migrated so it can be addressed in the current one.
+    fn promote(&mut self, key: i32) {
+        let Some(migration) = self.migration.as_mut() else {
+            return;
+        };
+        let Some(old_index) = migration.find(key) else {
+            return;
+        };
+
+        migration.mark_moved(old_index);
+        let value = mem::take(&mut migration.values[old_index]);
+        let (index, _) = self.find_index(key);
+        self.keys[index] = key;
+        self.values[index] = value;
+    }
+
+    fn remove_at(&mut self, index: usize) -> V {
+        let old_value = mem::take(&mut self.values[index]);
+        self.keys[index] = MISSING_VALUE;
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        old_value
+    }
 }
 
 impl<V: Clone + Default> Default for IntHashMap<V> {
@@ -209,6 +548,33 @@ impl<V: Clone + Default> Default for IntHashMap<V> {
     }
 }
 
+impl<V: Clone + Default + PartialEq> PartialEq for IntHashMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<V: Clone + Default + Eq> Eq for IntHashMap<V> {}
+
+impl<V: Hash> Hash for IntHashMap<V> {
+    /// Sums per-entry hashes so that maps equal under `==` hash alike whatever their capacity or
+    /// insertion order. Each entry mixes its key and value hashes non-linearly, since a plain sum
+    /// would not change when values are swapped between keys.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut position = 0;
+        let mut sum = 0u64;
+        while let Some((key, value)) = self.next_entry(&mut position) {
+            let mut value_hasher = FastHasher::new();
+            value.hash(&mut value_hasher);
+            let entry_hash = ((fast_int_hash(key) as u64) << 32) ^ value_hasher.finish();
+            sum = sum.wrapping_add(fast_long_hash(entry_hash as i64) as u64);
+        }
+
+        state.write_usize(self.len());
+        state.write_u64(sum);
+    }
+}
+
 pub struct IntHashMapIter<'a, V> {
     map: &'a IntHashMap<V>,
     index: usize,
@@ -218,20 +584,11 @@ impl<'a, V> Iterator for IntHashMapIter<'a, V> {
     type Item = (i32, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < self.map.keys.len() {
-            let key = self.map.keys[self.index];
-            if key != MISSING_VALUE {
-                let value = &self.map.values[self.index];
-                self.index += 1;
-                return Some((key, value));
-            }
-            self.index += 1;
-        }
-        None
+        self.map.next_entry(&mut self.index)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.map.size))
+        (0, Some(self.map.len()))
     }
 }
 
@@ -244,19 +601,11 @@ impl<'a, V> Iterator for IntHashMapKeys<'a, V> {
     type Item = i32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < self.map.keys.len() {
-            let key = self.map.keys[self.index];
-            if key != MISSING_VALUE {
-                self.index += 1;
-                return Some(key);
-            }
-            self.index += 1;
-        }
-        None
+        self.map.next_entry(&mut self.index).map(|(key, _)| key)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.map.size))
+        (0, Some(self.map.len()))
     }
 }
 
@@ -269,26 +618,363 @@ impl<'a, V> Iterator for IntHashMapValues<'a, V> {
     type Item = &'a V;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < self.map.keys.len() {
-            let key = self.map.keys[self.index];
+        self.map.next_entry(&mut self.index).map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.map.len()))
+    }
+}
+
+pub struct IntHashMapIterMut<'a, V> {
+    keys: core::slice::Iter<'a, i32>,
+    values: core::slice::IterMut<'a, V>,
+    missing_key_value: Option<&'a mut V>,
+}
+
+impl<'a, V> Iterator for IntHashMapIterMut<'a, V> {
+    type Item = (i32, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (&key, value) in (&mut self.keys).zip(&mut self.values) {
+            if key != MISSING_VALUE {
+                return Some((key, value));
+            }
+        }
+
+        self.missing_key_value.take().map(|value| (MISSING_VALUE, value))
+    }
+}
+
+pub struct IntHashMapIntoIter<V> {
+    keys: std::vec::IntoIter<i32>,
+    values: std::vec::IntoIter<V>,
+    migration: Option<(Migration<()>, core::iter::Enumerate<std::vec::IntoIter<V>>)>,
+    missing_key_value: Option<V>,
+    remaining: usize,
+}
+
+impl<V> Iterator for IntHashMapIntoIter<V> {
+    type Item = (i32, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (key, value) in (&mut self.keys).zip(&mut self.values) {
             if key != MISSING_VALUE {
-                let value = &self.map.values[self.index];
-                self.index += 1;
-                return Some(value);
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+
+        if let Some((migration, values)) = self.migration.as_mut() {
+            for (index, value) in values {
+                if migration.is_live(index) {
+                    self.remaining -= 1;
+                    return Some((migration.keys[index], value));
+                }
             }
+        }
+
+        let value = self.missing_key_value.take()?;
+        self.remaining -= 1;
+        Some((MISSING_VALUE, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for IntHashMapIntoIter<V> {}
+
+pub struct IntHashMapDrain<'a, V: Clone + Default> {
+    map: &'a mut IntHashMap<V>,
+    index: usize,
+    missing_key_value: Option<V>,
+}
+
+impl<V: Clone + Default> Iterator for IntHashMapDrain<'_, V> {
+    type Item = (i32, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.map.keys.len() {
+            let index = self.index;
             self.index += 1;
+
+            let key = mem::replace(&mut self.map.keys[index], MISSING_VALUE);
+            if key != MISSING_VALUE {
+                self.map.size -= 1;
+                return Some((key, mem::take(&mut self.map.values[index])));
+            }
         }
-        None
+
+        self.missing_key_value.take().map(|value| (MISSING_VALUE, value))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.map.size))
+        let remaining = self.map.size + self.missing_key_value.is_some() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<V: Clone + Default> Drop for IntHashMapDrain<'_, V> {
+    fn drop(&mut self) {
+        if self.map.size > 0 {
+            self.map.clear();
+        }
+    }
+}
+
+/// Iterator over the keys of an [`IntHashMap`] that can access the current value and remove
+/// the current entry.
+pub struct IntHashMapCursor<'a, V: Clone + Default> {
+    map: &'a mut IntHashMap<V>,
+    index: usize,
+    remaining: usize,
+    yield_missing_key: bool,
+    current: Option<usize>,
+}
+
+impl<V: Clone + Default> IntHashMapCursor<'_, V> {
+    /// Value of the entry last returned by `next`, if it has not been removed.
+    pub fn value(&self) -> Option<&V> {
+        match self.current? {
+            MISSING_KEY_INDEX => self.map.missing_key_value.as_ref(),
+            index => Some(&self.map.values[index]),
+        }
+    }
+
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        match self.current? {
+            MISSING_KEY_INDEX => self.map.missing_key_value.as_mut(),
+            index => Some(&mut self.map.values[index]),
+        }
+    }
+
+    /// Removes the entry last returned by `next`, returning its value, or `None` if there is no
+    /// such entry or it was already removed.
+    pub fn remove(&mut self) -> Option<V> {
+        match self.current.take()? {
+            MISSING_KEY_INDEX => self.map.missing_key_value.take(),
+            index => {
+                let value = self.map.remove_at(index);
+                // Compaction may have pulled an entry not yet visited into this slot
+                self.index = index;
+                self.remaining += 1;
+                Some(value)
+            }
+        }
+    }
+}
+
+impl<V: Clone + Default> Iterator for IntHashMapCursor<'_, V> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let index = self.index;
+            self.index = (index + 1) & self.map.mask;
+            self.remaining -= 1;
+
+            let key = self.map.keys[index];
+            if key != MISSING_VALUE {
+                self.current = Some(index);
+                return Some(key);
+            }
+        }
+
+        if mem::take(&mut self.yield_missing_key) {
+            self.current = Some(MISSING_KEY_INDEX);
+            return Some(MISSING_VALUE);
+        }
+        self.current = None;
+        None
+    }
+}
+
+pub enum IntHashMapEntry<'a, V: Clone + Default> {
+    Occupied(IntHashMapOccupiedEntry<'a, V>),
+    Vacant(IntHashMapVacantEntry<'a, V>),
+}
+
+impl<'a, V: Clone + Default> IntHashMapEntry<'a, V> {
+    pub fn key(&self) -> i32 {
+        match self {
+            Self::Occupied(entry) => entry.key,
+            Self::Vacant(entry) => entry.key,
+        }
+    }
+
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(value),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+pub struct IntHashMapOccupiedEntry<'a, V: Clone + Default> {
+    map: &'a mut IntHashMap<V>,
+    key: i32,
+    index: usize,
+}
+
+impl<'a, V: Clone + Default> IntHashMapOccupiedEntry<'a, V> {
+    pub fn key(&self) -> i32 {
+        self.key
+    }
+
+    pub fn get(&self) -> &V {
+        if self.index == MISSING_KEY_INDEX {
+            self.map.missing_key_value.as_ref().expect("entry is occupied")
+        } else {
+            &self.map.values[self.index]
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        if self.index == MISSING_KEY_INDEX {
+            self.map.missing_key_value.as_mut().expect("entry is occupied")
+        } else {
+            &mut self.map.values[self.index]
+        }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        if self.index == MISSING_KEY_INDEX {
+            self.map.missing_key_value.as_mut().expect("entry is occupied")
+        } else {
+            &mut self.map.values[self.index]
+        }
+    }
+
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        if self.index == MISSING_KEY_INDEX {
+            self.map.missing_key_value.take().expect("entry is occupied")
+        } else {
+            self.map.remove_at(self.index)
+        }
+    }
+}
+
+pub struct IntHashMapVacantEntry<'a, V: Clone + Default> {
+    map: &'a mut IntHashMap<V>,
+    key: i32,
+    index: usize,
+}
+
+impl<'a, V: Clone + Default> IntHashMapVacantEntry<'a, V> {
+    pub fn key(&self) -> i32 {
+        self.key
+    }
+
+    /// Inserts `value`, panicking if the map is at its load limit with resizing disabled.
+    pub fn insert(self, value: V) -> &'a mut V {
+        match self.try_insert(value) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    pub fn try_insert(self, value: V) -> Result<&'a mut V> {
+        if self.index == MISSING_KEY_INDEX {
+            return Ok(self.map.missing_key_value.insert(value));
+        }
+
+        if self.map.size >= self.map.resize_threshold {
+            return Err(resize_disabled_error(self.map.capacity(), self.map.len()));
+        }
+
+        self.map.keys[self.index] = self.key;
+        self.map.values[self.index] = value;
+        self.map.size += 1;
+        Ok(&mut self.map.values[self.index])
+    }
+}
+
+impl<V> IntoIterator for IntHashMap<V> {
+    type Item = (i32, V);
+    type IntoIter = IntHashMapIntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let remaining = self.len();
+        let migration = self.migration.map(|migration| {
+            let (liveness, values) = migration.split_values();
+            (liveness, values.into_iter().enumerate())
+        });
+
+        IntHashMapIntoIter {
+            keys: self.keys.into_iter(),
+            values: self.values.into_iter(),
+            migration,
+            missing_key_value: self.missing_key_value,
+            remaining,
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a IntHashMap<V> {
+    type Item = (i32, &'a V);
+    type IntoIter = IntHashMapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntHashMapIter {
+            map: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, V: Clone + Default> IntoIterator for &'a mut IntHashMap<V> {
+    type Item = (i32, &'a mut V);
+    type IntoIter = IntHashMapIterMut<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<V: Clone + Default> Extend<(i32, V)> for IntHashMap<V> {
+    fn extend<I: IntoIterator<Item = (i32, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V: Clone + Default> FromIterator<(i32, V)> for IntHashMap<V> {
+    fn from_iter<I: IntoIterator<Item = (i32, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use agrona_core::error::AgronaError;
 
     #[test]
     fn test_basic_operations() {
@@ -345,4 +1031,236 @@ mod tests {
         let count = map.iter().count();
         assert_eq!(count, 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_min_key() {
+        let mut map = IntHashMap::new();
+
+        assert_eq!(map.insert(i32::MIN, 1), None);
+        assert_eq!(map.insert(0, 2), None);
+        assert_eq!(map.insert(i32::MIN, 3), Some(1));
+        assert_eq!(map.len(), 2);
+        assert!(map.contains_key(i32::MIN));
+
+        *map.get_mut(i32::MIN).unwrap() += 1;
+        let mut entries: Vec<_> = map.iter().map(|(key, &value)| (key, value)).collect();
+        entries.sort();
+        assert_eq!(entries, vec![(i32::MIN, 4), (0, 2)]);
+
+        assert_eq!(map.remove(i32::MIN), Some(4));
+        assert_eq!(map.get(i32::MIN), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_entry_api() {
+        let mut map: IntHashMap<u64> = IntHashMap::new();
+
+        *map.entry(1).or_insert(10) += 1;
+        *map.entry(1).or_insert(10) += 1;
+        map.entry(2).and_modify(|value| *value = 0).or_insert_with(|| 20);
+        map.entry(2).and_modify(|value| *value += 1).or_default();
+        *map.entry(i32::MIN).or_default() += 5;
+        assert_eq!(map.get(1), Some(&12));
+        assert_eq!(map.get(2), Some(&21));
+        assert_eq!(map.get(i32::MIN), Some(&5));
+
+        match map.entry(2) {
+            IntHashMapEntry::Occupied(entry) => assert_eq!(entry.remove(), 21),
+            IntHashMapEntry::Vacant(_) => panic!("expected occupied entry"),
+        }
+        match map.entry(i32::MIN) {
+            IntHashMapEntry::Occupied(mut entry) => assert_eq!(entry.insert(6), 5),
+            IntHashMapEntry::Vacant(_) => panic!("expected occupied entry"),
+        }
+        assert_eq!(map.entry(3).key(), 3);
+        assert_eq!(map.len(), 2);
+
+        for key in 0..100 {
+            *map.entry(key).or_default() += 1;
+        }
+        assert_eq!(map.len(), 101);
+        assert_eq!(map.get(1), Some(&13));
+    }
+
+    #[test]
+    fn test_retain_visits_each_entry_once() {
+        // Dense keys in a small table produce long chains that wrap around the end
+        let mut map: IntHashMap<i32> = (0..1000).map(|key| (key, 0)).collect();
+        map.insert(i32::MIN, 0);
+
+        map.retain(|key, visits| {
+            *visits += 1;
+            key % 3 != 0
+        });
+
+        assert_eq!(map.len(), 667);
+        assert!(map.iter().all(|(key, &visits)| key % 3 != 0 && visits == 1));
+        for key in 0..1000 {
+            assert_eq!(map.contains_key(key), key % 3 != 0);
+        }
+    }
+
+    #[test]
+    fn test_drain_and_into_iter() {
+        let mut map: IntHashMap<String> = IntHashMap::new();
+        map.extend((0..20).map(|key| (key, key.to_string())));
+        map.insert(i32::MIN, "min".to_string());
+
+        for (_, value) in &mut map {
+            value.push('!');
+        }
+
+        let mut drained: Vec<_> = map.drain().collect();
+        drained.sort();
+        assert_eq!(drained.len(), 21);
+        assert_eq!(drained[0], (i32::MIN, "min!".to_string()));
+        assert!(map.is_empty());
+
+        map.insert(7, "seven".to_string());
+        drop(map.drain());
+        assert!(map.is_empty() && map.get(7).is_none());
+
+        map.insert(8, "eight".to_string());
+        let owned: Vec<_> = map.into_iter().collect();
+        assert_eq!(owned, vec![(8, "eight".to_string())]);
+    }
+
+    #[test]
+    fn test_load_factor_and_resize_control() {
+        assert!(IntHashMap::<i32>::with_capacity_and_load_factor(16, 0.95).is_err());
+
+        let mut map = IntHashMap::with_capacity_and_load_factor(100, 0.5).unwrap();
+        assert_eq!(map.capacity(), 256);
+        assert_eq!(map.load_factor(), 0.5);
+
+        map.set_resize_enabled(false);
+        for key in 0..128 {
+            assert_eq!(map.try_insert(key, key).unwrap(), None);
+        }
+        assert!(matches!(map.try_insert(128, 0), Err(AgronaError::IllegalState(_))));
+        assert_eq!(map.try_insert(127, 1).unwrap(), Some(127));
+        assert_eq!(map.try_insert(i32::MIN, 0).unwrap(), None);
+        match map.entry(500) {
+            IntHashMapEntry::Vacant(entry) => assert!(entry.try_insert(0).is_err()),
+            IntHashMapEntry::Occupied(_) => panic!("expected vacant entry"),
+        }
+        assert_eq!(map.capacity(), 256);
+
+        map.reserve(100);
+        assert_eq!(map.capacity(), 512);
+        for key in 128..228 {
+            map.try_insert(key, key).unwrap();
+        }
+        assert_eq!(map.len(), 229);
+    }
+
+    #[test]
+    fn test_shrink_to_fit_and_compact() {
+        let mut map = IntHashMap::new();
+        for key in 0..10_000 {
+            map.insert(key, key);
+        }
+        map.retain(|key, _| key < 10);
+        assert_eq!(map.capacity(), 16384);
+
+        map.shrink_to_fit();
+        assert_eq!(map.capacity(), 16);
+        map.compact();
+        assert_eq!(map.capacity(), 16);
+        for key in 0..10_000 {
+            assert_eq!(map.get(key).copied(), (key < 10).then_some(key));
+        }
+    }
+
+    #[test]
+    fn test_incremental_resize_keeps_entries_reachable() {
+        let mut map = IntHashMap::new();
+        map.set_incremental_resize(true);
+
+        let mut saw_migration = false;
+        for key in 0..10_000 {
+            map.insert(key, key);
+            saw_migration |= map.is_migrating();
+
+            if map.is_migrating() {
+                // Every key must be reachable, whichever table it currently lives in
+                assert_eq!(map.get(key / 2), Some(&(key / 2)));
+                assert_eq!(map.len(), key as usize + 1);
+            }
+        }
+        assert!(saw_migration);
+
+        // Updates and removals of entries still in the old table
+        while !map.is_migrating() {
+            map.insert(map.len() as i32, 0);
+        }
+        let old_key = (0..map.len() as i32).find(|&key| map.get(key) == Some(&key) && key % 7 == 0).unwrap();
+        assert_eq!(map.remove(old_key), Some(old_key));
+        assert_eq!(map.insert(old_key + 1, -1), Some(old_key + 1));
+        *map.entry(old_key + 2).or_default() += 1;
+        assert_eq!(map.get(old_key + 2), Some(&(old_key + 3)));
+        assert_eq!(map.iter().count(), map.len());
+        assert!(!map.contains_key(old_key));
+
+        let expected_len = map.len();
+        let owned: Vec<_> = map.into_iter().collect();
+        assert_eq!(owned.len(), expected_len);
+    }
+
+    #[test]
+    fn test_cursor_removal_and_order_independent_equality() {
+        use std::collections::hash_map::DefaultHasher;
+
+        // Colliding keys so that removals shift entries back under the cursor
+        let mut map: IntHashMap<i32> = (0..300).map(|i| (i << 20, i)).chain([(i32::MIN, -1)]).collect();
+
+        let mut visited = 0;
+        let mut cursor = map.cursor();
+        while let Some(key) = cursor.next() {
+            visited += 1;
+            let value = *cursor.value().unwrap();
+            if value % 2 == 0 || key == i32::MIN {
+                assert_eq!(cursor.remove(), Some(value));
+                assert_eq!(cursor.remove(), None);
+                assert!(cursor.value().is_none());
+            } else {
+                *cursor.value_mut().unwrap() *= 10;
+            }
+        }
+        assert_eq!(visited, 301);
+        assert_eq!(map.len(), 150);
+        assert!(map.iter().all(|(key, &value)| value == (key >> 20) * 10 && value % 20 == 10));
+
+        let mut reordered = IntHashMap::with_capacity(1024);
+        for i in (0..300).rev().filter(|i| i % 2 == 1) {
+            reordered.insert(i << 20, i * 10);
+        }
+        assert!(map == reordered);
+
+        let hash = |map: &IntHashMap<i32>| {
+            let mut hasher = DefaultHasher::new();
+            map.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&map), hash(&reordered));
+
+        reordered.insert(1 << 20, 0);
+        assert!(map != reordered);
+    }
+
+    #[test]
+    fn test_hash_depends_on_which_key_holds_which_value() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |entries: &[(i32, i32)]| {
+            let mut hasher = DefaultHasher::new();
+            entries.iter().copied().collect::<IntHashMap<i32>>().hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_ne!(hash(&[(1, 10), (2, 20)]), hash(&[(1, 20), (2, 10)]));
+        assert_ne!(hash(&[(1, 10), (2, 20), (3, 30)]), hash(&[(1, 30), (2, 10), (3, 20)]));
+        assert_eq!(hash(&[(1, 10), (2, 20)]), hash(&[(2, 20), (1, 10)]));
+    }
+}
@@ -1,13 +1,33 @@
+use crate::collection_util::{
+    capacity_for, resize_disabled_error, resize_threshold, should_move_entry, validate_load_factor,
+};
 use crate::hashing::{fast_int_hash, mix_hash};
+use crate::incremental_resize::{Migration, DEFAULT_MIGRATION_BATCH};
+use agrona_core::error::Result;
+use core::hash::{Hash, Hasher};
 
 const MISSING_VALUE: i32 = i32::MIN;
+const MISSING_VALUE_INDEX: usize = usize::MAX;
 const MIN_CAPACITY: usize = 8;
 const DEFAULT_LOAD_FACTOR: f32 = 0.67;
 
+/// Open-addressing set of `i32`.
+///
+/// `i32::MIN` marks empty slots in the table, so its membership is tracked out-of-band.
+///
+/// As with [`IntHashMap`](crate::IntHashMap), growth can be disabled so that
+/// [`try_insert`](Self::try_insert) errors rather than rehashing, or made incremental so that
+/// growth is spread across subsequent operations.
 pub struct IntHashSet {
     keys: Vec<i32>,
+    contains_missing_value: bool,
+    migration: Option<Migration<()>>,
     size: usize,
+    load_factor: f32,
     resize_threshold: usize,
+    resize_enabled: bool,
+    incremental_resize: bool,
+    migration_batch: usize,
     mask: usize,
 }
 
@@ -17,25 +37,88 @@ impl IntHashSet {
     }
 
     pub fn with_capacity(initial_capacity: usize) -> Self {
-        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
-        let resize_threshold = (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+        Self::with_table(initial_capacity.max(MIN_CAPACITY).next_power_of_two(), DEFAULT_LOAD_FACTOR)
+    }
+
+    /// Set sized to hold `initial_capacity` values without resizing at the given load factor,
+    /// which must be within `0.1..=0.9`.
+    pub fn with_capacity_and_load_factor(initial_capacity: usize, load_factor: f32) -> Result<Self> {
+        validate_load_factor(load_factor)?;
+        Ok(Self::with_table(capacity_for(initial_capacity, load_factor, MIN_CAPACITY), load_factor))
+    }
 
+    fn with_table(capacity: usize, load_factor: f32) -> Self {
         Self {
             keys: vec![MISSING_VALUE; capacity],
+            contains_missing_value: false,
+            migration: None,
             size: 0,
-            resize_threshold,
+            load_factor,
+            resize_threshold: resize_threshold(capacity, load_factor),
+            resize_enabled: true,
+            incremental_resize: false,
+            migration_batch: DEFAULT_MIGRATION_BATCH,
             mask: capacity - 1,
         }
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.size
+        self.size + self.contains_missing_value as usize
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.size == 0
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn load_factor(&self) -> f32 {
+        self.load_factor
+    }
+
+    #[inline]
+    pub fn is_resize_enabled(&self) -> bool {
+        self.resize_enabled
+    }
+
+    /// Enables or disables automatic growth. Explicit [`reserve`](Self::reserve) and
+    /// [`shrink_to_fit`](Self::shrink_to_fit) calls still resize.
+    pub fn set_resize_enabled(&mut self, resize_enabled: bool) {
+        self.resize_enabled = resize_enabled;
+    }
+
+    #[inline]
+    pub fn is_incremental_resize(&self) -> bool {
+        self.incremental_resize
+    }
+
+    /// Grows by migrating a bounded number of slots per mutating operation instead of
+    /// rehashing everything at once.
+    ///
+    /// Only the rehash is spread out. The doubled table is still allocated and filled with empty
+    /// slots by the operation that crosses the load factor, so that call costs a write per new
+    /// slot, which is far cheaper than rehashing every value but is not free.
+    pub fn set_incremental_resize(&mut self, incremental_resize: bool) {
+        self.incremental_resize = incremental_resize;
+    }
+
+    #[inline]
+    pub fn migration_batch(&self) -> usize {
+        self.migration_batch
+    }
+
+    /// Slots of the old table migrated per mutating operation during an incremental resize, at
+    /// least one. Smaller batches bound each operation more tightly but keep both tables alive
+    /// for longer.
+    pub fn set_migration_batch(&mut self, migration_batch: usize) {
+        self.migration_batch = migration_batch.max(1);
+    }
+
+    /// Whether values are still being migrated from a smaller table.
+    #[inline]
+    pub fn is_migrating(&self) -> bool {
+        self.migration.is_some()
     }
 
     #[inline]
@@ -65,62 +148,166 @@ impl IntHashSet {
     }
 
     pub fn contains(&self, key: i32) -> bool {
-        self.find_index(key).1
+        if key == MISSING_VALUE {
+            return self.contains_missing_value;
+        }
+
+        self.find_index(key).1 || self.migration.as_ref().is_some_and(|migration| migration.find(key).is_some())
     }
 
+    /// Adds `key`, returning `true` if it was not already present.
+    ///
+    /// Panics if a new key would need a resize while resizing is disabled; use
+    /// [`try_insert`](Self::try_insert) to handle that case.
     pub fn insert(&mut self, key: i32) -> bool {
-        if self.size >= self.resize_threshold {
-            self.resize();
+        match self.try_insert(key) {
+            Ok(inserted) => inserted,
+            Err(error) => panic!("{}", error),
         }
+    }
 
-        let (index, found) = self.find_index(key);
+    /// Adds `key`, returning `true` if it was not already present, or errors with
+    /// `IllegalState` if a new key would need a resize while resizing is disabled.
+    pub fn try_insert(&mut self, key: i32) -> Result<bool> {
+        if key == MISSING_VALUE {
+            return Ok(!core::mem::replace(&mut self.contains_missing_value, true));
+        }
 
-        if !found {
-            self.keys[index] = key;
-            self.size += 1;
-            true
-        } else {
-            false
+        self.migrate(self.migration_batch);
+        let (mut index, found) = self.find_index(key);
+        if found || self.migration.as_ref().is_some_and(|migration| migration.find(key).is_some()) {
+            return Ok(false);
+        }
+
+        if self.size >= self.resize_threshold {
+            self.grow()?;
+            index = self.find_index(key).0;
         }
+
+        self.keys[index] = key;
+        self.size += 1;
+        Ok(true)
     }
 
     pub fn remove(&mut self, key: i32) -> bool {
+        if key == MISSING_VALUE {
+            return core::mem::replace(&mut self.contains_missing_value, false);
+        }
+
+        self.migrate(self.migration_batch);
+        if let Some(migration) = self.migration.as_mut() {
+            if let Some(index) = migration.find(key) {
+                migration.mark_moved(index);
+                self.size -= 1;
+                return true;
+            }
+        }
+
         let (index, found) = self.find_index(key);
 
         if !found {
             return false;
         }
 
+        self.remove_at(index);
+        true
+    }
+
+    fn remove_at(&mut self, index: usize) {
         self.keys[index] = MISSING_VALUE;
         self.size -= 1;
 
         self.compact_chain(index);
-
-        true
     }
 
     pub fn clear(&mut self) {
         self.keys.fill(MISSING_VALUE);
+        self.contains_missing_value = false;
+        self.migration = None;
         self.size = 0;
     }
 
-    fn resize(&mut self) {
-        let old_keys = std::mem::take(&mut self.keys);
-        let old_size = self.size;
+    /// Grows the table so `additional` more values fit without a resize.
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = capacity_for(self.size.saturating_add(additional), self.load_factor, self.capacity());
+        if capacity != self.capacity() {
+            self.rehash(capacity);
+        }
+    }
 
-        let new_capacity = old_keys.len() * 2;
-        self.keys = vec![MISSING_VALUE; new_capacity];
+    /// Shrinks the table to the smallest capacity that holds the current values within the
+    /// load factor.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = capacity_for(self.size, self.load_factor, MIN_CAPACITY);
+        if capacity != self.capacity() {
+            self.rehash(capacity);
+        }
+    }
+
+    /// Rehashes into a table sized for the current values, as Agrona's `compact` does.
+    pub fn compact(&mut self) {
+        self.rehash(capacity_for(self.size, self.load_factor, MIN_CAPACITY));
+    }
+
+    fn grow(&mut self) -> Result<()> {
+        if !self.resize_enabled {
+            return Err(resize_disabled_error(self.capacity(), self.len()));
+        }
+
+        self.finish_migration();
+        let new_capacity = self.capacity() * 2;
+        if !self.incremental_resize {
+            self.rehash(new_capacity);
+            return Ok(());
+        }
+
+        let old_keys = core::mem::replace(&mut self.keys, vec![MISSING_VALUE; new_capacity]);
+        let old_length = old_keys.len();
+        self.migration = Some(Migration::new(old_keys, vec![(); old_length]));
         self.mask = new_capacity - 1;
-        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
-        self.size = 0;
+        self.resize_threshold = resize_threshold(new_capacity, self.load_factor);
+        Ok(())
+    }
+
+    /// Moves the live values in up to `slots` slots of the old table into the current one.
+    fn migrate(&mut self, slots: usize) {
+        let Some(mut migration) = self.migration.take() else {
+            return;
+        };
+
+        let end = migration.next_index.saturating_add(slots).min(migration.capacity());
+        for old_index in migration.next_index..end {
+            if migration.is_live(old_index) {
+                let key = migration.keys[old_index];
+                let (index, _) = self.find_index(key);
+                self.keys[index] = key;
+                migration.mark_moved(old_index);
+            }
+        }
+        migration.next_index = end;
+
+        if !migration.is_complete() {
+            self.migration = Some(migration);
+        }
+    }
+
+    fn finish_migration(&mut self) {
+        self.migrate(usize::MAX);
+    }
+
+    fn rehash(&mut self, new_capacity: usize) {
+        self.finish_migration();
+
+        let old_keys = core::mem::replace(&mut self.keys, vec![MISSING_VALUE; new_capacity]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = resize_threshold(new_capacity, self.load_factor);
 
         for key in old_keys {
             if key != MISSING_VALUE {
-                self.insert(key);
+                let (index, _) = self.find_index(key);
+                self.keys[index] = key;
             }
         }
-
-        debug_assert_eq!(self.size, old_size);
     }
 
     fn compact_chain(&mut self, deleted_index: usize) {
@@ -130,7 +317,7 @@ impl IntHashSet {
             let key = self.keys[index];
             let ideal_index = Self::hash_key(key) & self.mask;
 
-            if self.should_move_entry(deleted_index, index, ideal_index) {
+            if should_move_entry(deleted_index, index, ideal_index) {
                 self.keys[deleted_index] = key;
                 self.keys[index] = MISSING_VALUE;
 
@@ -142,21 +329,70 @@ impl IntHashSet {
         }
     }
 
-    #[inline]
-    fn should_move_entry(&self, deleted_index: usize, current_index: usize, ideal_index: usize) -> bool {
-        if deleted_index < current_index {
-            ideal_index <= deleted_index || ideal_index > current_index
-        } else {
-            ideal_index <= deleted_index && ideal_index > current_index
-        }
-    }
-
     pub fn iter(&self) -> IntHashSetIter<'_> {
         IntHashSetIter {
             set: self,
             index: 0,
         }
     }
+
+    /// Iterates in table order with the ability to [`remove`](IntHashSetCursor::remove) the
+    /// value last returned.
+    pub fn cursor(&mut self) -> IntHashSetCursor<'_> {
+        self.finish_migration();
+
+        // Start just after an empty slot so no chain wraps past the starting point. Removal then
+        // only pulls values back from further along the sweep, so a refilled slot is re-examined
+        // rather than skipped.
+        let (index, remaining) = match self.keys.iter().position(|&key| key == MISSING_VALUE) {
+            Some(empty_index) if self.size > 0 => ((empty_index + 1) & self.mask, self.capacity() - 1),
+            _ => (0, 0),
+        };
+
+        IntHashSetCursor {
+            yield_missing_value: self.contains_missing_value,
+            set: self,
+            index,
+            remaining,
+            current: None,
+        }
+    }
+
+    /// Keeps only the values for which `f` returns `true`.
+    pub fn retain<F: FnMut(i32) -> bool>(&mut self, mut f: F) {
+        let mut cursor = self.cursor();
+        while let Some(value) = cursor.next() {
+            if !f(value) {
+                cursor.remove();
+            }
+        }
+    }
+
+    /// Keeps only the values also in `other`, returning `true` if any were removed.
+    pub fn retain_all(&mut self, other: &IntHashSet) -> bool {
+        let len = self.len();
+        self.retain(|value| other.contains(value));
+        self.len() != len
+    }
+
+    /// Values in either set, each once.
+    pub fn union<'a>(&'a self, other: &'a IntHashSet) -> impl Iterator<Item = i32> + 'a {
+        self.iter().chain(other.difference(self))
+    }
+
+    /// Values in both sets.
+    pub fn intersection<'a>(&'a self, other: &'a IntHashSet) -> impl Iterator<Item = i32> + 'a {
+        self.iter().filter(move |&value| other.contains(value))
+    }
+
+    /// Values in this set but not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a IntHashSet) -> impl Iterator<Item = i32> + 'a {
+        self.iter().filter(move |&value| !other.contains(value))
+    }
+
+    pub fn is_subset(&self, other: &IntHashSet) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
 }
 
 impl Default for IntHashSet {
@@ -165,6 +401,57 @@ impl Default for IntHashSet {
     }
 }
 
+impl core::fmt::Debug for IntHashSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for IntHashSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl Eq for IntHashSet {}
+
+impl Hash for IntHashSet {
+    /// Sums the value hashes so that sets equal under `==` hash alike whatever their capacity or
+    /// insertion order.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let sum = self
+            .iter()
+            .fold(0u32, |sum, value| sum.wrapping_add(mix_hash(fast_int_hash(value))));
+        state.write_usize(self.len());
+        state.write_u32(sum);
+    }
+}
+
+impl<'a> IntoIterator for &'a IntHashSet {
+    type Item = i32;
+    type IntoIter = IntHashSetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<i32> for IntHashSet {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<i32> for IntHashSet {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
 pub struct IntHashSetIter<'a> {
     set: &'a IntHashSet,
     index: usize,
@@ -174,19 +461,93 @@ impl<'a> Iterator for IntHashSetIter<'a> {
     type Item = i32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < self.set.keys.len() {
+        let table_length = self.set.keys.len();
+        while self.index < table_length {
             let key = self.set.keys[self.index];
+            self.index += 1;
             if key != MISSING_VALUE {
-                self.index += 1;
                 return Some(key);
             }
+        }
+
+        // Values not yet migrated out of the previous table follow the current table
+        let old_length = self.set.migration.as_ref().map_or(0, Migration::capacity);
+        if let Some(migration) = self.set.migration.as_ref() {
+            while self.index < table_length + old_length {
+                let index = self.index - table_length;
+                self.index += 1;
+                if migration.is_live(index) {
+                    return Some(migration.keys[index]);
+                }
+            }
+        }
+
+        if self.index == table_length + old_length {
             self.index += 1;
+            if self.set.contains_missing_value {
+                return Some(MISSING_VALUE);
+            }
         }
         None
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.set.size))
+        (0, Some(self.set.len()))
+    }
+}
+
+/// Iterator over an [`IntHashSet`] that can remove the value it last returned.
+pub struct IntHashSetCursor<'a> {
+    set: &'a mut IntHashSet,
+    index: usize,
+    remaining: usize,
+    yield_missing_value: bool,
+    current: Option<usize>,
+}
+
+impl IntHashSetCursor<'_> {
+    /// Removes the value last returned by `next`, returning `false` if there is none or it was
+    /// already removed.
+    pub fn remove(&mut self) -> bool {
+        match self.current.take() {
+            Some(MISSING_VALUE_INDEX) => {
+                self.set.contains_missing_value = false;
+                true
+            }
+            Some(index) => {
+                self.set.remove_at(index);
+                // Compaction may have pulled a value not yet visited into this slot
+                self.index = index;
+                self.remaining += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Iterator for IntHashSetCursor<'_> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let index = self.index;
+            self.index = (index + 1) & self.set.mask;
+            self.remaining -= 1;
+
+            let key = self.set.keys[index];
+            if key != MISSING_VALUE {
+                self.current = Some(index);
+                return Some(key);
+            }
+        }
+
+        if core::mem::take(&mut self.yield_missing_value) {
+            self.current = Some(MISSING_VALUE_INDEX);
+            return Some(MISSING_VALUE);
+        }
+        self.current = None;
+        None
     }
 }
 
@@ -242,4 +603,139 @@ mod tests {
         values.sort();
         assert_eq!(values, vec![1, 2, 3]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_min_value() {
+        let mut set = IntHashSet::new();
+
+        assert!(set.insert(i32::MIN));
+        assert!(!set.insert(i32::MIN));
+        assert!(set.insert(-1));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(i32::MIN));
+
+        let mut values: Vec<_> = set.iter().collect();
+        values.sort();
+        assert_eq!(values, vec![i32::MIN, -1]);
+
+        assert!(set.remove(i32::MIN));
+        assert!(!set.remove(i32::MIN));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_resize_control_and_shrink() {
+        assert!(IntHashSet::with_capacity_and_load_factor(16, 0.05).is_err());
+
+        let mut set = IntHashSet::with_capacity_and_load_factor(10, 0.9).unwrap();
+        assert_eq!(set.capacity(), 16);
+
+        set.set_resize_enabled(false);
+        for value in 0..14 {
+            assert!(set.try_insert(value).unwrap());
+        }
+        assert!(!set.try_insert(13).unwrap());
+        assert!(set.try_insert(14).is_err());
+
+        set.set_resize_enabled(true);
+        for value in 14..1000 {
+            set.insert(value);
+        }
+        for value in 10..1000 {
+            set.remove(value);
+        }
+        set.shrink_to_fit();
+        assert_eq!(set.capacity(), 16);
+        assert_eq!(set.len(), 10);
+        assert!((0..10).all(|value| set.contains(value)));
+
+        set.reserve(100);
+        assert_eq!(set.capacity(), 128);
+    }
+
+    #[test]
+    fn test_incremental_resize() {
+        let mut set = IntHashSet::new();
+        set.set_incremental_resize(true);
+
+        for value in 0..5 {
+            set.insert(value);
+        }
+        // Crossing the threshold starts a migration which later inserts and removes drain
+        while !set.is_migrating() {
+            set.insert(set.len() as i32);
+        }
+        let migrating_len = set.len() as i32;
+        assert!((0..migrating_len).all(|value| set.contains(value)));
+        assert!(!set.insert(0));
+        assert!(set.remove(1));
+        assert!(!set.contains(1));
+        assert_eq!(set.iter().count(), set.len());
+
+        for value in migrating_len..10_000 {
+            set.insert(value);
+        }
+        assert_eq!(set.len(), 9_999);
+        assert!(!set.contains(1));
+        assert!((2..10_000).all(|value| set.contains(value)));
+    }
+
+    #[test]
+    fn test_cursor_removal_visits_every_value_once() {
+        // Keys sharing a low-bit pattern collide, so removals shift chains under the cursor
+        let mut set: IntHashSet = (0..200).map(|value| value << 16).chain([i32::MIN]).collect();
+
+        let mut visited = Vec::new();
+        let mut cursor = set.cursor();
+        while let Some(value) = cursor.next() {
+            visited.push(value);
+            if value % 3 == 0 || value == i32::MIN {
+                assert!(cursor.remove());
+                assert!(!cursor.remove());
+            }
+        }
+
+        visited.sort();
+        let mut expected: Vec<_> = (0..200).map(|value| value << 16).chain([i32::MIN]).collect();
+        expected.sort();
+        assert_eq!(visited, expected);
+
+        assert_eq!(set.len(), 133);
+        assert!(set.iter().all(|value| value % 3 != 0));
+        assert!(!set.contains(i32::MIN));
+    }
+
+    #[test]
+    fn test_set_algebra_and_order_independent_equality() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let evens: IntHashSet = (0..20).step_by(2).collect();
+        let small: IntHashSet = (0..10).collect();
+
+        let mut union: Vec<_> = evens.union(&small).collect();
+        union.sort();
+        assert_eq!(union, (0..10).chain((10..20).step_by(2)).collect::<Vec<_>>());
+        let intersection: IntHashSet = evens.intersection(&small).collect();
+        assert_eq!(intersection, [0, 2, 4, 6, 8].into_iter().collect());
+        assert_eq!(evens.difference(&small).count(), 5);
+        assert!(!small.is_subset(&evens));
+
+        let mut retained: IntHashSet = small.iter().collect();
+        assert!(retained.retain_all(&evens));
+        assert!(!retained.retain_all(&evens));
+        assert!(retained.is_subset(&evens));
+
+        // Same values, different capacity and insertion order
+        let mut reversed = IntHashSet::with_capacity(256);
+        reversed.extend((0..10).rev().filter(|value| value % 2 == 0));
+        assert_eq!(retained, reversed);
+
+        let hash = |set: &IntHashSet| {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&retained), hash(&reversed));
+        assert_ne!(retained, evens);
+    }
+}
@@ -0,0 +1,156 @@
+use agrona_core::error::{AgronaError, Result};
+
+/// Small least-recently-used cache of resources keyed by `i32`.
+///
+/// Missing entries are created by the `factory`, and entries pushed out by a new one are released
+/// by the `closer`, as is everything still cached when the cache is dropped. Entries are kept
+/// most recently used first and looked up with a linear scan, which suits the small capacities
+/// this is meant for.
+pub struct IntLruCache<T, F, C>
+where
+    F: FnMut(i32) -> T,
+    C: FnMut(T),
+{
+    keys: Vec<i32>,
+    values: Vec<T>,
+    capacity: usize,
+    factory: F,
+    closer: C,
+}
+
+impl<T, F, C> IntLruCache<T, F, C>
+where
+    F: FnMut(i32) -> T,
+    C: FnMut(T),
+{
+    pub fn new(capacity: usize, factory: F, closer: C) -> Result<Self> {
+        if capacity == 0 {
+            return Err(AgronaError::InvalidCapacity { capacity });
+        }
+
+        Ok(Self {
+            keys: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+            capacity,
+            factory,
+            closer,
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the resource for `key`, creating it on a miss and closing the least recently used
+    /// resource if the cache is full. Either way `key` becomes the most recently used.
+    pub fn lookup(&mut self, key: i32) -> &mut T {
+        match self.keys.iter().position(|&existing_key| existing_key == key) {
+            Some(index) => {
+                self.keys[..=index].rotate_right(1);
+                self.values[..=index].rotate_right(1);
+            }
+            None => {
+                let value = (self.factory)(key);
+                if self.values.len() == self.capacity {
+                    self.keys.pop();
+                    if let Some(evicted) = self.values.pop() {
+                        (self.closer)(evicted);
+                    }
+                }
+                self.keys.insert(0, key);
+                self.values.insert(0, value);
+            }
+        }
+
+        &mut self.values[0]
+    }
+
+    /// Whether `key` is cached, without affecting its recency.
+    pub fn contains_key(&self, key: i32) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// Removes `key` and closes its resource, returning `true` if it was cached.
+    pub fn remove(&mut self, key: i32) -> bool {
+        match self.keys.iter().position(|&existing_key| existing_key == key) {
+            Some(index) => {
+                self.keys.remove(index);
+                (self.closer)(self.values.remove(index));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Closes every cached resource.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        for value in self.values.drain(..) {
+            (self.closer)(value);
+        }
+    }
+}
+
+impl<T, F, C> Drop for IntLruCache<T, F, C>
+where
+    F: FnMut(i32) -> T,
+    C: FnMut(T),
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_creates_on_miss_and_closes_least_recently_used() {
+        let created = RefCell::new(Vec::new());
+        let closed = RefCell::new(Vec::new());
+
+        let mut cache = IntLruCache::new(
+            2,
+            |key| {
+                created.borrow_mut().push(key);
+                format!("resource-{}", key)
+            },
+            |resource: String| closed.borrow_mut().push(resource),
+        )
+        .unwrap();
+
+        assert_eq!(cache.lookup(1), "resource-1");
+        cache.lookup(2);
+        // Touching 1 makes 2 the least recently used
+        cache.lookup(1).push('!');
+        cache.lookup(3);
+
+        assert!(cache.contains_key(1));
+        assert!(!cache.contains_key(2));
+        assert_eq!(cache.lookup(1), "resource-1!");
+        assert!(cache.remove(3));
+        assert!(!cache.remove(3));
+        drop(cache);
+
+        assert_eq!(*created.borrow(), vec![1, 2, 3]);
+        assert_eq!(*closed.borrow(), vec!["resource-2", "resource-3", "resource-1!"]);
+    }
+
+    #[test]
+    fn test_rejects_zero_capacity() {
+        assert!(IntLruCache::new(0, |key| key, drop).is_err());
+    }
+}
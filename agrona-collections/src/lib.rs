@@ -1,11 +1,37 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod collection_util;
+mod incremental_resize;
+pub mod int2int_hash_map;
+pub mod int2object_cache;
+pub mod int_array_list;
+pub mod int_array_queue;
 pub mod int_hash_map;
 pub mod int_hash_set;
+pub mod int_lru_cache;
+pub mod long2long_hash_map;
+pub mod long2object_hash_map;
+pub mod long_array_list;
+pub mod long_array_queue;
+pub mod long_hash_set;
 pub mod mutable_integer;
+pub mod object2int_hash_map;
+pub mod object2long_hash_map;
 pub mod hashing;
 
+pub use int2int_hash_map::*;
+pub use int2object_cache::*;
+pub use int_array_list::*;
+pub use int_array_queue::*;
 pub use int_hash_map::*;
 pub use int_hash_set::*;
+pub use int_lru_cache::*;
+pub use long2long_hash_map::*;
+pub use long2object_hash_map::*;
+pub use long_array_list::*;
+pub use long_array_queue::*;
+pub use long_hash_set::*;
 pub use mutable_integer::*;
+pub use object2int_hash_map::*;
+pub use object2long_hash_map::*;
 pub use hashing::*;
\ No newline at end of file
@@ -0,0 +1,400 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{fast_long_hash, mix_hash};
+use agrona_core::error::{AgronaError, Result};
+
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing map from `i64` to `i64` with keys and values interleaved in one array.
+///
+/// A caller-chosen `missing_value` marks empty slots and is returned for absent keys, so it
+/// cannot be stored as a value, but every `i64` is a valid key.
+#[derive(Clone, Debug)]
+pub struct Long2LongHashMap {
+    entries: Vec<i64>,
+    missing_value: i64,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl Long2LongHashMap {
+    pub fn new(missing_value: i64) -> Self {
+        Self::with_capacity(MIN_CAPACITY, missing_value)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, missing_value: i64) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            entries: vec![missing_value; capacity * 2],
+            missing_value,
+            size: 0,
+            resize_threshold: (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn missing_value(&self) -> i64 {
+        self.missing_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+
+    #[inline]
+    fn hash_key(key: i64) -> usize {
+        mix_hash(fast_long_hash(key)) as usize
+    }
+
+    #[inline]
+    fn is_occupied(&self, index: usize) -> bool {
+        self.entries[index * 2 + 1] != self.missing_value
+    }
+
+    #[inline]
+    fn find_index(&self, key: i64) -> (usize, bool) {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            if !self.is_occupied(index) {
+                return (index, false);
+            }
+            if self.entries[index * 2] == key {
+                return (index, true);
+            }
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    /// Value for `key`, or the missing value if there is none.
+    #[inline]
+    pub fn get(&self, key: i64) -> i64 {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.entries[index * 2 + 1]
+        } else {
+            self.missing_value
+        }
+    }
+
+    #[inline]
+    pub fn get_or_default(&self, key: i64, default_value: i64) -> i64 {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.entries[index * 2 + 1]
+        } else {
+            default_value
+        }
+    }
+
+    pub fn contains_key(&self, key: i64) -> bool {
+        self.find_index(key).1
+    }
+
+    pub fn contains_value(&self, value: i64) -> bool {
+        value != self.missing_value && self.values().any(|existing| existing == value)
+    }
+
+    /// Inserts `value` for `key`, returning the previous value or the missing value.
+    ///
+    /// Errors with `IllegalArgument` if `value` is the missing value.
+    pub fn put(&mut self, key: i64, value: i64) -> Result<i64> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        if found {
+            return Ok(core::mem::replace(&mut self.entries[index * 2 + 1], value));
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Inserts `value` only if `key` is absent, returning the existing value or the missing
+    /// value when it inserted.
+    pub fn put_if_absent(&mut self, key: i64, value: i64) -> Result<i64> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        if found {
+            return Ok(self.entries[index * 2 + 1]);
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Removes `key`, returning its value or the missing value.
+    pub fn remove(&mut self, key: i64) -> i64 {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        self.remove_at(index)
+    }
+
+    /// Sets `key` to `remapping(key, current)`, where `current` is the missing value when the key
+    /// is absent, and returns the new value. Returning the missing value removes the key.
+    pub fn compute<F>(&mut self, key: i64, remapping: F) -> i64
+    where
+        F: FnOnce(i64, i64) -> i64,
+    {
+        let (index, found) = self.find_index(key);
+        let old_value = if found { self.entries[index * 2 + 1] } else { self.missing_value };
+        let new_value = remapping(key, old_value);
+
+        self.update_at(index, found, key, new_value);
+        new_value
+    }
+
+    /// Inserts `mapping(key)` if `key` is absent, returning the resulting value. Nothing is
+    /// inserted if `mapping` returns the missing value.
+    pub fn compute_if_absent<F>(&mut self, key: i64, mapping: F) -> i64
+    where
+        F: FnOnce(i64) -> i64,
+    {
+        let (index, found) = self.find_index(key);
+        if found {
+            return self.entries[index * 2 + 1];
+        }
+
+        let new_value = mapping(key);
+        self.update_at(index, false, key, new_value);
+        new_value
+    }
+
+    /// Replaces the value of a present `key` with `remapping(key, value)`, returning the new
+    /// value. Returning the missing value removes the key.
+    pub fn compute_if_present<F>(&mut self, key: i64, remapping: F) -> i64
+    where
+        F: FnOnce(i64, i64) -> i64,
+    {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        let new_value = remapping(key, self.entries[index * 2 + 1]);
+        self.update_at(index, true, key, new_value);
+        new_value
+    }
+
+    /// Inserts `value` if `key` is absent, otherwise replaces the value with
+    /// `remapping(old, value)`. Returns the new value; the missing value removes the key.
+    pub fn merge<F>(&mut self, key: i64, value: i64, remapping: F) -> Result<i64>
+    where
+        F: FnOnce(i64, i64) -> i64,
+    {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(key);
+        let new_value = if found {
+            remapping(self.entries[index * 2 + 1], value)
+        } else {
+            value
+        };
+
+        self.update_at(index, found, key, new_value);
+        Ok(new_value)
+    }
+
+    /// Adds `delta` to the value for `key`, treating an absent key as zero, and returns the new
+    /// value. A result equal to the missing value removes the key.
+    pub fn increment(&mut self, key: i64, delta: i64) -> i64 {
+        let missing_value = self.missing_value;
+        self.compute(key, |_, old_value| {
+            let base = if old_value == missing_value { 0 } else { old_value };
+            base.wrapping_add(delta)
+        })
+    }
+
+    pub fn clear(&mut self) {
+        if self.size > 0 {
+            self.entries.fill(self.missing_value);
+            self.size = 0;
+        }
+    }
+
+    pub fn iter(&self) -> Long2LongHashMapIter<'_> {
+        Long2LongHashMapIter {
+            entries: self.entries.chunks_exact(2),
+            missing_value: self.missing_value,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn check_value(&self, value: i64) -> Result<()> {
+        if value == self.missing_value {
+            return Err(AgronaError::IllegalArgument(format!(
+                "cannot store the missing value {}",
+                value
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Stores the outcome of a compute-style update at the slot `find_index` returned.
+    #[inline]
+    fn update_at(&mut self, index: usize, found: bool, key: i64, new_value: i64) {
+        if new_value == self.missing_value {
+            if found {
+                self.remove_at(index);
+            }
+        } else if found {
+            self.entries[index * 2 + 1] = new_value;
+        } else {
+            self.insert_at(index, key, new_value);
+        }
+    }
+
+    #[inline]
+    fn insert_at(&mut self, index: usize, key: i64, value: i64) {
+        self.entries[index * 2] = key;
+        self.entries[index * 2 + 1] = value;
+        self.size += 1;
+
+        if self.size > self.resize_threshold {
+            self.resize();
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> i64 {
+        let old_value = self.entries[index * 2 + 1];
+        self.entries[index * 2 + 1] = self.missing_value;
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        old_value
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = self.capacity() * 2;
+        let old_entries = core::mem::replace(&mut self.entries, vec![self.missing_value; new_capacity * 2]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        for entry in old_entries.chunks_exact(2) {
+            if entry[1] != self.missing_value {
+                let (index, _) = self.find_index(entry[0]);
+                self.entries[index * 2] = entry[0];
+                self.entries[index * 2 + 1] = entry[1];
+            }
+        }
+    }
+
+    fn compact_chain(&mut self, mut deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while self.is_occupied(index) {
+            let key = self.entries[index * 2];
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.entries[deleted_index * 2] = key;
+                self.entries[deleted_index * 2 + 1] = self.entries[index * 2 + 1];
+                self.entries[index * 2 + 1] = self.missing_value;
+                deleted_index = index;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+}
+
+pub struct Long2LongHashMapIter<'a> {
+    entries: core::slice::ChunksExact<'a, i64>,
+    missing_value: i64,
+}
+
+impl<'a> Iterator for Long2LongHashMapIter<'a> {
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let missing_value = self.missing_value;
+        self.entries
+            .find(|entry| entry[1] != missing_value)
+            .map(|entry| (entry[0], entry[1]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.entries.len()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Long2LongHashMap {
+    type Item = (i64, i64);
+    type IntoIter = Long2LongHashMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER_ID_BASE: i64 = 1 << 40;
+
+    #[test]
+    fn test_put_get_remove_with_wide_keys() {
+        let mut map = Long2LongHashMap::new(0);
+
+        assert_eq!(map.put(i64::MIN, -5).unwrap(), 0);
+        assert_eq!(map.put(ORDER_ID_BASE, i64::MAX).unwrap(), 0);
+        assert_eq!(map.put(ORDER_ID_BASE + 1, 8).unwrap(), 0);
+        assert_eq!(map.get(ORDER_ID_BASE), i64::MAX);
+        assert_eq!(map.get(ORDER_ID_BASE + 2), 0);
+        assert!(matches!(map.put(1, 0), Err(AgronaError::IllegalArgument(_))));
+
+        assert_eq!(map.increment(ORDER_ID_BASE + 1, -8), 0);
+        assert!(!map.contains_key(ORDER_ID_BASE + 1));
+        assert_eq!(map.merge(i64::MIN, 5, |old, new| old + new).unwrap(), 0);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_resize_and_removal_keep_entries_reachable() {
+        let mut map = Long2LongHashMap::with_capacity(4, -1);
+
+        for key in 0..1000 {
+            map.put(ORDER_ID_BASE * key, key).unwrap();
+        }
+        for key in (0..1000).step_by(2) {
+            assert_eq!(map.remove(ORDER_ID_BASE * key), key);
+        }
+
+        assert_eq!(map.len(), 500);
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            (1..1000).step_by(2).map(|key| (ORDER_ID_BASE * key, key)).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{fast_long_hash, mix_hash};
+use core::mem;
+
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing map from `i64` keys to `V`.
+///
+/// Empty slots hold `None`, so every `i64` is a valid key and `V` needs no `Clone` or
+/// `Default`.
+#[derive(Clone, Debug)]
+pub struct Long2ObjectHashMap<V> {
+    keys: Vec<i64>,
+    values: Vec<Option<V>>,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl<V> Long2ObjectHashMap<V> {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY)
+    }
+
+    pub fn with_capacity(initial_capacity: usize) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            keys: vec![0; capacity],
+            values: (0..capacity).map(|_| None).collect(),
+            size: 0,
+            resize_threshold: (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    fn hash_key(key: i64) -> usize {
+        mix_hash(fast_long_hash(key)) as usize
+    }
+
+    #[inline]
+    fn find_index(&self, key: i64) -> (usize, bool) {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            if self.values[index].is_none() {
+                return (index, false);
+            }
+            if self.keys[index] == key {
+                return (index, true);
+            }
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    pub fn get(&self, key: i64) -> Option<&V> {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.values[index].as_ref()
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, key: i64) -> Option<&mut V> {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.values[index].as_mut()
+        } else {
+            None
+        }
+    }
+
+    pub fn insert(&mut self, key: i64, value: V) -> Option<V> {
+        let (index, found) = self.find_index(key);
+
+        if found {
+            return self.values[index].replace(value);
+        }
+
+        self.keys[index] = key;
+        self.values[index] = Some(value);
+        self.size += 1;
+
+        if self.size > self.resize_threshold {
+            self.resize();
+        }
+
+        None
+    }
+
+    pub fn remove(&mut self, key: i64) -> Option<V> {
+        let (index, found) = self.find_index(key);
+
+        if !found {
+            return None;
+        }
+
+        let old_value = self.values[index].take();
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        old_value
+    }
+
+    pub fn contains_key(&self, key: i64) -> bool {
+        self.find_index(key).1
+    }
+
+    pub fn clear(&mut self) {
+        if self.size > 0 {
+            self.values.iter_mut().for_each(|value| *value = None);
+            self.size = 0;
+        }
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = self.keys.len() * 2;
+        let old_keys = mem::replace(&mut self.keys, vec![0; new_capacity]);
+        let old_values = mem::replace(&mut self.values, (0..new_capacity).map(|_| None).collect());
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        for (key, value) in old_keys.into_iter().zip(old_values) {
+            if value.is_some() {
+                let (index, _) = self.find_index(key);
+                self.keys[index] = key;
+                self.values[index] = value;
+            }
+        }
+    }
+
+    fn compact_chain(&mut self, mut deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while self.values[index].is_some() {
+            let key = self.keys[index];
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.keys[deleted_index] = key;
+                self.values[deleted_index] = self.values[index].take();
+                deleted_index = index;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    pub fn iter(&self) -> Long2ObjectHashMapIter<'_, V> {
+        Long2ObjectHashMapIter {
+            keys: self.keys.iter(),
+            values: self.values.iter(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> Long2ObjectHashMapIterMut<'_, V> {
+        Long2ObjectHashMapIterMut {
+            keys: self.keys.iter(),
+            values: self.values.iter_mut(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+}
+
+impl<V> Default for Long2ObjectHashMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Long2ObjectHashMapIter<'a, V> {
+    keys: core::slice::Iter<'a, i64>,
+    values: core::slice::Iter<'a, Option<V>>,
+    remaining: usize,
+}
+
+impl<'a, V> Iterator for Long2ObjectHashMapIter<'a, V> {
+    type Item = (i64, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (&key, value) in (&mut self.keys).zip(&mut self.values) {
+            if let Some(value) = value {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for Long2ObjectHashMapIter<'_, V> {}
+
+pub struct Long2ObjectHashMapIterMut<'a, V> {
+    keys: core::slice::Iter<'a, i64>,
+    values: core::slice::IterMut<'a, Option<V>>,
+    remaining: usize,
+}
+
+impl<'a, V> Iterator for Long2ObjectHashMapIterMut<'a, V> {
+    type Item = (i64, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (&key, value) in (&mut self.keys).zip(&mut self.values) {
+            if let Some(value) = value {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for Long2ObjectHashMapIterMut<'_, V> {}
+
+impl<'a, V> IntoIterator for &'a Long2ObjectHashMap<V> {
+    type Item = (i64, &'a V);
+    type IntoIter = Long2ObjectHashMapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut Long2ObjectHashMap<V> {
+    type Item = (i64, &'a mut V);
+    type IntoIter = Long2ObjectHashMapIterMut<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<V> FromIterator<(i64, V)> for Long2ObjectHashMap<V> {
+    fn from_iter<I: IntoIterator<Item = (i64, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Instrument {
+        symbol: &'static str,
+    }
+
+    #[test]
+    fn test_basic_operations_without_default_bound() {
+        let mut map = Long2ObjectHashMap::new();
+
+        assert_eq!(map.insert(i64::MIN, Instrument { symbol: "A" }), None);
+        assert_eq!(map.insert(0, Instrument { symbol: "B" }), None);
+        assert_eq!(
+            map.insert(0, Instrument { symbol: "C" }),
+            Some(Instrument { symbol: "B" })
+        );
+        assert_eq!(map.len(), 2);
+
+        map.get_mut(i64::MIN).unwrap().symbol = "D";
+        assert_eq!(map.get(i64::MIN), Some(&Instrument { symbol: "D" }));
+        assert_eq!(map.remove(0), Some(Instrument { symbol: "C" }));
+        assert_eq!(map.remove(0), None);
+        assert_eq!(map.len(), 1);
+
+        map.clear();
+        assert!(map.is_empty());
+        assert!(!map.contains_key(i64::MIN));
+    }
+
+    #[test]
+    fn test_iterators_after_resize_and_removal() {
+        let mut map: Long2ObjectHashMap<String> = (0..500i64).map(|id| (id << 33, id.to_string())).collect();
+        for id in (0..500i64).step_by(2) {
+            assert_eq!(map.remove(id << 33), Some(id.to_string()));
+        }
+
+        for (_, value) in &mut map {
+            value.push('!');
+        }
+
+        assert_eq!(map.iter().len(), 250);
+        let mut entries: Vec<_> = map.iter().map(|(key, value)| (key >> 33, value.clone())).collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            (1..500i64).step_by(2).map(|id| (id, format!("{}!", id))).collect::<Vec<_>>()
+        );
+        assert_eq!(map.keys().count(), map.values().count());
+    }
+}
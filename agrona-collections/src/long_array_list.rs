@@ -0,0 +1,257 @@
+use crate::long_hash_set::LongHashSet;
+
+pub const DEFAULT_LONG_NULL_VALUE: i64 = i64::MIN;
+const MIN_CAPACITY: usize = 8;
+
+/// Growable list of `i64` with Agrona's semantics: a `null_value` is returned where Agrona
+/// would return null, e.g. when popping an empty list.
+#[derive(Clone, Debug)]
+pub struct LongArrayList {
+    elements: Vec<i64>,
+    null_value: i64,
+}
+
+impl LongArrayList {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY, DEFAULT_LONG_NULL_VALUE)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, null_value: i64) -> Self {
+        Self {
+            elements: Vec::with_capacity(initial_capacity.max(MIN_CAPACITY)),
+            null_value,
+        }
+    }
+
+    /// Wraps existing elements without copying them.
+    pub fn wrap(elements: Vec<i64>, null_value: i64) -> Self {
+        Self { elements, null_value }
+    }
+
+    #[inline]
+    pub fn null_value(&self) -> i64 {
+        self.null_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+
+    /// Element at `index`, panicking if it is out of bounds.
+    #[inline]
+    pub fn get_long(&self, index: usize) -> i64 {
+        self.elements[index]
+    }
+
+    /// Replaces the element at `index`, returning the previous one.
+    #[inline]
+    pub fn set_long(&mut self, index: usize, value: i64) -> i64 {
+        core::mem::replace(&mut self.elements[index], value)
+    }
+
+    #[inline]
+    pub fn add_long(&mut self, value: i64) {
+        self.elements.push(value);
+    }
+
+    pub fn add_long_at(&mut self, index: usize, value: i64) {
+        self.elements.insert(index, value);
+    }
+
+    /// Pushes onto the end, treating the list as a stack.
+    #[inline]
+    pub fn push_long(&mut self, value: i64) {
+        self.elements.push(value);
+    }
+
+    /// Pops from the end, or returns the null value if empty.
+    #[inline]
+    pub fn pop_long(&mut self) -> i64 {
+        self.elements.pop().unwrap_or(self.null_value)
+    }
+
+    /// Last element, or the null value if empty.
+    #[inline]
+    pub fn peek_long(&self) -> i64 {
+        self.elements.last().copied().unwrap_or(self.null_value)
+    }
+
+    /// Removes the element at `index`, shifting later elements down.
+    pub fn remove_at(&mut self, index: usize) -> i64 {
+        self.elements.remove(index)
+    }
+
+    /// Removes the element at `index` in O(1) by moving the last element into its place.
+    #[inline]
+    pub fn fast_unordered_remove(&mut self, index: usize) -> i64 {
+        self.elements.swap_remove(index)
+    }
+
+    /// Removes the first occurrence of `value`, preserving order.
+    pub fn remove_long(&mut self, value: i64) -> bool {
+        match self.index_of(value) {
+            Some(index) => {
+                self.elements.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the first occurrence of `value` without preserving order.
+    pub fn fast_unordered_remove_long(&mut self, value: i64) -> bool {
+        match self.index_of(value) {
+            Some(index) => {
+                self.elements.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub fn contains_long(&self, value: i64) -> bool {
+        self.elements.contains(&value)
+    }
+
+    pub fn index_of(&self, value: i64) -> Option<usize> {
+        self.elements.iter().position(|&element| element == value)
+    }
+
+    pub fn last_index_of(&self, value: i64) -> Option<usize> {
+        self.elements.iter().rposition(|&element| element == value)
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn ensure_capacity(&mut self, required_capacity: usize) {
+        self.elements.reserve(required_capacity.saturating_sub(self.elements.len()));
+    }
+
+    pub fn trim_to_size(&mut self) {
+        self.elements.shrink_to(MIN_CAPACITY);
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[i64] {
+        &self.elements
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, i64>> {
+        self.elements.iter().copied()
+    }
+
+    pub fn into_vec(self) -> Vec<i64> {
+        self.elements
+    }
+}
+
+impl Default for LongArrayList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for LongArrayList {
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl Eq for LongArrayList {}
+
+impl From<Vec<i64>> for LongArrayList {
+    fn from(elements: Vec<i64>) -> Self {
+        Self::wrap(elements, DEFAULT_LONG_NULL_VALUE)
+    }
+}
+
+impl From<&LongHashSet> for LongArrayList {
+    fn from(set: &LongHashSet) -> Self {
+        let mut list = Self::with_capacity(set.len(), DEFAULT_LONG_NULL_VALUE);
+        list.extend(set.iter());
+        list
+    }
+}
+
+impl From<&LongArrayList> for LongHashSet {
+    fn from(list: &LongArrayList) -> Self {
+        let mut set = LongHashSet::with_capacity(list.len() * 2);
+        for value in list.iter() {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl FromIterator<i64> for LongArrayList {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<i64> for LongArrayList {
+    fn extend<I: IntoIterator<Item = i64>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for &'a LongArrayList {
+    type Item = i64;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, i64>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_operations_with_wide_values() {
+        let mut list = LongArrayList::with_capacity(2, 0);
+
+        for id in 1..=4i64 {
+            list.add_long(id << 40);
+        }
+        list.add_long_at(0, -1);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.get_long(1), 1 << 40);
+        assert_eq!(list.last_index_of(4 << 40), Some(4));
+
+        assert_eq!(list.fast_unordered_remove(0), -1);
+        assert_eq!(list.as_slice(), &[4 << 40, 1 << 40, 2 << 40, 3 << 40]);
+        assert!(list.contains_long(3 << 40));
+
+        list.clear();
+        assert_eq!(list.pop_long(), 0);
+    }
+
+    #[test]
+    fn test_hash_set_conversions() {
+        let list: LongArrayList = [i64::MAX, i64::MIN, i64::MAX].into_iter().collect();
+
+        let set = LongHashSet::from(&list);
+        assert_eq!(set.len(), 2);
+
+        let mut round_trip = LongArrayList::from(&set).into_vec();
+        round_trip.sort();
+        assert_eq!(round_trip, vec![i64::MIN, i64::MAX]);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::long_array_list::DEFAULT_LONG_NULL_VALUE;
+use crate::long_hash_set::LongHashSet;
+
+const MIN_CAPACITY: usize = 8;
+
+/// Double-ended queue of `i64` over a power-of-two ring buffer that doubles when full.
+///
+/// Polling or peeking an empty queue returns the `null_value`.
+#[derive(Clone, Debug)]
+pub struct LongArrayQueue {
+    elements: Vec<i64>,
+    head: usize,
+    size: usize,
+    mask: usize,
+    null_value: i64,
+}
+
+impl LongArrayQueue {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY, DEFAULT_LONG_NULL_VALUE)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, null_value: i64) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            elements: vec![null_value; capacity],
+            head: 0,
+            size: 0,
+            mask: capacity - 1,
+            null_value,
+        }
+    }
+
+    #[inline]
+    pub fn null_value(&self) -> i64 {
+        self.null_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Adds `value` to the tail.
+    pub fn offer_long(&mut self, value: i64) {
+        if self.size == self.elements.len() {
+            self.grow();
+        }
+
+        let index = (self.head + self.size) & self.mask;
+        self.elements[index] = value;
+        self.size += 1;
+    }
+
+    /// Adds `value` to the head.
+    pub fn offer_first_long(&mut self, value: i64) {
+        if self.size == self.elements.len() {
+            self.grow();
+        }
+
+        self.head = self.head.wrapping_sub(1) & self.mask;
+        self.elements[self.head] = value;
+        self.size += 1;
+    }
+
+    /// Removes and returns the head, or the null value if empty.
+    pub fn poll_long(&mut self) -> i64 {
+        if self.size == 0 {
+            return self.null_value;
+        }
+
+        let value = self.elements[self.head];
+        self.head = (self.head + 1) & self.mask;
+        self.size -= 1;
+        value
+    }
+
+    /// Removes and returns the tail, or the null value if empty.
+    pub fn poll_last_long(&mut self) -> i64 {
+        if self.size == 0 {
+            return self.null_value;
+        }
+
+        self.size -= 1;
+        self.elements[(self.head + self.size) & self.mask]
+    }
+
+    #[inline]
+    pub fn peek_long(&self) -> i64 {
+        if self.size == 0 {
+            self.null_value
+        } else {
+            self.elements[self.head]
+        }
+    }
+
+    #[inline]
+    pub fn peek_last_long(&self) -> i64 {
+        if self.size == 0 {
+            self.null_value
+        } else {
+            self.elements[(self.head + self.size - 1) & self.mask]
+        }
+    }
+
+    /// Element `index` places from the head, or the null value if out of range.
+    #[inline]
+    pub fn get_long(&self, index: usize) -> i64 {
+        if index < self.size {
+            self.elements[(self.head + index) & self.mask]
+        } else {
+            self.null_value
+        }
+    }
+
+    pub fn contains_long(&self, value: i64) -> bool {
+        self.iter().any(|element| element == value)
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.size = 0;
+    }
+
+    /// Iterates from head to tail without allocating.
+    pub fn iter(&self) -> LongArrayQueueIter<'_> {
+        LongArrayQueueIter {
+            queue: self,
+            index: 0,
+        }
+    }
+
+    fn grow(&mut self) {
+        let old_capacity = self.elements.len();
+        let mut elements = vec![self.null_value; old_capacity * 2];
+
+        let (wrapped, front) = self.elements.split_at(self.head);
+        elements[..front.len()].copy_from_slice(front);
+        elements[front.len()..old_capacity].copy_from_slice(wrapped);
+
+        self.elements = elements;
+        self.head = 0;
+        self.mask = old_capacity * 2 - 1;
+    }
+}
+
+impl Default for LongArrayQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct LongArrayQueueIter<'a> {
+    queue: &'a LongArrayQueue,
+    index: usize,
+}
+
+impl<'a> Iterator for LongArrayQueueIter<'a> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.queue.size {
+            return None;
+        }
+
+        let value = self.queue.elements[(self.queue.head + self.index) & self.queue.mask];
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.queue.size - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for LongArrayQueueIter<'_> {}
+
+impl<'a> IntoIterator for &'a LongArrayQueue {
+    type Item = i64;
+    type IntoIter = LongArrayQueueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<i64> for LongArrayQueue {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl Extend<i64> for LongArrayQueue {
+    fn extend<I: IntoIterator<Item = i64>>(&mut self, iter: I) {
+        for value in iter {
+            self.offer_long(value);
+        }
+    }
+}
+
+impl From<&LongArrayQueue> for LongHashSet {
+    fn from(queue: &LongArrayQueue) -> Self {
+        let mut set = LongHashSet::with_capacity(queue.len() * 2);
+        for value in queue {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deque_operations_across_growth() {
+        let mut queue = LongArrayQueue::new();
+
+        for id in 0..20i64 {
+            if id % 2 == 0 {
+                queue.offer_long(id << 40);
+            } else {
+                queue.offer_first_long(-(id << 40));
+            }
+        }
+
+        assert_eq!(queue.len(), 20);
+        assert_eq!(queue.capacity(), 32);
+        assert_eq!(queue.peek_long(), -(19 << 40));
+        assert_eq!(queue.peek_last_long(), 18 << 40);
+
+        let drained: Vec<_> = core::iter::from_fn(|| Some(queue.poll_long()).filter(|&id| id != i64::MIN)).collect();
+        assert_eq!(drained.len(), 20);
+        assert!(drained.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(queue.poll_last_long(), i64::MIN);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{fast_long_hash, mix_hash};
+
+const MISSING_VALUE: i64 = i64::MIN;
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing set of `i64`.
+///
+/// `i64::MIN` marks empty slots in the table, so its membership is tracked out-of-band.
+pub struct LongHashSet {
+    keys: Vec<i64>,
+    contains_missing_value: bool,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl LongHashSet {
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY)
+    }
+
+    pub fn with_capacity(initial_capacity: usize) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+        let resize_threshold = (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        Self {
+            keys: vec![MISSING_VALUE; capacity],
+            contains_missing_value: false,
+            size: 0,
+            resize_threshold,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size + self.contains_missing_value as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    fn hash_key(key: i64) -> usize {
+        mix_hash(fast_long_hash(key)) as usize
+    }
+
+    #[inline]
+    fn find_index(&self, key: i64) -> (usize, bool) {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            let existing_key = self.keys[index];
+            if existing_key == MISSING_VALUE {
+                return (index, false);
+            }
+            if existing_key == key {
+                return (index, true);
+            }
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    pub fn contains(&self, key: i64) -> bool {
+        if key == MISSING_VALUE {
+            return self.contains_missing_value;
+        }
+
+        self.find_index(key).1
+    }
+
+    pub fn insert(&mut self, key: i64) -> bool {
+        if key == MISSING_VALUE {
+            return !core::mem::replace(&mut self.contains_missing_value, true);
+        }
+
+        if self.size >= self.resize_threshold {
+            self.resize();
+        }
+
+        let (index, found) = self.find_index(key);
+
+        if !found {
+            self.keys[index] = key;
+            self.size += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn remove(&mut self, key: i64) -> bool {
+        if key == MISSING_VALUE {
+            return core::mem::replace(&mut self.contains_missing_value, false);
+        }
+
+        let (index, found) = self.find_index(key);
+
+        if !found {
+            return false;
+        }
+
+        self.keys[index] = MISSING_VALUE;
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.fill(MISSING_VALUE);
+        self.contains_missing_value = false;
+        self.size = 0;
+    }
+
+    fn resize(&mut self) {
+        let old_keys = std::mem::take(&mut self.keys);
+        let old_size = self.size;
+
+        let new_capacity = old_keys.len() * 2;
+        self.keys = vec![MISSING_VALUE; new_capacity];
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+        self.size = 0;
+
+        for key in old_keys {
+            if key != MISSING_VALUE {
+                self.insert(key);
+            }
+        }
+
+        debug_assert_eq!(self.size, old_size);
+    }
+
+    fn compact_chain(&mut self, deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while self.keys[index] != MISSING_VALUE {
+            let key = self.keys[index];
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.keys[deleted_index] = key;
+                self.keys[index] = MISSING_VALUE;
+
+                self.compact_chain(index);
+                break;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+
+    pub fn iter(&self) -> LongHashSetIter<'_> {
+        LongHashSetIter {
+            set: self,
+            index: 0,
+        }
+    }
+}
+
+impl Default for LongHashSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct LongHashSetIter<'a> {
+    set: &'a LongHashSet,
+    index: usize,
+}
+
+impl<'a> Iterator for LongHashSetIter<'a> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.set.keys.len() {
+            let key = self.set.keys[self.index];
+            if key != MISSING_VALUE {
+                self.index += 1;
+                return Some(key);
+            }
+            self.index += 1;
+        }
+
+        if self.index == self.set.keys.len() {
+            self.index += 1;
+            if self.set.contains_missing_value {
+                return Some(MISSING_VALUE);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.set.len()))
+    }
+}
+
+impl<'a> IntoIterator for &'a LongHashSet {
+    type Item = i64;
+    type IntoIter = LongHashSetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<i64> for LongHashSet {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_and_min_values() {
+        let mut set = LongHashSet::new();
+
+        assert!(set.insert(i64::MIN));
+        assert!(set.insert(i64::MAX));
+        assert!(set.insert(1 << 40));
+        assert!(!set.insert(1 << 40));
+        assert!(!set.contains(1 << 41));
+        assert_eq!(set.len(), 3);
+
+        let mut values: Vec<_> = set.iter().collect();
+        values.sort();
+        assert_eq!(values, vec![i64::MIN, 1 << 40, i64::MAX]);
+
+        assert!(set.remove(i64::MIN));
+        assert!(set.remove(1 << 40));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_resize_and_removal() {
+        let mut set = LongHashSet::with_capacity(4);
+
+        for i in 0..1000i64 {
+            set.insert(i << 32);
+        }
+        for i in (0..1000i64).step_by(3) {
+            assert!(set.remove(i << 32));
+        }
+
+        assert_eq!(set.len(), 666);
+        for i in 0..1000i64 {
+            assert_eq!(set.contains(i << 32), i % 3 != 0);
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{mix_hash, FastHasher};
+use agrona_core::error::{AgronaError, Result};
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing map from hashable keys to `i32`, hashed with [`FastHasher`].
+///
+/// A caller-chosen `missing_value` is returned for absent keys and cannot be stored. Lookups
+/// accept any borrowed form of the key, e.g. `&str` for `String` keys, and never allocate.
+#[derive(Clone, Debug)]
+pub struct Object2IntHashMap<K> {
+    keys: Vec<Option<K>>,
+    values: Vec<i32>,
+    missing_value: i32,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl<K: Hash + Eq> Object2IntHashMap<K> {
+    pub fn new(missing_value: i32) -> Self {
+        Self::with_capacity(MIN_CAPACITY, missing_value)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, missing_value: i32) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            keys: (0..capacity).map(|_| None).collect(),
+            values: vec![missing_value; capacity],
+            missing_value,
+            size: 0,
+            resize_threshold: (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn missing_value(&self) -> i32 {
+        self.missing_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    fn hash_key<Q: Hash + ?Sized>(key: &Q) -> usize {
+        let mut hasher = FastHasher::new();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+        mix_hash((hash ^ (hash >> 32)) as u32) as usize
+    }
+
+    #[inline]
+    fn find_index<Q>(&self, key: &Q) -> (usize, bool)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            match &self.keys[index] {
+                None => return (index, false),
+                Some(existing_key) if existing_key.borrow() == key => return (index, true),
+                Some(_) => index = (index + 1) & self.mask,
+            }
+        }
+    }
+
+    /// Value for `key`, or the missing value if there is none.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> i32
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_or_default(key, self.missing_value)
+    }
+
+    #[inline]
+    pub fn get_or_default<Q>(&self, key: &Q, default_value: i32) -> i32
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.values[index]
+        } else {
+            default_value
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_index(key).1
+    }
+
+    /// Inserts `value` for `key`, returning the previous value or the missing value.
+    ///
+    /// Errors with `IllegalArgument` if `value` is the missing value.
+    pub fn put(&mut self, key: K, value: i32) -> Result<i32> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(&key);
+        if found {
+            return Ok(mem::replace(&mut self.values[index], value));
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Removes `key`, returning its value or the missing value.
+    pub fn remove<Q>(&mut self, key: &Q) -> i32
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        self.remove_at(index).1
+    }
+
+    /// Entry for `key`, for inspecting and updating it with a single probe.
+    pub fn entry(&mut self, key: K) -> Object2IntEntry<'_, K> {
+        let (index, found) = self.find_index(&key);
+        if found {
+            Object2IntEntry::Occupied(Object2IntOccupiedEntry { map: self, index })
+        } else {
+            Object2IntEntry::Vacant(Object2IntVacantEntry { map: self, index, key })
+        }
+    }
+
+    /// Adds `delta` to the value for `key`, treating an absent key as zero, and returns the new
+    /// value. A result equal to the missing value removes the key.
+    pub fn increment(&mut self, key: K, delta: i32) -> i32 {
+        let (index, found) = self.find_index(&key);
+        let old_value = if found { self.values[index] } else { 0 };
+        let new_value = old_value.wrapping_add(delta);
+
+        if new_value == self.missing_value {
+            if found {
+                self.remove_at(index);
+            }
+        } else if found {
+            self.values[index] = new_value;
+        } else {
+            self.insert_at(index, key, new_value);
+        }
+
+        new_value
+    }
+
+    pub fn clear(&mut self) {
+        if self.size > 0 {
+            self.keys.iter_mut().for_each(|key| *key = None);
+            self.values.fill(self.missing_value);
+            self.size = 0;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, i32)> + '_ {
+        self.keys
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(key, &value)| key.as_ref().map(|key| (key, value)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.keys.iter().flatten()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = i32> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn check_value(&self, value: i32) -> Result<()> {
+        if value == self.missing_value {
+            return Err(AgronaError::IllegalArgument(format!(
+                "cannot store the missing value {}",
+                value
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn insert_at(&mut self, index: usize, key: K, value: i32) {
+        self.keys[index] = Some(key);
+        self.values[index] = value;
+        self.size += 1;
+
+        if self.size > self.resize_threshold {
+            self.resize();
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, i32) {
+        let key = self.keys[index].take().expect("slot is occupied");
+        let value = mem::replace(&mut self.values[index], self.missing_value);
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        (key, value)
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = self.keys.len() * 2;
+        let old_keys = mem::replace(&mut self.keys, (0..new_capacity).map(|_| None).collect());
+        let old_values = mem::replace(&mut self.values, vec![self.missing_value; new_capacity]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        for (key, value) in old_keys.into_iter().zip(old_values) {
+            if let Some(key) = key {
+                let (index, _) = self.find_index(&key);
+                self.keys[index] = Some(key);
+                self.values[index] = value;
+            }
+        }
+    }
+
+    fn compact_chain(&mut self, mut deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while let Some(key) = &self.keys[index] {
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.keys[deleted_index] = self.keys[index].take();
+                self.values[deleted_index] = mem::replace(&mut self.values[index], self.missing_value);
+                deleted_index = index;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+}
+
+pub enum Object2IntEntry<'a, K> {
+    Occupied(Object2IntOccupiedEntry<'a, K>),
+    Vacant(Object2IntVacantEntry<'a, K>),
+}
+
+impl<'a, K: Hash + Eq> Object2IntEntry<'a, K> {
+    /// Inserts `value` if vacant, returning the resulting value.
+    pub fn or_insert(self, value: i32) -> Result<i32> {
+        self.or_insert_with(|| value)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> i32>(self, default: F) -> Result<i32> {
+        match self {
+            Self::Occupied(entry) => Ok(entry.get()),
+            Self::Vacant(entry) => {
+                let value = default();
+                entry.insert(value)?;
+                Ok(value)
+            }
+        }
+    }
+
+    /// Replaces an occupied value with `f(value)`; the missing value removes the entry.
+    pub fn and_modify<F: FnOnce(i32) -> i32>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(entry) => {
+                let new_value = f(entry.get());
+                if new_value == entry.map.missing_value {
+                    let Object2IntOccupiedEntry { map, index } = entry;
+                    let (key, _) = map.remove_at(index);
+                    let (index, _) = map.find_index(&key);
+                    Self::Vacant(Object2IntVacantEntry { map, index, key })
+                } else {
+                    entry.map.values[entry.index] = new_value;
+                    Self::Occupied(entry)
+                }
+            }
+            vacant => vacant,
+        }
+    }
+}
+
+pub struct Object2IntOccupiedEntry<'a, K> {
+    map: &'a mut Object2IntHashMap<K>,
+    index: usize,
+}
+
+impl<'a, K: Hash + Eq> Object2IntOccupiedEntry<'a, K> {
+    pub fn key(&self) -> &K {
+        self.map.keys[self.index].as_ref().expect("slot is occupied")
+    }
+
+    pub fn get(&self) -> i32 {
+        self.map.values[self.index]
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: i32) -> Result<i32> {
+        self.map.check_value(value)?;
+        Ok(mem::replace(&mut self.map.values[self.index], value))
+    }
+
+    pub fn remove(self) -> (K, i32) {
+        self.map.remove_at(self.index)
+    }
+}
+
+pub struct Object2IntVacantEntry<'a, K> {
+    map: &'a mut Object2IntHashMap<K>,
+    index: usize,
+    key: K,
+}
+
+impl<'a, K: Hash + Eq> Object2IntVacantEntry<'a, K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn insert(self, value: i32) -> Result<()> {
+        self.map.check_value(value)?;
+        self.map.insert_at(self.index, self.key, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_lookup() {
+        let mut map = Object2IntHashMap::new(-1);
+
+        assert_eq!(map.put("AAPL".to_string(), 1).unwrap(), -1);
+        assert_eq!(map.put("MSFT".to_string(), 2).unwrap(), -1);
+        assert_eq!(map.put("AAPL".to_string(), 3).unwrap(), 1);
+        assert!(matches!(map.put("GOOG".to_string(), -1), Err(AgronaError::IllegalArgument(_))));
+
+        assert_eq!(map.get("AAPL"), 3);
+        assert_eq!(map.get("GOOG"), -1);
+        assert_eq!(map.get_or_default("GOOG", 0), 0);
+        assert!(map.contains_key("MSFT"));
+
+        assert_eq!(map.remove("MSFT"), 2);
+        assert_eq!(map.remove("MSFT"), -1);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_entry_upsert() {
+        let mut map = Object2IntHashMap::new(0);
+
+        assert_eq!(map.entry("ES".to_string()).or_insert(5).unwrap(), 5);
+        assert_eq!(map.entry("ES".to_string()).and_modify(|value| value * 2).or_insert(1).unwrap(), 10);
+        assert_eq!(map.entry("ES".to_string()).and_modify(|_| 0).or_insert(7).unwrap(), 7);
+
+        match map.entry("NQ".to_string()) {
+            Object2IntEntry::Vacant(entry) => {
+                assert_eq!(entry.key(), "NQ");
+                entry.insert(4).unwrap();
+            }
+            Object2IntEntry::Occupied(_) => panic!("expected vacant entry"),
+        }
+        match map.entry("ES".to_string()) {
+            Object2IntEntry::Occupied(entry) => assert_eq!(entry.remove(), ("ES".to_string(), 7)),
+            Object2IntEntry::Vacant(_) => panic!("expected occupied entry"),
+        }
+
+        assert_eq!(map.increment("NQ".to_string(), -4), 0);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_resize_and_removal() {
+        let mut map = Object2IntHashMap::with_capacity(4, i32::MIN);
+
+        for id in 0..1000 {
+            map.put(format!("SYM{}", id), id).unwrap();
+        }
+        for id in (0..1000).step_by(2) {
+            assert_eq!(map.remove(format!("SYM{}", id).as_str()), id);
+        }
+
+        assert_eq!(map.len(), 500);
+        for id in 0..1000 {
+            let expected = if id % 2 == 0 { i32::MIN } else { id };
+            assert_eq!(map.get(format!("SYM{}", id).as_str()), expected);
+        }
+        assert_eq!(map.iter().count(), 500);
+        assert_eq!(map.values().map(i64::from).sum::<i64>(), (1..1000).step_by(2).sum::<i64>());
+    }
+}
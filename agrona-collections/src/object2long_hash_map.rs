@@ -0,0 +1,391 @@
+use crate::collection_util::should_move_entry;
+use crate::hashing::{mix_hash, FastHasher};
+use agrona_core::error::{AgronaError, Result};
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+const MIN_CAPACITY: usize = 8;
+const DEFAULT_LOAD_FACTOR: f32 = 0.67;
+
+/// Open-addressing map from hashable keys to `i64`, hashed with [`FastHasher`].
+///
+/// A caller-chosen `missing_value` is returned for absent keys and cannot be stored. Lookups
+/// accept any borrowed form of the key, e.g. `&str` for `String` keys, and never allocate.
+#[derive(Clone, Debug)]
+pub struct Object2LongHashMap<K> {
+    keys: Vec<Option<K>>,
+    values: Vec<i64>,
+    missing_value: i64,
+    size: usize,
+    resize_threshold: usize,
+    mask: usize,
+}
+
+impl<K: Hash + Eq> Object2LongHashMap<K> {
+    pub fn new(missing_value: i64) -> Self {
+        Self::with_capacity(MIN_CAPACITY, missing_value)
+    }
+
+    pub fn with_capacity(initial_capacity: usize, missing_value: i64) -> Self {
+        let capacity = (initial_capacity.max(MIN_CAPACITY)).next_power_of_two();
+
+        Self {
+            keys: (0..capacity).map(|_| None).collect(),
+            values: vec![missing_value; capacity],
+            missing_value,
+            size: 0,
+            resize_threshold: (capacity as f32 * DEFAULT_LOAD_FACTOR) as usize,
+            mask: capacity - 1,
+        }
+    }
+
+    #[inline]
+    pub fn missing_value(&self) -> i64 {
+        self.missing_value
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    fn hash_key<Q: Hash + ?Sized>(key: &Q) -> usize {
+        let mut hasher = FastHasher::new();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+        mix_hash((hash ^ (hash >> 32)) as u32) as usize
+    }
+
+    #[inline]
+    fn find_index<Q>(&self, key: &Q) -> (usize, bool)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut index = Self::hash_key(key) & self.mask;
+
+        loop {
+            match &self.keys[index] {
+                None => return (index, false),
+                Some(existing_key) if existing_key.borrow() == key => return (index, true),
+                Some(_) => index = (index + 1) & self.mask,
+            }
+        }
+    }
+
+    /// Value for `key`, or the missing value if there is none.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> i64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_or_default(key, self.missing_value)
+    }
+
+    #[inline]
+    pub fn get_or_default<Q>(&self, key: &Q, default_value: i64) -> i64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, found) = self.find_index(key);
+        if found {
+            self.values[index]
+        } else {
+            default_value
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_index(key).1
+    }
+
+    /// Inserts `value` for `key`, returning the previous value or the missing value.
+    ///
+    /// Errors with `IllegalArgument` if `value` is the missing value.
+    pub fn put(&mut self, key: K, value: i64) -> Result<i64> {
+        self.check_value(value)?;
+
+        let (index, found) = self.find_index(&key);
+        if found {
+            return Ok(mem::replace(&mut self.values[index], value));
+        }
+
+        self.insert_at(index, key, value);
+        Ok(self.missing_value)
+    }
+
+    /// Removes `key`, returning its value or the missing value.
+    pub fn remove<Q>(&mut self, key: &Q) -> i64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, found) = self.find_index(key);
+        if !found {
+            return self.missing_value;
+        }
+
+        self.remove_at(index).1
+    }
+
+    /// Entry for `key`, for inspecting and updating it with a single probe.
+    pub fn entry(&mut self, key: K) -> Object2LongEntry<'_, K> {
+        let (index, found) = self.find_index(&key);
+        if found {
+            Object2LongEntry::Occupied(Object2LongOccupiedEntry { map: self, index })
+        } else {
+            Object2LongEntry::Vacant(Object2LongVacantEntry { map: self, index, key })
+        }
+    }
+
+    /// Adds `delta` to the value for `key`, treating an absent key as zero, and returns the new
+    /// value. A result equal to the missing value removes the key.
+    pub fn increment(&mut self, key: K, delta: i64) -> i64 {
+        let (index, found) = self.find_index(&key);
+        let old_value = if found { self.values[index] } else { 0 };
+        let new_value = old_value.wrapping_add(delta);
+
+        if new_value == self.missing_value {
+            if found {
+                self.remove_at(index);
+            }
+        } else if found {
+            self.values[index] = new_value;
+        } else {
+            self.insert_at(index, key, new_value);
+        }
+
+        new_value
+    }
+
+    pub fn clear(&mut self) {
+        if self.size > 0 {
+            self.keys.iter_mut().for_each(|key| *key = None);
+            self.values.fill(self.missing_value);
+            self.size = 0;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, i64)> + '_ {
+        self.keys
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(key, &value)| key.as_ref().map(|key| (key, value)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.keys.iter().flatten()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn check_value(&self, value: i64) -> Result<()> {
+        if value == self.missing_value {
+            return Err(AgronaError::IllegalArgument(format!(
+                "cannot store the missing value {}",
+                value
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn insert_at(&mut self, index: usize, key: K, value: i64) {
+        self.keys[index] = Some(key);
+        self.values[index] = value;
+        self.size += 1;
+
+        if self.size > self.resize_threshold {
+            self.resize();
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, i64) {
+        let key = self.keys[index].take().expect("slot is occupied");
+        let value = mem::replace(&mut self.values[index], self.missing_value);
+        self.size -= 1;
+
+        self.compact_chain(index);
+
+        (key, value)
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = self.keys.len() * 2;
+        let old_keys = mem::replace(&mut self.keys, (0..new_capacity).map(|_| None).collect());
+        let old_values = mem::replace(&mut self.values, vec![self.missing_value; new_capacity]);
+        self.mask = new_capacity - 1;
+        self.resize_threshold = (new_capacity as f32 * DEFAULT_LOAD_FACTOR) as usize;
+
+        for (key, value) in old_keys.into_iter().zip(old_values) {
+            if let Some(key) = key {
+                let (index, _) = self.find_index(&key);
+                self.keys[index] = Some(key);
+                self.values[index] = value;
+            }
+        }
+    }
+
+    fn compact_chain(&mut self, mut deleted_index: usize) {
+        let mut index = (deleted_index + 1) & self.mask;
+
+        while let Some(key) = &self.keys[index] {
+            let ideal_index = Self::hash_key(key) & self.mask;
+
+            if should_move_entry(deleted_index, index, ideal_index) {
+                self.keys[deleted_index] = self.keys[index].take();
+                self.values[deleted_index] = mem::replace(&mut self.values[index], self.missing_value);
+                deleted_index = index;
+            }
+
+            index = (index + 1) & self.mask;
+        }
+    }
+}
+
+pub enum Object2LongEntry<'a, K> {
+    Occupied(Object2LongOccupiedEntry<'a, K>),
+    Vacant(Object2LongVacantEntry<'a, K>),
+}
+
+impl<'a, K: Hash + Eq> Object2LongEntry<'a, K> {
+    /// Inserts `value` if vacant, returning the resulting value.
+    pub fn or_insert(self, value: i64) -> Result<i64> {
+        self.or_insert_with(|| value)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> i64>(self, default: F) -> Result<i64> {
+        match self {
+            Self::Occupied(entry) => Ok(entry.get()),
+            Self::Vacant(entry) => {
+                let value = default();
+                entry.insert(value)?;
+                Ok(value)
+            }
+        }
+    }
+
+    /// Replaces an occupied value with `f(value)`; the missing value removes the entry.
+    pub fn and_modify<F: FnOnce(i64) -> i64>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(entry) => {
+                let new_value = f(entry.get());
+                if new_value == entry.map.missing_value {
+                    let Object2LongOccupiedEntry { map, index } = entry;
+                    let (key, _) = map.remove_at(index);
+                    let (index, _) = map.find_index(&key);
+                    Self::Vacant(Object2LongVacantEntry { map, index, key })
+                } else {
+                    entry.map.values[entry.index] = new_value;
+                    Self::Occupied(entry)
+                }
+            }
+            vacant => vacant,
+        }
+    }
+}
+
+pub struct Object2LongOccupiedEntry<'a, K> {
+    map: &'a mut Object2LongHashMap<K>,
+    index: usize,
+}
+
+impl<'a, K: Hash + Eq> Object2LongOccupiedEntry<'a, K> {
+    pub fn key(&self) -> &K {
+        self.map.keys[self.index].as_ref().expect("slot is occupied")
+    }
+
+    pub fn get(&self) -> i64 {
+        self.map.values[self.index]
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: i64) -> Result<i64> {
+        self.map.check_value(value)?;
+        Ok(mem::replace(&mut self.map.values[self.index], value))
+    }
+
+    pub fn remove(self) -> (K, i64) {
+        self.map.remove_at(self.index)
+    }
+}
+
+pub struct Object2LongVacantEntry<'a, K> {
+    map: &'a mut Object2LongHashMap<K>,
+    index: usize,
+    key: K,
+}
+
+impl<'a, K: Hash + Eq> Object2LongVacantEntry<'a, K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn insert(self, value: i64) -> Result<()> {
+        self.map.check_value(value)?;
+        self.map.insert_at(self.index, self.key, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_lookup_and_entry() {
+        let mut map = Object2LongHashMap::new(i64::MIN);
+
+        assert_eq!(map.put("BTC-USD".to_string(), 1 << 40).unwrap(), i64::MIN);
+        assert_eq!(map.get("BTC-USD"), 1 << 40);
+        assert_eq!(map.get("ETH-USD"), i64::MIN);
+        assert!(matches!(map.put("X".to_string(), i64::MIN), Err(AgronaError::IllegalArgument(_))));
+
+        assert_eq!(map.entry("ETH-USD".to_string()).or_insert_with(|| -7).unwrap(), -7);
+        assert_eq!(map.entry("BTC-USD".to_string()).and_modify(|value| value + 1).or_insert(0).unwrap(), (1 << 40) + 1);
+        assert_eq!(map.increment("ETH-USD".to_string(), 14), 7);
+
+        let mut entries: Vec<_> = map.iter().map(|(key, value)| (key.as_str(), value)).collect();
+        entries.sort();
+        assert_eq!(entries, vec![("BTC-USD", (1 << 40) + 1), ("ETH-USD", 7)]);
+    }
+
+    #[test]
+    fn test_resize_and_removal() {
+        let mut map = Object2LongHashMap::with_capacity(4, -1);
+
+        for id in 0..1000i64 {
+            map.put(id.to_string(), id << 32).unwrap();
+        }
+        for id in (0..1000i64).step_by(2) {
+            assert_eq!(map.remove(id.to_string().as_str()), id << 32);
+        }
+
+        assert_eq!(map.len(), 500);
+        for id in 0..1000i64 {
+            assert_eq!(map.contains_key(id.to_string().as_str()), id % 2 == 1);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Randomized operation sequences checked against the std collections, with keys drawn from a
+//! small range around zero plus the extremes so that collisions, removals and `i32::MIN` are
+//! all exercised. Each sequence runs with both immediate and incremental resizing, the latter
+//! migrating a single slot per operation so that migrations span many operations.
+
+use agrona_collections::{IntHashMap, IntHashSet};
+use std::collections::{HashMap, HashSet};
+
+const SEEDS: u64 = 32;
+const OPERATIONS: usize = 5_000;
+
+/// xorshift64*, enough to vary operation sequences reproducibly by seed.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn key(&mut self) -> i32 {
+        match self.next() % 10 {
+            0 => i32::MIN,
+            1 => i32::MAX,
+            _ => (self.next() % 128) as i32 - 64,
+        }
+    }
+}
+
+#[test]
+fn test_int_hash_map_matches_std() {
+    for (seed, incremental) in (1..=SEEDS).flat_map(|seed| [(seed, false), (seed, true)]) {
+        let mut random = Random(seed);
+        let mut map = IntHashMap::new();
+        map.set_incremental_resize(incremental);
+        map.set_migration_batch(1);
+        let mut migrating_operations = 0;
+        let mut expected = HashMap::new();
+
+        for _ in 0..OPERATIONS {
+            let key = random.key();
+            migrating_operations += map.is_migrating() as usize;
+            match random.next() % 6 {
+                0 | 1 => {
+                    let value = random.next() as i64;
+                    assert_eq!(
+                        map.insert(key, value),
+                        expected.insert(key, value),
+                        "seed {} incremental {}",
+                        seed,
+                        incremental,
+                    );
+                }
+                2 => assert_eq!(map.remove(key), expected.remove(&key), "seed {} incremental {}", seed, incremental),
+                3 => {
+                    if let Some(value) = map.get_mut(key) {
+                        *value += 1;
+                    }
+                    if let Some(value) = expected.get_mut(&key) {
+                        *value += 1;
+                    }
+                }
+                4 => assert_eq!(
+                    map.contains_key(key),
+                    expected.contains_key(&key),
+                    "seed {} incremental {}",
+                    seed,
+                    incremental,
+                ),
+                _ => match random.next() % 100 {
+                    0 => {
+                        map.clear();
+                        expected.clear();
+                    }
+                    1 => {
+                        let modulus = 2 + (random.next() % 5) as i32;
+                        map.retain(|key, _| key % modulus != 0);
+                        expected.retain(|key, _| key % modulus != 0);
+                    }
+                    _ => {}
+                },
+            }
+
+            assert_eq!(map.get(key), expected.get(&key), "seed {} incremental {}", seed, incremental);
+            assert_eq!(map.len(), expected.len(), "seed {} incremental {}", seed, incremental);
+        }
+
+        let mut entries: Vec<_> = map.iter().map(|(key, &value)| (key, value)).collect();
+        let mut expected_entries: Vec<_> = expected.into_iter().collect();
+        entries.sort();
+        expected_entries.sort();
+        assert_eq!(entries, expected_entries, "seed {} incremental {}", seed, incremental);
+        // Guards against the sequence no longer reaching the migration path at all
+        assert!(
+            !incremental || migrating_operations > 50,
+            "seed {} migrated for {} operations",
+            seed,
+            migrating_operations
+        );
+        assert_eq!(map.keys().count(), map.len());
+        assert_eq!(map.values().count(), map.len());
+    }
+}
+
+#[test]
+fn test_int_hash_set_matches_std() {
+    for (seed, incremental) in (1..=SEEDS).flat_map(|seed| [(seed, false), (seed, true)]) {
+        let mut random = Random(seed);
+        let mut set = IntHashSet::new();
+        set.set_incremental_resize(incremental);
+        set.set_migration_batch(1);
+        let mut migrating_operations = 0;
+        let mut expected = HashSet::new();
+
+        for _ in 0..OPERATIONS {
+            let key = random.key();
+            migrating_operations += set.is_migrating() as usize;
+            match random.next() % 5 {
+                0 | 1 => assert_eq!(set.insert(key), expected.insert(key), "seed {} incremental {}", seed, incremental),
+                2 | 3 => assert_eq!(
+                    set.remove(key),
+                    expected.remove(&key),
+                    "seed {} incremental {}",
+                    seed,
+                    incremental,
+                ),
+                _ => match random.next() % 100 {
+                    0 => {
+                        set.clear();
+                        expected.clear();
+                    }
+                    1 => {
+                        let modulus = 2 + (random.next() % 5) as i32;
+                        set.retain(|key| key % modulus != 0);
+                        expected.retain(|key| key % modulus != 0);
+                    }
+                    _ => {}
+                },
+            }
+
+            assert_eq!(set.contains(key), expected.contains(&key), "seed {} incremental {}", seed, incremental);
+            assert_eq!(set.len(), expected.len(), "seed {} incremental {}", seed, incremental);
+        }
+
+        let mut values: Vec<_> = set.iter().collect();
+        let mut expected_values: Vec<_> = expected.into_iter().collect();
+        values.sort();
+        expected_values.sort();
+        assert_eq!(values, expected_values, "seed {} incremental {}", seed, incremental);
+        // Guards against the sequence no longer reaching the migration path at all
+        assert!(
+            !incremental || migrating_operations > 50,
+            "seed {} migrated for {} operations",
+            seed,
+            migrating_operations
+        );
+    }
+}
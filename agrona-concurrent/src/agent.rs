@@ -0,0 +1,42 @@
+use agrona_core::error::{AgronaError, Result};
+
+/// A unit of work driven by an [`crate::AgentRunner`] thread or an [`crate::AgentInvoker`].
+///
+/// Returning [`AgronaError::AgentTermination`] from any callback asks the driver to stop the agent.
+pub trait Agent: Send {
+    fn on_start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Performs one duty cycle, returning the amount of work done so idle strategies can back off.
+    fn do_work(&mut self) -> Result<usize>;
+
+    fn on_close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn role_name(&self) -> &str;
+}
+
+impl<A: Agent + ?Sized> Agent for Box<A> {
+    fn on_start(&mut self) -> Result<()> {
+        (**self).on_start()
+    }
+
+    fn do_work(&mut self) -> Result<usize> {
+        (**self).do_work()
+    }
+
+    fn on_close(&mut self) -> Result<()> {
+        (**self).on_close()
+    }
+
+    fn role_name(&self) -> &str {
+        (**self).role_name()
+    }
+}
+
+#[inline]
+pub(crate) fn is_termination(error: &AgronaError) -> bool {
+    matches!(error, AgronaError::AgentTermination(_))
+}
@@ -0,0 +1,157 @@
+use crate::agent::{is_termination, Agent};
+use crate::error_handler::ErrorHandler;
+use std::sync::Arc;
+
+/// Drives an [`Agent`] on the caller's thread, one duty cycle per [`AgentInvoker::invoke`].
+pub struct AgentInvoker<A: Agent> {
+    agent: A,
+    error_handler: Arc<dyn ErrorHandler>,
+    is_started: bool,
+    is_running: bool,
+    is_closed: bool,
+}
+
+impl<A: Agent> AgentInvoker<A> {
+    pub fn new(agent: A, error_handler: Arc<dyn ErrorHandler>) -> Self {
+        Self {
+            agent,
+            error_handler,
+            is_started: false,
+            is_running: false,
+            is_closed: false,
+        }
+    }
+
+    pub fn agent(&self) -> &A {
+        &self.agent
+    }
+
+    pub fn agent_mut(&mut self) -> &mut A {
+        &mut self.agent
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.is_started
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    pub fn start(&mut self) {
+        if self.is_started {
+            return;
+        }
+
+        self.is_started = true;
+        match self.agent.on_start() {
+            Ok(()) => self.is_running = true,
+            Err(error) => {
+                self.error_handler.on_error(&error);
+                self.close();
+            }
+        }
+    }
+
+    /// Runs a single duty cycle, returning 0 if the agent is not running.
+    pub fn invoke(&mut self) -> usize {
+        if !self.is_running {
+            return 0;
+        }
+
+        match self.agent.do_work() {
+            Ok(work_count) => work_count,
+            Err(error) => {
+                self.error_handler.on_error(&error);
+                if is_termination(&error) {
+                    self.close();
+                }
+                0
+            }
+        }
+    }
+
+    pub fn close(&mut self) {
+        if self.is_closed {
+            return;
+        }
+
+        self.is_running = false;
+        self.is_closed = true;
+        if let Err(error) = self.agent.on_close() {
+            self.error_handler.on_error(&error);
+        }
+    }
+}
+
+impl<A: Agent> Drop for AgentInvoker<A> {
+    fn drop(&mut self) {
+        if self.is_started {
+            self.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agrona_core::error::{AgronaError, Result};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct StepAgent {
+        steps: usize,
+        closes: Arc<AtomicUsize>,
+    }
+
+    impl Agent for StepAgent {
+        fn do_work(&mut self) -> Result<usize> {
+            self.steps += 1;
+            if self.steps == 3 {
+                return Err(AgronaError::AgentTermination("three steps".to_string()));
+            }
+            Ok(self.steps)
+        }
+
+        fn on_close(&mut self) -> Result<()> {
+            self.closes.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn role_name(&self) -> &str {
+            "step-agent"
+        }
+    }
+
+    #[test]
+    fn test_invoke_lifecycle() {
+        let closes = Arc::new(AtomicUsize::new(0));
+        let errors = Arc::new(AtomicUsize::new(0));
+        let handler_errors = Arc::clone(&errors);
+        let mut invoker = AgentInvoker::new(
+            StepAgent { steps: 0, closes: Arc::clone(&closes) },
+            Arc::new(move |_: &AgronaError| {
+                handler_errors.fetch_add(1, Ordering::Relaxed);
+            }),
+        );
+
+        assert_eq!(invoker.invoke(), 0);
+
+        invoker.start();
+        assert!(invoker.is_running());
+        assert_eq!(invoker.invoke(), 1);
+        assert_eq!(invoker.invoke(), 2);
+        assert_eq!(invoker.invoke(), 0);
+
+        assert!(invoker.is_closed());
+        assert_eq!(errors.load(Ordering::Relaxed), 1);
+        assert_eq!(invoker.invoke(), 0);
+        assert_eq!(invoker.agent().steps, 3);
+
+        drop(invoker);
+        assert_eq!(closes.load(Ordering::Relaxed), 1);
+    }
+}
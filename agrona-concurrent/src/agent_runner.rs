@@ -0,0 +1,356 @@
+use crate::agent::{is_termination, Agent};
+use crate::error_handler::ErrorHandler;
+use crate::idle_strategy::IdleStrategy;
+use agrona_core::error::{AgronaError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Thread settings applied by [`AgentRunner::start_with_options`] before the agent starts.
+#[derive(Debug, Clone, Default)]
+pub struct AgentRunnerOptions {
+    thread_name: Option<String>,
+    cpu_affinity: Option<Vec<usize>>,
+    sched_fifo_priority: Option<i32>,
+}
+
+impl AgentRunnerOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the thread, defaulting to the agent role name.
+    pub fn thread_name(mut self, thread_name: impl Into<String>) -> Self {
+        self.thread_name = Some(thread_name.into());
+        self
+    }
+
+    /// Pins the thread to `cores`, e.g. as parsed by `thread_affinity::parse_core_list`. Linux only.
+    pub fn cpu_affinity(mut self, cores: Vec<usize>) -> Self {
+        self.cpu_affinity = Some(cores);
+        self
+    }
+
+    /// Runs the thread under `SCHED_FIFO` at `priority`. Linux only.
+    pub fn sched_fifo_priority(mut self, priority: i32) -> Self {
+        self.sched_fifo_priority = Some(priority);
+        self
+    }
+
+    #[cfg(target_os = "linux")]
+    fn apply_to_current_thread(&self) -> Result<()> {
+        use crate::thread_affinity;
+
+        if let Some(cores) = &self.cpu_affinity {
+            thread_affinity::set_current_thread_affinity(cores)?;
+        }
+        if let Some(priority) = self.sched_fifo_priority {
+            thread_affinity::set_current_thread_sched_fifo(priority)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn apply_to_current_thread(&self) -> Result<()> {
+        if self.cpu_affinity.is_some() || self.sched_fifo_priority.is_some() {
+            return Err(AgronaError::IllegalState(
+                "cpu affinity and SCHED_FIFO are only supported on Linux".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs an [`Agent`] on a dedicated thread named after its role until closed.
+pub struct AgentRunner {
+    role_name: String,
+    is_running: Arc<AtomicBool>,
+    is_closed: bool,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AgentRunner {
+    pub fn start<A, I>(agent: A, idle_strategy: I, error_handler: Arc<dyn ErrorHandler>) -> Result<Self>
+    where
+        A: Agent + 'static,
+        I: IdleStrategy + Send + 'static,
+    {
+        Self::start_with_options(agent, idle_strategy, error_handler, AgentRunnerOptions::default())
+    }
+
+    /// Starts the agent on a thread configured by `options`.
+    ///
+    /// If the options cannot be applied, e.g. for lack of permissions, the agent is dropped
+    /// without starting and the error is returned.
+    pub fn start_with_options<A, I>(
+        agent: A,
+        idle_strategy: I,
+        error_handler: Arc<dyn ErrorHandler>,
+        options: AgentRunnerOptions,
+    ) -> Result<Self>
+    where
+        A: Agent + 'static,
+        I: IdleStrategy + Send + 'static,
+    {
+        let role_name = agent.role_name().to_string();
+        let thread_name = options.thread_name.clone().unwrap_or_else(|| role_name.clone());
+        let is_running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&is_running);
+        let (setup_sender, setup_receiver) = mpsc::channel();
+
+        let thread = thread::Builder::new().name(thread_name).spawn(move || {
+            let setup = options.apply_to_current_thread();
+            let is_ready = setup.is_ok();
+            let _ = setup_sender.send(setup);
+            if is_ready {
+                run(agent, idle_strategy, error_handler, thread_running);
+            }
+        })?;
+
+        let setup = setup_receiver
+            .recv()
+            .unwrap_or_else(|_| Err(AgronaError::IllegalState(format!("agent {} thread panicked", role_name))));
+        if let Err(error) = setup {
+            is_running.store(false, Ordering::Release);
+            let _ = thread.join();
+            return Err(error);
+        }
+
+        Ok(Self {
+            role_name,
+            is_running,
+            is_closed: false,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn role_name(&self) -> &str {
+        &self.role_name
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::Acquire)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        self.close_with_timeout(DEFAULT_CLOSE_TIMEOUT)
+    }
+
+    /// Signals the agent to stop and waits up to `timeout` for its thread to run `on_close` and exit.
+    pub fn close_with_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.is_running.store(false, Ordering::Release);
+
+        let Some(thread) = self.thread.take() else {
+            self.is_closed = true;
+            return Ok(());
+        };
+
+        let deadline = Instant::now() + timeout;
+        while !thread.is_finished() {
+            if Instant::now() >= deadline {
+                self.thread = Some(thread);
+                return Err(AgronaError::IllegalState(format!(
+                    "agent {} did not stop within {:?}",
+                    self.role_name, timeout
+                )));
+            }
+            thread.thread().unpark();
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        self.is_closed = true;
+        thread.join().map_err(|_| {
+            AgronaError::IllegalState(format!("agent {} thread panicked", self.role_name))
+        })
+    }
+}
+
+impl Drop for AgentRunner {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+fn run<A, I>(mut agent: A, mut idle_strategy: I, error_handler: Arc<dyn ErrorHandler>, is_running: Arc<AtomicBool>)
+where
+    A: Agent,
+    I: IdleStrategy,
+{
+    if let Err(error) = agent.on_start() {
+        is_running.store(false, Ordering::Release);
+        error_handler.on_error(&error);
+    }
+
+    while is_running.load(Ordering::Acquire) {
+        match agent.do_work() {
+            Ok(work_count) => idle_strategy.idle(work_count),
+            Err(error) => {
+                if is_termination(&error) {
+                    is_running.store(false, Ordering::Release);
+                }
+                error_handler.on_error(&error);
+            }
+        }
+    }
+
+    if let Err(error) = agent.on_close() {
+        error_handler.on_error(&error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::idle_strategy::{BusySpinIdleStrategy, SleepingIdleStrategy};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+
+    struct CountingAgent {
+        work: Arc<AtomicUsize>,
+        started_on: Arc<Mutex<Option<String>>>,
+        closed: Arc<AtomicBool>,
+        fail_every: usize,
+    }
+
+    impl Agent for CountingAgent {
+        fn on_start(&mut self) -> Result<()> {
+            *self.started_on.lock().unwrap() = thread::current().name().map(str::to_string);
+            Ok(())
+        }
+
+        fn do_work(&mut self) -> Result<usize> {
+            let count = self.work.fetch_add(1, Ordering::Relaxed) + 1;
+            if self.fail_every > 0 && count.is_multiple_of(self.fail_every) {
+                return Err(AgronaError::IllegalState(format!("failure at {}", count)));
+            }
+            Ok(1)
+        }
+
+        fn on_close(&mut self) -> Result<()> {
+            self.closed.store(true, Ordering::Release);
+            Ok(())
+        }
+
+        fn role_name(&self) -> &str {
+            "counting-agent"
+        }
+    }
+
+    fn wait_for(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition not met in time");
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_runs_agent_on_named_thread() {
+        let work = Arc::new(AtomicUsize::new(0));
+        let started_on = Arc::new(Mutex::new(None));
+        let closed = Arc::new(AtomicBool::new(false));
+        let errors = Arc::new(AtomicUsize::new(0));
+        let handler_errors = Arc::clone(&errors);
+
+        let agent = CountingAgent {
+            work: Arc::clone(&work),
+            started_on: Arc::clone(&started_on),
+            closed: Arc::clone(&closed),
+            fail_every: 10,
+        };
+        let mut runner = AgentRunner::start(
+            agent,
+            BusySpinIdleStrategy::new(),
+            Arc::new(move |_: &AgronaError| {
+                handler_errors.fetch_add(1, Ordering::Relaxed);
+            }),
+        )
+        .unwrap();
+
+        wait_for(|| errors.load(Ordering::Relaxed) >= 3);
+        assert!(runner.is_running());
+        assert_eq!(runner.role_name(), "counting-agent");
+
+        runner.close().unwrap();
+        assert!(runner.is_closed());
+        assert!(!runner.is_running());
+        assert!(closed.load(Ordering::Acquire));
+        assert_eq!(started_on.lock().unwrap().as_deref(), Some("counting-agent"));
+    }
+
+    struct TerminatingAgent;
+
+    impl Agent for TerminatingAgent {
+        fn do_work(&mut self) -> Result<usize> {
+            Err(AgronaError::AgentTermination("done".to_string()))
+        }
+
+        fn role_name(&self) -> &str {
+            "terminating-agent"
+        }
+    }
+
+    #[test]
+    fn test_termination_error_stops_runner() {
+        let mut runner = AgentRunner::start(
+            TerminatingAgent,
+            SleepingIdleStrategy::new(Duration::from_millis(1)),
+            Arc::new(|_: &AgronaError| {}),
+        )
+        .unwrap();
+
+        wait_for(|| !runner.is_running());
+        runner.close_with_timeout(Duration::from_secs(1)).unwrap();
+        assert!(runner.is_closed());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_start_with_options() {
+        use crate::thread_affinity::{current_thread_affinity, MAX_CPUS};
+
+        let core = current_thread_affinity().unwrap()[0];
+        let started_on = Arc::new(Mutex::new(None));
+        let agent = |started_on: &Arc<Mutex<Option<String>>>| CountingAgent {
+            work: Arc::new(AtomicUsize::new(0)),
+            started_on: Arc::clone(started_on),
+            closed: Arc::new(AtomicBool::new(false)),
+            fail_every: 0,
+        };
+
+        let mut runner = AgentRunner::start_with_options(
+            agent(&started_on),
+            BusySpinIdleStrategy::new(),
+            Arc::new(|_: &AgronaError| {}),
+            AgentRunnerOptions::new().thread_name("pinned-agent").cpu_affinity(vec![core]),
+        )
+        .unwrap();
+        wait_for(|| started_on.lock().unwrap().is_some());
+        assert_eq!(started_on.lock().unwrap().as_deref(), Some("pinned-agent"));
+        runner.close().unwrap();
+
+        let not_started = Arc::new(Mutex::new(None));
+        for options in [
+            AgentRunnerOptions::new().cpu_affinity(vec![MAX_CPUS - 1]),
+            AgentRunnerOptions::new().sched_fifo_priority(1_000),
+        ] {
+            let result = AgentRunner::start_with_options(
+                agent(&not_started),
+                BusySpinIdleStrategy::new(),
+                Arc::new(|_: &AgronaError| {}),
+                options,
+            );
+            assert!(matches!(result, Err(AgronaError::IllegalArgument(_))));
+        }
+        assert!(not_started.lock().unwrap().is_none());
+    }
+}
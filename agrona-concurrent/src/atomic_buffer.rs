@@ -27,6 +27,14 @@ impl AtomicBuffer {
         }
     }
 
+    pub fn as_ptr(&self) -> *const u8 {
+        self.inner.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.inner.as_mut_ptr()
+    }
+
     #[inline]
     pub fn get_volatile_u8(&self, index: usize) -> Result<u8> {
         self.bounds_check(index, 1)?;
@@ -406,6 +414,8 @@ mod tests {
         buffer.put_ordered_u32(0, 100).unwrap();
         assert_eq!(buffer.get_volatile_u32(0).unwrap(), 100);
 
+        // AtomicBuffer::new does not zero its memory, so start the counter from a known value
+        buffer.put_volatile_u64(8, 0).unwrap();
         buffer.add_ordered_u64(8, 50).unwrap();
         buffer.add_ordered_u64(8, 25).unwrap();
         assert_eq!(buffer.get_volatile_u64(8).unwrap(), 75);
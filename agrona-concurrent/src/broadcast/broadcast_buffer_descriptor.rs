@@ -0,0 +1,34 @@
+//! Layout of the trailer that follows the records of a broadcast buffer.
+
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::{is_power_of_two, CACHE_LINE_LENGTH, SIZE_OF_I64};
+use agrona_core::error::{AgronaError, Result};
+
+/// Position the transmitter is about to write up to, published before the record is written.
+pub const TAIL_INTENT_COUNTER_OFFSET: usize = 0;
+/// Position up to which records are complete.
+pub const TAIL_COUNTER_OFFSET: usize = TAIL_INTENT_COUNTER_OFFSET + SIZE_OF_I64;
+/// Position of the start of the most recently completed record.
+pub const LATEST_COUNTER_OFFSET: usize = TAIL_COUNTER_OFFSET + SIZE_OF_I64;
+pub const TRAILER_LENGTH: usize = CACHE_LINE_LENGTH * 2;
+
+/// Validates that `buffer` is a power-of-two record region followed by the trailer and returns
+/// the capacity of the record region.
+pub fn check_capacity(buffer: &SharedBuffer) -> Result<usize> {
+    let length = buffer.capacity();
+    if length < TRAILER_LENGTH {
+        return Err(AgronaError::InvalidLayout(format!(
+            "broadcast buffer length {} is less than trailer length {}",
+            length, TRAILER_LENGTH
+        )));
+    }
+
+    let capacity = length - TRAILER_LENGTH;
+    if !is_power_of_two(capacity as u64) || capacity > i32::MAX as usize {
+        return Err(AgronaError::InvalidCapacity { capacity });
+    }
+
+    buffer.check_aligned(capacity, TRAILER_LENGTH, SIZE_OF_I64)?;
+
+    Ok(capacity)
+}
@@ -0,0 +1,132 @@
+use crate::broadcast::broadcast_buffer_descriptor::{
+    check_capacity, LATEST_COUNTER_OFFSET, TAIL_COUNTER_OFFSET, TAIL_INTENT_COUNTER_OFFSET,
+};
+use crate::broadcast::record_descriptor::{
+    length_offset, msg_offset, type_offset, ALIGNMENT, HEADER_LENGTH, PADDING_MSG_TYPE_ID,
+};
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::align;
+use agrona_core::error::Result;
+use std::sync::atomic::{fence, Ordering};
+
+/// Receives messages from a [`BroadcastTransmitter`](crate::broadcast::BroadcastTransmitter),
+/// starting from the latest message at the time of attaching.
+///
+/// The transmitter may overwrite a record while it is being read, so after
+/// [`receive_next`](Self::receive_next) a message must be copied out and then confirmed with
+/// [`validate`](Self::validate). [`CopyBroadcastReceiver`](crate::broadcast::CopyBroadcastReceiver)
+/// does this for you.
+pub struct BroadcastReceiver {
+    buffer: SharedBuffer,
+    capacity: usize,
+    mask: i64,
+    tail_intent_counter_index: usize,
+    tail_counter_index: usize,
+    record_offset: usize,
+    cursor: i64,
+    next_record: i64,
+    lapped_count: u64,
+}
+
+impl BroadcastReceiver {
+    /// Attaches to `buffer`, validating its layout but leaving its contents untouched.
+    pub fn new(buffer: impl Into<SharedBuffer>) -> Result<Self> {
+        let buffer = buffer.into();
+        let capacity = check_capacity(&buffer)?;
+        let mask = capacity as i64 - 1;
+        let cursor = buffer.get_i64_volatile(capacity + LATEST_COUNTER_OFFSET);
+
+        Ok(Self {
+            buffer,
+            capacity,
+            mask,
+            tail_intent_counter_index: capacity + TAIL_INTENT_COUNTER_OFFSET,
+            tail_counter_index: capacity + TAIL_COUNTER_OFFSET,
+            record_offset: (cursor & mask) as usize,
+            cursor,
+            next_record: cursor,
+            lapped_count: 0,
+        })
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of times the transmitter has overtaken this receiver.
+    #[inline]
+    pub fn lapped_count(&self) -> u64 {
+        self.lapped_count
+    }
+
+    /// Moves to the next available message, returning `false` if there is none.
+    pub fn receive_next(&mut self) -> bool {
+        let buffer = &self.buffer;
+        let tail = buffer.get_i64_volatile(self.tail_counter_index);
+        let mut cursor = self.next_record;
+
+        if tail <= cursor {
+            return false;
+        }
+
+        if !self.is_valid(cursor) {
+            self.lapped_count += 1;
+            cursor = buffer.get_i64(self.capacity + LATEST_COUNTER_OFFSET);
+        }
+
+        let mut record_offset = (cursor & self.mask) as usize;
+        if buffer.get_i32(type_offset(record_offset)) == PADDING_MSG_TYPE_ID {
+            cursor += (self.capacity - record_offset) as i64;
+            record_offset = 0;
+        }
+
+        let record_length = buffer.get_i32(length_offset(record_offset)).max(0) as usize;
+        self.cursor = cursor;
+        self.next_record = cursor + align(record_length, ALIGNMENT) as i64;
+        self.record_offset = record_offset;
+
+        true
+    }
+
+    #[inline]
+    pub fn type_id(&self) -> i32 {
+        self.buffer.get_i32(type_offset(self.record_offset))
+    }
+
+    /// Offset of the current message within the buffer.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        msg_offset(self.record_offset)
+    }
+
+    /// Length of the current message, clamped so a torn record cannot index out of bounds.
+    #[inline]
+    pub fn length(&self) -> usize {
+        let record_length = self.buffer.get_i32(length_offset(self.record_offset)).max(0) as usize;
+        record_length
+            .saturating_sub(HEADER_LENGTH)
+            .min(self.capacity - self.offset())
+    }
+
+    /// Copies the current message into `dst`, which must be at least [`length`](Self::length)
+    /// bytes, and returns the number of bytes copied. Confirm the copy with
+    /// [`validate`](Self::validate).
+    pub fn copy_message(&self, dst: &mut [u8]) -> usize {
+        let length = self.length().min(dst.len());
+        self.buffer.get_bytes(self.offset(), &mut dst[..length]);
+        length
+    }
+
+    /// Whether the current message is still intact, i.e. has not been overwritten since
+    /// [`receive_next`](Self::receive_next).
+    pub fn validate(&self) -> bool {
+        fence(Ordering::Acquire);
+        self.is_valid(self.cursor)
+    }
+
+    #[inline]
+    fn is_valid(&self, cursor: i64) -> bool {
+        cursor + self.capacity as i64 > self.buffer.get_i64_volatile(self.tail_intent_counter_index)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::broadcast::broadcast_buffer_descriptor::{
+    check_capacity, LATEST_COUNTER_OFFSET, TAIL_COUNTER_OFFSET, TAIL_INTENT_COUNTER_OFFSET,
+};
+use crate::broadcast::record_descriptor::{
+    check_type_id, length_offset, msg_offset, type_offset, ALIGNMENT, HEADER_LENGTH, PADDING_MSG_TYPE_ID,
+};
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::align;
+use agrona_core::error::{AgronaError, Result};
+
+/// Transmits messages to any number of [`BroadcastReceiver`](crate::broadcast::BroadcastReceiver)s
+/// over a [`SharedBuffer`]. Receivers that fall behind by more than the capacity are lapped
+/// rather than holding the transmitter back. There must be only one transmitter per buffer.
+pub struct BroadcastTransmitter {
+    buffer: SharedBuffer,
+    capacity: usize,
+    max_msg_length: usize,
+    tail_intent_counter_index: usize,
+    tail_counter_index: usize,
+    latest_counter_index: usize,
+}
+
+impl BroadcastTransmitter {
+    /// Attaches to `buffer`, validating its layout but leaving its contents untouched.
+    pub fn new(buffer: impl Into<SharedBuffer>) -> Result<Self> {
+        let buffer = buffer.into();
+        let capacity = check_capacity(&buffer)?;
+
+        Ok(Self {
+            buffer,
+            capacity,
+            max_msg_length: capacity >> 3,
+            tail_intent_counter_index: capacity + TAIL_INTENT_COUNTER_OFFSET,
+            tail_counter_index: capacity + TAIL_COUNTER_OFFSET,
+            latest_counter_index: capacity + LATEST_COUNTER_OFFSET,
+        })
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn max_msg_length(&self) -> usize {
+        self.max_msg_length
+    }
+
+    /// Transmits a message, overwriting the oldest records if receivers have not kept up.
+    pub fn transmit(&mut self, msg_type_id: i32, src: &[u8]) -> Result<()> {
+        check_type_id(msg_type_id)?;
+        if src.len() > self.max_msg_length {
+            return Err(AgronaError::IllegalArgument(format!(
+                "encoded message exceeds max message length of {}, length={}",
+                self.max_msg_length, src.len()
+            )));
+        }
+
+        let buffer = &self.buffer;
+        let mut current_tail = buffer.get_i64(self.tail_counter_index);
+        let mut record_offset = current_tail as usize & (self.capacity - 1);
+        let record_length = src.len() + HEADER_LENGTH;
+        let aligned_record_length = align(record_length, ALIGNMENT);
+        let new_tail = current_tail + aligned_record_length as i64;
+
+        let to_end_of_buffer = self.capacity - record_offset;
+        if to_end_of_buffer < aligned_record_length {
+            self.signal_tail_intent(new_tail + to_end_of_buffer as i64);
+            buffer.put_i32(length_offset(record_offset), to_end_of_buffer as i32);
+            buffer.put_i32(type_offset(record_offset), PADDING_MSG_TYPE_ID);
+
+            current_tail += to_end_of_buffer as i64;
+            record_offset = 0;
+        } else {
+            self.signal_tail_intent(new_tail);
+        }
+
+        buffer.put_i32(length_offset(record_offset), record_length as i32);
+        buffer.put_i32(type_offset(record_offset), msg_type_id);
+        buffer.put_bytes(msg_offset(record_offset), src);
+
+        buffer.put_i64(self.latest_counter_index, current_tail);
+        buffer.put_i64_ordered(self.tail_counter_index, current_tail + aligned_record_length as i64);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn signal_tail_intent(&self, new_tail: i64) {
+        self.buffer.put_i64_ordered(self.tail_intent_counter_index, new_tail);
+        // Receivers validate against the intent after copying, so the record writes must not be
+        // reordered ahead of it.
+        std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
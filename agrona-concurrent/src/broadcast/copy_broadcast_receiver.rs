@@ -0,0 +1,124 @@
+use crate::broadcast::BroadcastReceiver;
+use agrona_core::error::{AgronaError, Result};
+
+/// Wraps a [`BroadcastReceiver`], copying each message into a scratch buffer and validating it
+/// before handing it to a handler, so handlers never see a torn message.
+pub struct CopyBroadcastReceiver {
+    receiver: BroadcastReceiver,
+    scratch: Vec<u8>,
+}
+
+impl CopyBroadcastReceiver {
+    pub fn new(receiver: BroadcastReceiver) -> Self {
+        let scratch = vec![0; receiver.capacity() >> 3];
+        Self { receiver, scratch }
+    }
+
+    /// Receives the messages currently available, returning how many were passed to `handler`.
+    ///
+    /// Errors with `IllegalState` if the transmitter overwrote a message while it was copied.
+    pub fn receive<F>(&mut self, mut handler: F) -> Result<usize>
+    where
+        F: FnMut(i32, &[u8]),
+    {
+        let mut messages_received = 0;
+        let last_seen_lapped_count = self.receiver.lapped_count();
+
+        while self.receiver.receive_next() {
+            if last_seen_lapped_count != self.receiver.lapped_count() {
+                return Err(AgronaError::IllegalState("unable to keep up with broadcast".to_string()));
+            }
+
+            let msg_type_id = self.receiver.type_id();
+            let length = self.receiver.copy_message(&mut self.scratch);
+
+            if !self.receiver.validate() {
+                return Err(AgronaError::IllegalState("unable to keep up with broadcast".to_string()));
+            }
+
+            handler(msg_type_id, &self.scratch[..length]);
+            messages_received += 1;
+        }
+
+        Ok(messages_received)
+    }
+
+    pub fn receiver(&self) -> &BroadcastReceiver {
+        &self.receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::broadcast::broadcast_buffer_descriptor::TRAILER_LENGTH;
+    use crate::broadcast::BroadcastTransmitter;
+    use crate::shared_buffer::SharedBuffer;
+
+    const CAPACITY: usize = 1024;
+
+    fn transmitter_and_receiver() -> (BroadcastTransmitter, CopyBroadcastReceiver) {
+        let buffer = SharedBuffer::new(CAPACITY + TRAILER_LENGTH).unwrap();
+        let transmitter = BroadcastTransmitter::new(buffer.clone()).unwrap();
+        let receiver = CopyBroadcastReceiver::new(BroadcastReceiver::new(buffer).unwrap());
+        (transmitter, receiver)
+    }
+
+    #[test]
+    fn test_validates_layout() {
+        let not_power_of_two = SharedBuffer::new(1000 + TRAILER_LENGTH).unwrap();
+        assert!(matches!(
+            BroadcastTransmitter::new(not_power_of_two),
+            Err(AgronaError::InvalidCapacity { capacity: 1000 })
+        ));
+        assert!(matches!(
+            BroadcastReceiver::new(SharedBuffer::new(8).unwrap()),
+            Err(AgronaError::InvalidLayout(_))
+        ));
+
+        let (mut transmitter, _) = transmitter_and_receiver();
+        assert!(matches!(transmitter.transmit(0, b"x"), Err(AgronaError::IllegalArgument(_))));
+        assert!(matches!(
+            transmitter.transmit(1, &[0; CAPACITY / 8 + 1]),
+            Err(AgronaError::IllegalArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_receives_messages_across_wrap() {
+        let (mut transmitter, mut receiver) = transmitter_and_receiver();
+
+        for round in 0..200u32 {
+            let message = round.to_le_bytes().repeat(1 + round as usize % 20);
+            transmitter.transmit(5, &message).unwrap();
+
+            let mut received = Vec::new();
+            let count = receiver
+                .receive(|msg_type_id, payload| {
+                    assert_eq!(msg_type_id, 5);
+                    received.push(payload.to_vec());
+                })
+                .unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(received, vec![message]);
+        }
+
+        assert_eq!(receiver.receiver().lapped_count(), 0);
+    }
+
+    #[test]
+    fn test_lapped_receiver_errors_then_resumes_from_latest() {
+        let (mut transmitter, mut receiver) = transmitter_and_receiver();
+
+        for round in 0..64u8 {
+            transmitter.transmit(1, &[round; 56]).unwrap();
+        }
+        assert!(matches!(receiver.receive(|_, _| {}), Err(AgronaError::IllegalState(_))));
+        assert_eq!(receiver.receiver().lapped_count(), 1);
+
+        transmitter.transmit(1, &[64; 56]).unwrap();
+        let mut received = Vec::new();
+        receiver.receive(|_, payload| received.push(payload[0])).unwrap();
+        assert_eq!(received, vec![64]);
+    }
+}
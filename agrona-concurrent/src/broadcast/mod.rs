@@ -0,0 +1,9 @@
+pub mod broadcast_buffer_descriptor;
+pub mod broadcast_receiver;
+pub mod broadcast_transmitter;
+pub mod copy_broadcast_receiver;
+pub mod record_descriptor;
+
+pub use broadcast_receiver::*;
+pub use broadcast_transmitter::*;
+pub use copy_broadcast_receiver::*;
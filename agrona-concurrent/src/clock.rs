@@ -0,0 +1,358 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Milliseconds since the Unix epoch.
+pub trait EpochClock: Send + Sync {
+    fn time(&self) -> i64;
+}
+
+/// Monotonic nanoseconds from an arbitrary origin, only meaningful as differences.
+///
+/// Time-driven code should also wait through [`NanoClock::sleep`] so a [`TestClock`] can stand in
+/// for real time.
+pub trait NanoClock: Send + Sync {
+    fn nano_time(&self) -> i64;
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Nanoseconds since the Unix epoch.
+pub trait EpochNanoClock: Send + Sync {
+    fn epoch_nano_time(&self) -> i64;
+}
+
+impl<C: EpochClock + ?Sized> EpochClock for Arc<C> {
+    fn time(&self) -> i64 {
+        (**self).time()
+    }
+}
+
+impl<C: NanoClock + ?Sized> NanoClock for Arc<C> {
+    fn nano_time(&self) -> i64 {
+        (**self).nano_time()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+impl<C: EpochNanoClock + ?Sized> EpochNanoClock for Arc<C> {
+    fn epoch_nano_time(&self) -> i64 {
+        (**self).epoch_nano_time()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemEpochClock;
+
+impl EpochClock for SystemEpochClock {
+    #[inline]
+    fn time(&self) -> i64 {
+        duration_since_epoch().as_millis() as i64
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemNanoClock;
+
+impl NanoClock for SystemNanoClock {
+    #[inline]
+    fn nano_time(&self) -> i64 {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as i64
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemEpochNanoClock;
+
+impl EpochNanoClock for SystemEpochNanoClock {
+    #[inline]
+    fn epoch_nano_time(&self) -> i64 {
+        duration_since_epoch().as_nanos() as i64
+    }
+}
+
+fn duration_since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+}
+
+/// An [`EpochClock`] updated once per duty cycle by its owning agent and read with a single load.
+#[repr(align(64))]
+#[derive(Debug, Default)]
+pub struct CachedEpochClock {
+    time_ms: AtomicI64,
+}
+
+impl CachedEpochClock {
+    pub const fn new() -> Self {
+        Self {
+            time_ms: AtomicI64::new(0),
+        }
+    }
+
+    #[inline]
+    pub fn update(&self, time_ms: i64) {
+        self.time_ms.store(time_ms, Ordering::Release);
+    }
+
+    /// Single-writer advance of the cached time.
+    #[inline]
+    pub fn advance(&self, delta_ms: i64) {
+        let time_ms = self.time_ms.load(Ordering::Relaxed);
+        self.time_ms.store(time_ms + delta_ms, Ordering::Release);
+    }
+}
+
+impl EpochClock for CachedEpochClock {
+    #[inline]
+    fn time(&self) -> i64 {
+        self.time_ms.load(Ordering::Acquire)
+    }
+}
+
+/// A [`NanoClock`] updated once per duty cycle by its owning agent and read with a single load.
+#[repr(align(64))]
+#[derive(Debug, Default)]
+pub struct CachedNanoClock {
+    time_ns: AtomicI64,
+}
+
+impl CachedNanoClock {
+    pub const fn new() -> Self {
+        Self {
+            time_ns: AtomicI64::new(0),
+        }
+    }
+
+    #[inline]
+    pub fn update(&self, time_ns: i64) {
+        self.time_ns.store(time_ns, Ordering::Release);
+    }
+
+    /// Single-writer advance of the cached time.
+    #[inline]
+    pub fn advance(&self, delta_ns: i64) {
+        let time_ns = self.time_ns.load(Ordering::Relaxed);
+        self.time_ns.store(time_ns + delta_ns, Ordering::Release);
+    }
+}
+
+impl NanoClock for CachedNanoClock {
+    #[inline]
+    fn nano_time(&self) -> i64 {
+        self.time_ns.load(Ordering::Acquire)
+    }
+}
+
+pub const DEFAULT_MAX_MEASUREMENT_RETRIES: usize = 100;
+pub const DEFAULT_MEASUREMENT_THRESHOLD: Duration = Duration::from_micros(250);
+pub const DEFAULT_RESAMPLE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Epoch nanoseconds derived from the monotonic clock, calibrated against wall time.
+///
+/// Each sample brackets a wall clock read between two monotonic reads and keeps the tightest
+/// bracket. Between samples the result advances monotonically, so call [`OffsetEpochNanoClock::sample`]
+/// periodically (e.g. after [`OffsetEpochNanoClock::resample_interval`]) to follow wall clock adjustments.
+#[derive(Debug)]
+pub struct OffsetEpochNanoClock {
+    max_measurement_retries: usize,
+    measurement_threshold_ns: i64,
+    resample_interval_ns: i64,
+    initial_nano_time: i64,
+    initial_epoch_nano_time: i64,
+    is_within_threshold: bool,
+}
+
+impl OffsetEpochNanoClock {
+    pub fn new() -> Self {
+        Self::with_settings(
+            DEFAULT_MAX_MEASUREMENT_RETRIES,
+            DEFAULT_MEASUREMENT_THRESHOLD,
+            DEFAULT_RESAMPLE_INTERVAL,
+        )
+    }
+
+    pub fn with_settings(
+        max_measurement_retries: usize,
+        measurement_threshold: Duration,
+        resample_interval: Duration,
+    ) -> Self {
+        let mut clock = Self {
+            max_measurement_retries: max_measurement_retries.max(1),
+            measurement_threshold_ns: measurement_threshold.as_nanos() as i64,
+            resample_interval_ns: resample_interval.as_nanos() as i64,
+            initial_nano_time: 0,
+            initial_epoch_nano_time: 0,
+            is_within_threshold: false,
+        };
+        clock.sample();
+        clock
+    }
+
+    /// Recalibrates the offset, returning whether a measurement within the threshold was found.
+    pub fn sample(&mut self) -> bool {
+        let nano_clock = SystemNanoClock;
+        let epoch_clock = SystemEpochNanoClock;
+
+        let mut best_gap = i64::MAX;
+        let mut best_nano_time = 0;
+        let mut best_epoch_nano_time = 0;
+
+        for _ in 0..self.max_measurement_retries {
+            let start = nano_clock.nano_time();
+            let epoch_nano_time = epoch_clock.epoch_nano_time();
+            let end = nano_clock.nano_time();
+
+            let gap = end - start;
+            if gap < best_gap {
+                best_gap = gap;
+                best_nano_time = start + gap / 2;
+                best_epoch_nano_time = epoch_nano_time;
+            }
+
+            if gap <= self.measurement_threshold_ns {
+                break;
+            }
+        }
+
+        self.initial_nano_time = best_nano_time;
+        self.initial_epoch_nano_time = best_epoch_nano_time;
+        self.is_within_threshold = best_gap <= self.measurement_threshold_ns;
+        self.is_within_threshold
+    }
+
+    pub fn is_within_threshold(&self) -> bool {
+        self.is_within_threshold
+    }
+
+    pub fn resample_interval(&self) -> Duration {
+        Duration::from_nanos(self.resample_interval_ns as u64)
+    }
+
+    pub fn needs_resample(&self) -> bool {
+        SystemNanoClock.nano_time() - self.initial_nano_time >= self.resample_interval_ns
+    }
+}
+
+impl Default for OffsetEpochNanoClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EpochNanoClock for OffsetEpochNanoClock {
+    #[inline]
+    fn epoch_nano_time(&self) -> i64 {
+        self.initial_epoch_nano_time + (SystemNanoClock.nano_time() - self.initial_nano_time)
+    }
+}
+
+/// A manually advanced clock for deterministic tests of time-dependent logic.
+///
+/// Implements every clock trait over one nanosecond counter; sleeping advances the clock
+/// instead of blocking.
+#[derive(Debug, Default)]
+pub struct TestClock {
+    time_ns: AtomicI64,
+}
+
+impl TestClock {
+    pub const fn new(time_ns: i64) -> Self {
+        Self {
+            time_ns: AtomicI64::new(time_ns),
+        }
+    }
+
+    pub fn set(&self, time_ns: i64) {
+        self.time_ns.store(time_ns, Ordering::Release);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.time_ns
+            .fetch_add(duration.as_nanos() as i64, Ordering::AcqRel);
+    }
+}
+
+impl EpochClock for TestClock {
+    fn time(&self) -> i64 {
+        self.time_ns.load(Ordering::Acquire) / 1_000_000
+    }
+}
+
+impl NanoClock for TestClock {
+    fn nano_time(&self) -> i64 {
+        self.time_ns.load(Ordering::Acquire)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+impl EpochNanoClock for TestClock {
+    fn epoch_nano_time(&self) -> i64 {
+        self.time_ns.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agrona_core::bit_util::CACHE_LINE_LENGTH;
+
+    #[test]
+    fn test_system_clocks_agree() {
+        let epoch_ms = SystemEpochClock.time();
+        let epoch_ns = SystemEpochNanoClock.epoch_nano_time();
+
+        assert!((epoch_ns / 1_000_000 - epoch_ms).abs() < 1_000);
+
+        let start = SystemNanoClock.nano_time();
+        thread::sleep(Duration::from_millis(1));
+        assert!(SystemNanoClock.nano_time() - start >= 1_000_000);
+    }
+
+    #[test]
+    fn test_cached_clocks() {
+        let epoch_clock = CachedEpochClock::new();
+        let nano_clock = CachedNanoClock::new();
+
+        epoch_clock.update(1_000);
+        epoch_clock.advance(5);
+        nano_clock.update(7);
+        nano_clock.advance(3);
+
+        assert_eq!(epoch_clock.time(), 1_005);
+        assert_eq!(nano_clock.nano_time(), 10);
+        assert_eq!(std::mem::align_of::<CachedNanoClock>(), CACHE_LINE_LENGTH);
+    }
+
+    #[test]
+    fn test_offset_epoch_nano_clock_tracks_wall_time() {
+        let clock = OffsetEpochNanoClock::new();
+        let wall = SystemEpochNanoClock.epoch_nano_time();
+
+        assert!((clock.epoch_nano_time() - wall).abs() < 50_000_000);
+        assert!(!clock.needs_resample());
+    }
+
+    #[test]
+    fn test_test_clock_advances_on_sleep() {
+        let clock = Arc::new(TestClock::new(2_000_000));
+        let nano_clock: Arc<dyn NanoClock> = clock.clone();
+
+        nano_clock.sleep(Duration::from_millis(3));
+
+        assert_eq!(clock.nano_time(), 5_000_000);
+        assert_eq!(clock.time(), 5);
+        assert_eq!(clock.epoch_nano_time(), 5_000_000);
+    }
+}
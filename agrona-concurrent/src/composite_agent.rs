@@ -0,0 +1,160 @@
+use crate::agent::Agent;
+use agrona_core::error::{AgronaError, Result};
+
+/// Runs a fixed group of agents round-robin as one, summing their work counts.
+///
+/// If an agent fails, the next duty cycle resumes with the agent after it so one failing
+/// agent cannot starve the rest.
+pub struct CompositeAgent {
+    role_name: String,
+    agents: Vec<Box<dyn Agent>>,
+    agent_index: usize,
+}
+
+impl CompositeAgent {
+    pub fn new(agents: Vec<Box<dyn Agent>>) -> Result<Self> {
+        if agents.is_empty() {
+            return Err(AgronaError::IllegalArgument(
+                "requires at least one sub-agent".to_string(),
+            ));
+        }
+
+        let role_name = composite_role_name(&agents);
+
+        Ok(Self {
+            role_name,
+            agents,
+            agent_index: 0,
+        })
+    }
+
+    pub fn agents(&self) -> &[Box<dyn Agent>] {
+        &self.agents
+    }
+}
+
+impl Agent for CompositeAgent {
+    fn on_start(&mut self) -> Result<()> {
+        start_all(&mut self.agents)
+    }
+
+    fn do_work(&mut self) -> Result<usize> {
+        do_work_round_robin(&mut self.agents, &mut self.agent_index)
+    }
+
+    fn on_close(&mut self) -> Result<()> {
+        close_all(&mut self.agents)
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role_name
+    }
+}
+
+/// Starts every agent even if some fail, returning the first error.
+pub(crate) fn start_all(agents: &mut [Box<dyn Agent>]) -> Result<()> {
+    let mut first_error = None;
+    for agent in agents {
+        if let Err(error) = agent.on_start() {
+            first_error.get_or_insert(error);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Closes every agent even if some fail, returning the first error.
+pub(crate) fn close_all(agents: &mut [Box<dyn Agent>]) -> Result<()> {
+    let mut first_error = None;
+    for agent in agents {
+        if let Err(error) = agent.on_close() {
+            first_error.get_or_insert(error);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Runs each agent's duty cycle from `agent_index`, which is left after a failing agent so the
+/// next call resumes with the one after it.
+pub(crate) fn do_work_round_robin(agents: &mut [Box<dyn Agent>], agent_index: &mut usize) -> Result<usize> {
+    let mut work_count = 0;
+
+    while *agent_index < agents.len() {
+        let agent = &mut agents[*agent_index];
+        *agent_index += 1;
+        work_count += agent.do_work()?;
+    }
+
+    *agent_index = 0;
+    Ok(work_count)
+}
+
+fn composite_role_name(agents: &[Box<dyn Agent>]) -> String {
+    let names: Vec<&str> = agents.iter().map(|agent| agent.role_name()).collect();
+    format!("[{}]", names.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedAgent {
+        name: &'static str,
+        work: usize,
+        fail: bool,
+        calls: usize,
+    }
+
+    impl FixedAgent {
+        fn boxed(name: &'static str, work: usize, fail: bool) -> Box<dyn Agent> {
+            Box::new(Self { name, work, fail, calls: 0 })
+        }
+    }
+
+    impl Agent for FixedAgent {
+        fn do_work(&mut self) -> Result<usize> {
+            self.calls += 1;
+            if self.fail {
+                return Err(AgronaError::IllegalState(self.name.to_string()));
+            }
+            Ok(self.work)
+        }
+
+        fn role_name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[test]
+    fn test_sums_work_counts() {
+        let mut agent = CompositeAgent::new(vec![
+            FixedAgent::boxed("a", 1, false),
+            FixedAgent::boxed("b", 2, false),
+            FixedAgent::boxed("c", 3, false),
+        ])
+        .unwrap();
+
+        assert_eq!(agent.role_name(), "[a,b,c]");
+        agent.on_start().unwrap();
+        assert_eq!(agent.do_work().unwrap(), 6);
+        assert_eq!(agent.do_work().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_resumes_after_failing_agent() {
+        let mut agent = CompositeAgent::new(vec![
+            FixedAgent::boxed("a", 1, false),
+            FixedAgent::boxed("b", 0, true),
+            FixedAgent::boxed("c", 3, false),
+        ])
+        .unwrap();
+
+        assert!(agent.do_work().is_err());
+        assert_eq!(agent.do_work().unwrap(), 3);
+        assert!(agent.do_work().is_err());
+    }
+
+    #[test]
+    fn test_requires_agents() {
+        assert!(CompositeAgent::new(Vec::new()).is_err());
+    }
+}
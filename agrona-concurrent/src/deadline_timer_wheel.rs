@@ -0,0 +1,401 @@
+use agrona_core::bit_util::is_power_of_two;
+use agrona_core::error::{AgronaError, Result};
+
+pub const NULL_DEADLINE: i64 = i64::MAX;
+pub const INITIAL_TICK_ALLOCATION: usize = 16;
+
+/// Hashed timing wheel of deadlines, in whatever time unit the caller uses consistently.
+///
+/// Each tick (spoke) owns a fixed run of slots in one flat array; a timer id encodes the spoke in
+/// the upper 32 bits and the slot in the lower 32. Slots are reused, so once each spoke has grown
+/// to its working-set size, scheduling and expiry no longer allocate.
+pub struct DeadlineTimerWheel {
+    start_time: i64,
+    tick_resolution: i64,
+    current_tick: i64,
+    timer_count: usize,
+    tick_mask: usize,
+    resolution_bits_to_shift: u32,
+    tick_allocation: usize,
+    allocation_bits_to_shift: u32,
+    wheel: Vec<i64>,
+    /// Scratch list of the current spoke's expired slots, reused across polls.
+    expired_slots: Vec<usize>,
+}
+
+impl DeadlineTimerWheel {
+    pub fn new(start_time: i64, tick_resolution: i64, ticks_per_wheel: usize) -> Result<Self> {
+        Self::with_initial_tick_allocation(
+            start_time,
+            tick_resolution,
+            ticks_per_wheel,
+            INITIAL_TICK_ALLOCATION,
+        )
+    }
+
+    pub fn with_initial_tick_allocation(
+        start_time: i64,
+        tick_resolution: i64,
+        ticks_per_wheel: usize,
+        initial_tick_allocation: usize,
+    ) -> Result<Self> {
+        check_power_of_two("tick resolution", tick_resolution as u64)?;
+        check_power_of_two("ticks per wheel", ticks_per_wheel as u64)?;
+        check_power_of_two("initial tick allocation", initial_tick_allocation as u64)?;
+
+        Ok(Self {
+            start_time,
+            tick_resolution,
+            current_tick: 0,
+            timer_count: 0,
+            tick_mask: ticks_per_wheel - 1,
+            resolution_bits_to_shift: tick_resolution.trailing_zeros(),
+            tick_allocation: initial_tick_allocation,
+            allocation_bits_to_shift: initial_tick_allocation.trailing_zeros(),
+            wheel: vec![NULL_DEADLINE; ticks_per_wheel * initial_tick_allocation],
+            expired_slots: Vec::with_capacity(initial_tick_allocation),
+        })
+    }
+
+    #[inline]
+    pub fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    #[inline]
+    pub fn tick_resolution(&self) -> i64 {
+        self.tick_resolution
+    }
+
+    #[inline]
+    pub fn ticks_per_wheel(&self) -> usize {
+        self.tick_mask + 1
+    }
+
+    #[inline]
+    pub fn tick_allocation(&self) -> usize {
+        self.tick_allocation
+    }
+
+    #[inline]
+    pub fn timer_count(&self) -> usize {
+        self.timer_count
+    }
+
+    /// Time at which the current tick ends and polling moves on to the next spoke.
+    #[inline]
+    pub fn current_tick_time(&self) -> i64 {
+        ((self.current_tick + 1) << self.resolution_bits_to_shift) + self.start_time
+    }
+
+    pub fn set_current_tick_time(&mut self, now: i64) {
+        self.current_tick = self
+            .current_tick
+            .max((now - self.start_time) >> self.resolution_bits_to_shift);
+    }
+
+    pub fn clear(&mut self) {
+        self.wheel.fill(NULL_DEADLINE);
+        self.timer_count = 0;
+    }
+
+    pub fn schedule_timer(&mut self, deadline: i64) -> i64 {
+        let deadline_tick = self
+            .current_tick
+            .max((deadline - self.start_time) >> self.resolution_bits_to_shift);
+        let spoke_index = deadline_tick as usize & self.tick_mask;
+        let tick_start_index = spoke_index << self.allocation_bits_to_shift;
+
+        for slot_index in 0..self.tick_allocation {
+            let wheel_index = tick_start_index + slot_index;
+            if self.wheel[wheel_index] == NULL_DEADLINE {
+                self.wheel[wheel_index] = deadline;
+                self.timer_count += 1;
+                return timer_id_for_slot(spoke_index, slot_index);
+            }
+        }
+
+        self.increase_capacity(deadline, spoke_index)
+    }
+
+    pub fn cancel_timer(&mut self, timer_id: i64) -> bool {
+        match self.wheel_index(timer_id) {
+            Some(wheel_index) if self.wheel[wheel_index] != NULL_DEADLINE => {
+                self.wheel[wheel_index] = NULL_DEADLINE;
+                self.timer_count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn deadline(&self, timer_id: i64) -> i64 {
+        self.wheel_index(timer_id)
+            .map_or(NULL_DEADLINE, |wheel_index| self.wheel[wheel_index])
+    }
+
+    /// Expires up to `expiry_limit` timers of the current tick whose deadline is at or before `now`,
+    /// earliest deadline first, calling `handler(now, timer_id)` for each.
+    ///
+    /// The current tick's slots are scanned once per poll and the expired ones sorted by deadline,
+    /// so timers left over by the limit are delivered in order by the next poll. A handler returning
+    /// `false` keeps its timer scheduled and ends the poll. Once the current tick is exhausted and
+    /// `now` has passed its end, the wheel advances to the next tick.
+    pub fn poll<F>(&mut self, now: i64, mut handler: F, expiry_limit: usize) -> usize
+    where
+        F: FnMut(i64, i64) -> bool,
+    {
+        let mut timers_expired = 0;
+
+        if self.timer_count > 0 {
+            let spoke_index = self.current_tick as usize & self.tick_mask;
+            let tick_start_index = spoke_index << self.allocation_bits_to_shift;
+            let spoke = &self.wheel[tick_start_index..tick_start_index + self.tick_allocation];
+
+            let mut expired_slots = core::mem::take(&mut self.expired_slots);
+            expired_slots.clear();
+            expired_slots.extend((0..spoke.len()).filter(|&slot_index| spoke[slot_index] <= now));
+            expired_slots.sort_unstable_by_key(|&slot_index| (spoke[slot_index], slot_index));
+
+            let mut handler_rejected = false;
+            for &slot_index in expired_slots.iter().take(expiry_limit) {
+                let wheel_index = tick_start_index + slot_index;
+                let deadline = self.wheel[wheel_index];
+                self.wheel[wheel_index] = NULL_DEADLINE;
+                self.timer_count -= 1;
+
+                if !handler(now, timer_id_for_slot(spoke_index, slot_index)) {
+                    self.wheel[wheel_index] = deadline;
+                    self.timer_count += 1;
+                    handler_rejected = true;
+                    break;
+                }
+
+                timers_expired += 1;
+            }
+
+            self.expired_slots = expired_slots;
+            if handler_rejected {
+                return timers_expired;
+            }
+        }
+
+        if timers_expired < expiry_limit && now >= self.current_tick_time() {
+            self.current_tick += 1;
+        }
+
+        timers_expired
+    }
+
+    /// Visits every scheduled timer as `(deadline, timer_id)` in wheel order.
+    pub fn for_each<F>(&self, mut consumer: F)
+    where
+        F: FnMut(i64, i64),
+    {
+        let mut remaining = self.timer_count;
+
+        for (wheel_index, &deadline) in self.wheel.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            if deadline != NULL_DEADLINE {
+                let spoke_index = wheel_index >> self.allocation_bits_to_shift;
+                let slot_index = wheel_index & (self.tick_allocation - 1);
+                consumer(deadline, timer_id_for_slot(spoke_index, slot_index));
+                remaining -= 1;
+            }
+        }
+    }
+
+    fn wheel_index(&self, timer_id: i64) -> Option<usize> {
+        let spoke_index = (timer_id >> 32) as usize;
+        let slot_index = (timer_id & 0xFFFF_FFFF) as usize;
+
+        if timer_id < 0 || spoke_index > self.tick_mask || slot_index >= self.tick_allocation {
+            return None;
+        }
+
+        Some((spoke_index << self.allocation_bits_to_shift) + slot_index)
+    }
+
+    fn increase_capacity(&mut self, deadline: i64, spoke_index: usize) -> i64 {
+        let old_tick_allocation = self.tick_allocation;
+        let new_tick_allocation = old_tick_allocation * 2;
+        let mut new_wheel = vec![NULL_DEADLINE; self.ticks_per_wheel() * new_tick_allocation];
+
+        for spoke in 0..self.ticks_per_wheel() {
+            let old_start = spoke * old_tick_allocation;
+            let new_start = spoke * new_tick_allocation;
+            new_wheel[new_start..new_start + old_tick_allocation]
+                .copy_from_slice(&self.wheel[old_start..old_start + old_tick_allocation]);
+        }
+
+        new_wheel[spoke_index * new_tick_allocation + old_tick_allocation] = deadline;
+
+        self.wheel = new_wheel;
+        self.tick_allocation = new_tick_allocation;
+        self.allocation_bits_to_shift = new_tick_allocation.trailing_zeros();
+        self.timer_count += 1;
+
+        timer_id_for_slot(spoke_index, old_tick_allocation)
+    }
+}
+
+#[inline]
+fn timer_id_for_slot(spoke_index: usize, slot_index: usize) -> i64 {
+    ((spoke_index as i64) << 32) | slot_index as i64
+}
+
+fn check_power_of_two(name: &str, value: u64) -> Result<()> {
+    if !is_power_of_two(value) {
+        return Err(AgronaError::IllegalArgument(format!(
+            "{} must be a positive power of two: {}",
+            name, value
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: i64 = 1 << 20;
+
+    fn poll_until(wheel: &mut DeadlineTimerWheel, start: i64, end: i64) -> Vec<(i64, i64)> {
+        let mut fired = Vec::new();
+        let mut now = start;
+        while now <= end {
+            wheel.poll(
+                now,
+                |time, timer_id| {
+                    fired.push((time, timer_id));
+                    true
+                },
+                usize::MAX,
+            );
+            now += RESOLUTION / 4;
+        }
+        fired
+    }
+
+    #[test]
+    fn test_rejects_non_power_of_two_configuration() {
+        assert!(DeadlineTimerWheel::new(0, 1000, 1024).is_err());
+        assert!(DeadlineTimerWheel::new(0, RESOLUTION, 1000).is_err());
+        assert!(DeadlineTimerWheel::with_initial_tick_allocation(0, RESOLUTION, 1024, 3).is_err());
+    }
+
+    #[test]
+    fn test_expires_timer_after_deadline() {
+        let mut wheel = DeadlineTimerWheel::new(0, RESOLUTION, 1024).unwrap();
+        let deadline = 5 * RESOLUTION;
+        let timer_id = wheel.schedule_timer(deadline);
+
+        assert_eq!(wheel.deadline(timer_id), deadline);
+        let fired = poll_until(&mut wheel, 0, 7 * RESOLUTION);
+
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1, timer_id);
+        assert!(fired[0].0 >= deadline);
+        assert_eq!(wheel.timer_count(), 0);
+    }
+
+    #[test]
+    fn test_expires_in_deadline_order_and_resumes_after_limit() {
+        fn poll_ids(wheel: &mut DeadlineTimerWheel, now: i64, expiry_limit: usize) -> Vec<i64> {
+            let mut fired = Vec::new();
+            wheel.poll(
+                now,
+                |_, timer_id| {
+                    fired.push(timer_id);
+                    true
+                },
+                expiry_limit,
+            );
+            fired
+        }
+
+        let mut wheel = DeadlineTimerWheel::new(0, RESOLUTION, 8).unwrap();
+        let late = wheel.schedule_timer(RESOLUTION + 30);
+        let early = wheel.schedule_timer(RESOLUTION + 10);
+        let pending = wheel.schedule_timer(RESOLUTION + 500);
+        let middle = wheel.schedule_timer(RESOLUTION + 20);
+        let latest = wheel.schedule_timer(RESOLUTION + 40);
+
+        wheel.set_current_tick_time(RESOLUTION);
+        assert_eq!(poll_ids(&mut wheel, RESOLUTION + 100, 2), vec![early, middle]);
+        // Timers left over by the limit are still delivered earliest first
+        assert_eq!(poll_ids(&mut wheel, RESOLUTION + 100, usize::MAX), vec![late, latest]);
+        assert_eq!(poll_ids(&mut wheel, RESOLUTION + 500, usize::MAX), vec![pending]);
+        assert_eq!(wheel.timer_count(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_timer_does_not_fire() {
+        let mut wheel = DeadlineTimerWheel::new(0, RESOLUTION, 16).unwrap();
+        let timer_id = wheel.schedule_timer(3 * RESOLUTION);
+
+        assert!(wheel.cancel_timer(timer_id));
+        assert!(!wheel.cancel_timer(timer_id));
+        assert_eq!(wheel.deadline(timer_id), NULL_DEADLINE);
+        assert!(poll_until(&mut wheel, 0, 5 * RESOLUTION).is_empty());
+    }
+
+    #[test]
+    fn test_timer_beyond_one_rotation() {
+        let mut wheel = DeadlineTimerWheel::new(0, RESOLUTION, 4).unwrap();
+        let deadline = 9 * RESOLUTION;
+        wheel.schedule_timer(deadline);
+
+        let fired = poll_until(&mut wheel, 0, 12 * RESOLUTION);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].0 >= deadline);
+    }
+
+    #[test]
+    fn test_expiry_limit_and_handler_rejection() {
+        let mut wheel = DeadlineTimerWheel::new(0, RESOLUTION, 8).unwrap();
+        for i in 0..3 {
+            wheel.schedule_timer(i);
+        }
+
+        assert_eq!(wheel.poll(RESOLUTION / 2, |_, _| true, 2), 2);
+        assert_eq!(wheel.poll(RESOLUTION / 2, |_, _| false, usize::MAX), 0);
+        assert_eq!(wheel.timer_count(), 1);
+        assert_eq!(wheel.poll(RESOLUTION / 2, |_, _| true, usize::MAX), 1);
+    }
+
+    #[test]
+    fn test_capacity_grows_and_keeps_timer_ids() {
+        let mut wheel =
+            DeadlineTimerWheel::with_initial_tick_allocation(0, RESOLUTION, 8, 2).unwrap();
+        let ids: Vec<i64> = (0..5)
+            .map(|i| wheel.schedule_timer(RESOLUTION + i))
+            .collect();
+
+        assert_eq!(wheel.tick_allocation(), 8);
+        for (i, &timer_id) in ids.iter().enumerate() {
+            assert_eq!(wheel.deadline(timer_id), RESOLUTION + i as i64);
+        }
+
+        let mut seen = 0;
+        wheel.for_each(|_, _| seen += 1);
+        assert_eq!(seen, 5);
+    }
+
+    #[test]
+    fn test_no_growth_after_warm_up() {
+        let mut wheel =
+            DeadlineTimerWheel::with_initial_tick_allocation(0, RESOLUTION, 16, 4).unwrap();
+        let mut now = 0;
+
+        for _ in 0..10_000 {
+            wheel.schedule_timer(now + 2 * RESOLUTION);
+            wheel.poll(now, |_, _| true, usize::MAX);
+            now += RESOLUTION / 2;
+        }
+
+        assert_eq!(wheel.tick_allocation(), 4);
+    }
+}
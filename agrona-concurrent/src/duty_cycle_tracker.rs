@@ -0,0 +1,150 @@
+use crate::clock::NanoClock;
+use crate::latency_histogram::LatencyHistogram;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Measures the time between successive duty cycles of an agent to detect stalls such as page
+/// faults or preemption.
+///
+/// Call [`DutyCycleTracker::update`] when the agent starts and
+/// [`DutyCycleTracker::measure_and_update`] once per duty cycle.
+pub struct DutyCycleTracker {
+    clock: Arc<dyn NanoClock>,
+    cycle_time_threshold_ns: i64,
+    time_of_last_update_ns: i64,
+    max_cycle_time_ns: i64,
+    threshold_exceeded_count: u64,
+    histogram: Option<LatencyHistogram>,
+}
+
+impl DutyCycleTracker {
+    pub fn new(clock: Arc<dyn NanoClock>, cycle_time_threshold: Duration) -> Self {
+        let now_ns = clock.nano_time();
+        Self {
+            clock,
+            cycle_time_threshold_ns: i64::try_from(cycle_time_threshold.as_nanos()).unwrap_or(i64::MAX),
+            time_of_last_update_ns: now_ns,
+            max_cycle_time_ns: 0,
+            threshold_exceeded_count: 0,
+            histogram: None,
+        }
+    }
+
+    /// Also records every cycle time into `histogram`.
+    pub fn with_histogram(
+        clock: Arc<dyn NanoClock>,
+        cycle_time_threshold: Duration,
+        histogram: LatencyHistogram,
+    ) -> Self {
+        let mut tracker = Self::new(clock, cycle_time_threshold);
+        tracker.histogram = Some(histogram);
+        tracker
+    }
+
+    /// Resets the start of the current cycle without measuring the one that just ended.
+    pub fn update(&mut self) {
+        self.time_of_last_update_ns = self.clock.nano_time();
+    }
+
+    /// Records the time since the previous update as a cycle and returns it.
+    pub fn measure_and_update(&mut self) -> Duration {
+        let now_ns = self.clock.nano_time();
+        let cycle_time_ns = now_ns.saturating_sub(self.time_of_last_update_ns).max(0);
+        self.time_of_last_update_ns = now_ns;
+
+        if cycle_time_ns > self.max_cycle_time_ns {
+            self.max_cycle_time_ns = cycle_time_ns;
+        }
+
+        if cycle_time_ns > self.cycle_time_threshold_ns {
+            self.threshold_exceeded_count += 1;
+        }
+
+        if let Some(histogram) = self.histogram.as_mut() {
+            histogram.record(cycle_time_ns as u64);
+        }
+
+        Duration::from_nanos(cycle_time_ns as u64)
+    }
+
+    #[inline]
+    pub fn cycle_time_threshold(&self) -> Duration {
+        Duration::from_nanos(self.cycle_time_threshold_ns as u64)
+    }
+
+    #[inline]
+    pub fn max_cycle_time(&self) -> Duration {
+        Duration::from_nanos(self.max_cycle_time_ns as u64)
+    }
+
+    #[inline]
+    pub fn cycle_time_threshold_exceeded_count(&self) -> u64 {
+        self.threshold_exceeded_count
+    }
+
+    pub fn histogram(&self) -> Option<&LatencyHistogram> {
+        self.histogram.as_ref()
+    }
+
+    /// Clears the statistics gathered so far and starts a new cycle.
+    pub fn reset(&mut self) {
+        self.max_cycle_time_ns = 0;
+        self.threshold_exceeded_count = 0;
+        if let Some(histogram) = self.histogram.as_mut() {
+            histogram.reset();
+        }
+        self.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+
+    #[test]
+    fn test_tracks_max_and_stalls() {
+        let clock = Arc::new(TestClock::new(1_000));
+        let mut tracker = DutyCycleTracker::with_histogram(
+            clock.clone(),
+            Duration::from_micros(10),
+            LatencyHistogram::default(),
+        );
+
+        for cycle_us in [1, 2, 50, 3, 10, 11] {
+            clock.advance(Duration::from_micros(cycle_us));
+            assert_eq!(tracker.measure_and_update(), Duration::from_micros(cycle_us));
+        }
+
+        assert_eq!(tracker.max_cycle_time(), Duration::from_micros(50));
+        assert_eq!(tracker.cycle_time_threshold_exceeded_count(), 2);
+
+        let histogram = tracker.histogram().unwrap();
+        assert_eq!(histogram.count(), 6);
+        assert_eq!(histogram.max(), 50_000);
+        assert_eq!(histogram.min(), 1_000);
+    }
+
+    #[test]
+    fn test_update_skips_measurement() {
+        let clock = Arc::new(TestClock::new(0));
+        let mut tracker = DutyCycleTracker::new(clock.clone(), Duration::from_millis(1));
+
+        clock.advance(Duration::from_secs(1));
+        tracker.update();
+        clock.advance(Duration::from_micros(5));
+        tracker.measure_and_update();
+
+        assert_eq!(tracker.max_cycle_time(), Duration::from_micros(5));
+        assert_eq!(tracker.cycle_time_threshold_exceeded_count(), 0);
+        assert!(tracker.histogram().is_none());
+
+        clock.advance(Duration::from_millis(2));
+        tracker.measure_and_update();
+        assert_eq!(tracker.cycle_time_threshold_exceeded_count(), 1);
+
+        tracker.reset();
+        assert_eq!(tracker.max_cycle_time(), Duration::ZERO);
+        assert_eq!(tracker.cycle_time_threshold_exceeded_count(), 0);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::agent::Agent;
+use crate::composite_agent::{close_all, do_work_round_robin, start_all};
+use agrona_core::error::{AgronaError, Result};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicCompositeStatus {
+    Init,
+    Active,
+    Closed,
+}
+
+const STATUS_INIT: u8 = 0;
+const STATUS_ACTIVE: u8 = 1;
+const STATUS_CLOSED: u8 = 2;
+
+/// Thread-safe handle for adding and removing agents of a running [`DynamicCompositeAgent`].
+///
+/// Requests are handed over through single-slot mailboxes that the agent thread drains at the
+/// start of its next duty cycle, so `on_start`/`on_close` always run on the agent thread.
+pub struct DynamicCompositeControl {
+    status: AtomicU8,
+    has_pending: AtomicBool,
+    add_agent: Mutex<Option<Box<dyn Agent>>>,
+    remove_agent: Mutex<Option<String>>,
+}
+
+impl DynamicCompositeControl {
+    fn new() -> Self {
+        Self {
+            status: AtomicU8::new(STATUS_INIT),
+            has_pending: AtomicBool::new(false),
+            add_agent: Mutex::new(None),
+            remove_agent: Mutex::new(None),
+        }
+    }
+
+    pub fn status(&self) -> DynamicCompositeStatus {
+        match self.status.load(Ordering::Acquire) {
+            STATUS_INIT => DynamicCompositeStatus::Init,
+            STATUS_ACTIVE => DynamicCompositeStatus::Active,
+            _ => DynamicCompositeStatus::Closed,
+        }
+    }
+
+    fn ensure_active(&self, operation: &str) -> Result<()> {
+        if self.status() != DynamicCompositeStatus::Active {
+            return Err(AgronaError::IllegalState(format!(
+                "{} called when not active",
+                operation
+            )));
+        }
+        Ok(())
+    }
+
+    /// Requests `agent` be added, returning `Ok(false)` while a previous add is still pending.
+    pub fn try_add(&self, agent: Box<dyn Agent>) -> Result<bool> {
+        self.ensure_active("add")?;
+
+        let mut slot = self.add_agent.lock().unwrap();
+        if slot.is_some() {
+            return Ok(false);
+        }
+        *slot = Some(agent);
+        self.has_pending.store(true, Ordering::Release);
+
+        Ok(true)
+    }
+
+    pub fn has_add_agent_completed(&self) -> Result<bool> {
+        self.ensure_active("has_add_agent_completed")?;
+        Ok(self.add_agent.lock().unwrap().is_none())
+    }
+
+    /// Requests the agent with `role_name` be removed, returning `Ok(false)` while a previous
+    /// removal is still pending.
+    pub fn try_remove(&self, role_name: &str) -> Result<bool> {
+        self.ensure_active("remove")?;
+
+        let mut slot = self.remove_agent.lock().unwrap();
+        if slot.is_some() {
+            return Ok(false);
+        }
+        *slot = Some(role_name.to_string());
+        self.has_pending.store(true, Ordering::Release);
+
+        Ok(true)
+    }
+
+    pub fn has_remove_agent_completed(&self) -> Result<bool> {
+        self.ensure_active("has_remove_agent_completed")?;
+        Ok(self.remove_agent.lock().unwrap().is_none())
+    }
+}
+
+/// A [`crate::CompositeAgent`] whose membership can change while it runs.
+pub struct DynamicCompositeAgent {
+    role_name: String,
+    agents: Vec<Box<dyn Agent>>,
+    agent_index: usize,
+    control: Arc<DynamicCompositeControl>,
+}
+
+impl DynamicCompositeAgent {
+    pub fn new(role_name: &str) -> Self {
+        Self::with_agents(role_name, Vec::new())
+    }
+
+    pub fn with_agents(role_name: &str, agents: Vec<Box<dyn Agent>>) -> Self {
+        Self {
+            role_name: role_name.to_string(),
+            agents,
+            agent_index: 0,
+            control: Arc::new(DynamicCompositeControl::new()),
+        }
+    }
+
+    pub fn control(&self) -> Arc<DynamicCompositeControl> {
+        Arc::clone(&self.control)
+    }
+
+    pub fn status(&self) -> DynamicCompositeStatus {
+        self.control.status()
+    }
+
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Applies both pending slots, since the pending flag has already been cleared, and returns
+    /// the first error.
+    fn apply_pending(&mut self) -> Result<()> {
+        let mut remove_result = Ok(());
+        let remove = self.control.remove_agent.lock().unwrap().take();
+        if let Some(role_name) = remove {
+            if let Some(index) = self.agents.iter().position(|agent| agent.role_name() == role_name) {
+                let mut agent = self.agents.remove(index);
+                if self.agent_index > index {
+                    self.agent_index -= 1;
+                }
+                remove_result = agent.on_close();
+            }
+        }
+
+        let mut add_result = Ok(());
+        let add = self.control.add_agent.lock().unwrap().take();
+        if let Some(mut agent) = add {
+            match agent.on_start() {
+                Ok(()) => self.agents.push(agent),
+                Err(error) => {
+                    // An agent that fails to start is closed rather than dropped, and the start
+                    // error takes precedence over any close error
+                    let _ = agent.on_close();
+                    add_result = Err(error);
+                }
+            }
+        }
+
+        remove_result.and(add_result)
+    }
+}
+
+impl Agent for DynamicCompositeAgent {
+    fn on_start(&mut self) -> Result<()> {
+        let result = start_all(&mut self.agents);
+        self.control.status.store(STATUS_ACTIVE, Ordering::Release);
+
+        result
+    }
+
+    fn do_work(&mut self) -> Result<usize> {
+        if self.control.has_pending.swap(false, Ordering::AcqRel) {
+            self.apply_pending()?;
+        }
+
+        do_work_round_robin(&mut self.agents, &mut self.agent_index)
+    }
+
+    fn on_close(&mut self) -> Result<()> {
+        self.control.status.store(STATUS_CLOSED, Ordering::Release);
+        self.control.add_agent.lock().unwrap().take();
+        self.control.remove_agent.lock().unwrap().take();
+
+        let result = close_all(&mut self.agents);
+        self.agents.clear();
+
+        result
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_runner::AgentRunner;
+    use crate::idle_strategy::BusySpinIdleStrategy;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[derive(Default)]
+    struct Lifecycle {
+        starts: AtomicUsize,
+        work: AtomicUsize,
+        closes: AtomicUsize,
+    }
+
+    struct TrackedAgent {
+        name: &'static str,
+        lifecycle: Arc<Lifecycle>,
+    }
+
+    impl Agent for TrackedAgent {
+        fn on_start(&mut self) -> Result<()> {
+            self.lifecycle.starts.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn do_work(&mut self) -> Result<usize> {
+            self.lifecycle.work.fetch_add(1, Ordering::Relaxed);
+            Ok(1)
+        }
+
+        fn on_close(&mut self) -> Result<()> {
+            self.lifecycle.closes.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn role_name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn tracked(name: &'static str) -> (Box<dyn Agent>, Arc<Lifecycle>) {
+        let lifecycle = Arc::new(Lifecycle::default());
+        (Box::new(TrackedAgent { name, lifecycle: Arc::clone(&lifecycle) }), lifecycle)
+    }
+
+    #[test]
+    fn test_add_and_remove_between_duty_cycles() {
+        let (first, first_lifecycle) = tracked("first");
+        let (second, second_lifecycle) = tracked("second");
+        let mut agent = DynamicCompositeAgent::with_agents("dynamic", vec![first]);
+        let control = agent.control();
+
+        assert!(control.try_add(tracked("early").0).is_err());
+
+        agent.on_start().unwrap();
+        assert_eq!(agent.do_work().unwrap(), 1);
+
+        assert!(control.try_add(second).unwrap());
+        assert!(!control.try_add(tracked("third").0).unwrap());
+        assert!(!control.has_add_agent_completed().unwrap());
+
+        assert_eq!(agent.do_work().unwrap(), 2);
+        assert!(control.has_add_agent_completed().unwrap());
+        assert_eq!(second_lifecycle.starts.load(Ordering::Relaxed), 1);
+
+        assert!(control.try_remove("first").unwrap());
+        assert_eq!(agent.do_work().unwrap(), 1);
+        assert!(control.has_remove_agent_completed().unwrap());
+        assert_eq!(first_lifecycle.closes.load(Ordering::Relaxed), 1);
+
+        agent.on_close().unwrap();
+        assert_eq!(agent.status(), DynamicCompositeStatus::Closed);
+        assert_eq!(second_lifecycle.closes.load(Ordering::Relaxed), 1);
+        assert!(agent.is_empty());
+    }
+
+    #[test]
+    fn test_closes_agent_that_fails_to_start() {
+        struct FailingStart(Arc<Lifecycle>);
+
+        impl Agent for FailingStart {
+            fn on_start(&mut self) -> Result<()> {
+                Err(AgronaError::IllegalState("start failed".to_string()))
+            }
+
+            fn do_work(&mut self) -> Result<usize> {
+                Ok(0)
+            }
+
+            fn on_close(&mut self) -> Result<()> {
+                self.0.closes.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+
+            fn role_name(&self) -> &str {
+                "failing"
+            }
+        }
+
+        let mut agent = DynamicCompositeAgent::new("dynamic");
+        let control = agent.control();
+        agent.on_start().unwrap();
+
+        let lifecycle = Arc::new(Lifecycle::default());
+        assert!(control.try_add(Box::new(FailingStart(Arc::clone(&lifecycle)))).unwrap());
+        assert!(matches!(agent.do_work(), Err(AgronaError::IllegalState(_))));
+
+        assert_eq!(lifecycle.closes.load(Ordering::Relaxed), 1);
+        assert!(agent.is_empty());
+        assert!(control.has_add_agent_completed().unwrap());
+    }
+
+    #[test]
+    fn test_applies_add_when_removed_agent_fails_to_close() {
+        struct FailingClose;
+
+        impl Agent for FailingClose {
+            fn do_work(&mut self) -> Result<usize> {
+                Ok(0)
+            }
+
+            fn on_close(&mut self) -> Result<()> {
+                Err(AgronaError::IllegalState("close failed".to_string()))
+            }
+
+            fn role_name(&self) -> &str {
+                "failing"
+            }
+        }
+
+        let mut agent = DynamicCompositeAgent::with_agents("dynamic", vec![Box::new(FailingClose)]);
+        let control = agent.control();
+        agent.on_start().unwrap();
+
+        let (second, second_lifecycle) = tracked("second");
+        assert!(control.try_remove("failing").unwrap());
+        assert!(control.try_add(second).unwrap());
+        assert!(matches!(agent.do_work(), Err(AgronaError::IllegalState(_))));
+
+        assert_eq!(second_lifecycle.starts.load(Ordering::Relaxed), 1);
+        assert_eq!(agent.len(), 1);
+        assert!(control.has_remove_agent_completed().unwrap());
+        assert!(control.try_add(tracked("third").0).unwrap());
+    }
+
+    #[test]
+    fn test_add_while_running_on_runner_thread() {
+        let agent = DynamicCompositeAgent::new("dynamic-runner");
+        let control = agent.control();
+        let mut runner = AgentRunner::start(agent, BusySpinIdleStrategy::new(), Arc::new(|_: &AgronaError| {}))
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while control.status() != DynamicCompositeStatus::Active {
+            assert!(Instant::now() < deadline);
+            thread::yield_now();
+        }
+
+        let (added, lifecycle) = tracked("added");
+        assert!(control.try_add(added).unwrap());
+        while lifecycle.work.load(Ordering::Relaxed) < 100 {
+            assert!(Instant::now() < deadline);
+            thread::yield_now();
+        }
+        assert!(control.has_add_agent_completed().unwrap());
+
+        runner.close().unwrap();
+        assert_eq!(lifecycle.starts.load(Ordering::Relaxed), 1);
+        assert_eq!(lifecycle.closes.load(Ordering::Relaxed), 1);
+        assert_eq!(control.status(), DynamicCompositeStatus::Closed);
+    }
+}
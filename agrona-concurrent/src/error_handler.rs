@@ -0,0 +1,80 @@
+use crate::status::AtomicCounter;
+use agrona_core::error::AgronaError;
+use std::sync::Arc;
+
+pub trait ErrorHandler: Send + Sync {
+    fn on_error(&self, error: &AgronaError);
+}
+
+impl<F> ErrorHandler for F
+where
+    F: Fn(&AgronaError) + Send + Sync,
+{
+    fn on_error(&self, error: &AgronaError) {
+        self(error)
+    }
+}
+
+pub struct LoggingErrorHandler;
+
+impl ErrorHandler for LoggingErrorHandler {
+    fn on_error(&self, error: &AgronaError) {
+        eprintln!("{}", error);
+    }
+}
+
+/// Delegates to another [`ErrorHandler`] and counts every error in a shared counter.
+pub struct CountedErrorHandler {
+    error_handler: Arc<dyn ErrorHandler>,
+    error_counter: AtomicCounter,
+}
+
+impl CountedErrorHandler {
+    pub fn new(error_handler: Arc<dyn ErrorHandler>, error_counter: AtomicCounter) -> Self {
+        Self {
+            error_handler,
+            error_counter,
+        }
+    }
+
+    pub fn error_counter(&self) -> &AtomicCounter {
+        &self.error_counter
+    }
+}
+
+impl ErrorHandler for CountedErrorHandler {
+    fn on_error(&self, error: &AgronaError) {
+        self.error_handler.on_error(error);
+        self.error_counter.increment();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::{CountersManager, COUNTER_LENGTH, METADATA_LENGTH};
+    use crate::AtomicBuffer;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_counted_error_handler() {
+        let mut manager = CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH).unwrap(),
+        )
+        .unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let delegate_seen = Arc::clone(&seen);
+
+        let handler = CountedErrorHandler::new(
+            Arc::new(move |error: &AgronaError| delegate_seen.lock().unwrap().push(error.kind())),
+            unsafe { manager.new_counter("errors", 0, &[]) }.unwrap(),
+        );
+
+        handler.on_error(&AgronaError::IllegalState("first".to_string()));
+        handler.on_error(&AgronaError::Io("second".to_string()));
+
+        assert_eq!(*seen.lock().unwrap(), vec!["IllegalState", "Io"]);
+        assert_eq!(manager.counter_value(handler.error_counter().id()).unwrap(), 2);
+    }
+}
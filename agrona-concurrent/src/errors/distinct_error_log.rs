@@ -0,0 +1,156 @@
+use crate::atomic_buffer::AtomicBuffer;
+use crate::clock::EpochClock;
+use crate::error_handler::ErrorHandler;
+use agrona_core::bit_util::{align, SIZE_OF_I32, SIZE_OF_I64};
+use agrona_core::buffer::{DirectBuffer, MutableBuffer};
+use agrona_core::error::{AgronaError, Result};
+use std::sync::{Arc, Mutex};
+
+pub const LENGTH_OFFSET: usize = 0;
+pub const OBSERVATION_COUNT_OFFSET: usize = LENGTH_OFFSET + SIZE_OF_I32;
+pub const LAST_OBSERVATION_TIMESTAMP_OFFSET: usize = OBSERVATION_COUNT_OFFSET + SIZE_OF_I32;
+pub const FIRST_OBSERVATION_TIMESTAMP_OFFSET: usize = LAST_OBSERVATION_TIMESTAMP_OFFSET + SIZE_OF_I64;
+pub const ENCODED_ERROR_OFFSET: usize = FIRST_OBSERVATION_TIMESTAMP_OFFSET + SIZE_OF_I64;
+pub const RECORD_ALIGNMENT: usize = SIZE_OF_I64;
+
+struct DistinctObservation {
+    encoded_error: String,
+    offset: usize,
+}
+
+struct ErrorLogState {
+    buffer: AtomicBuffer,
+    next_offset: usize,
+    observations: Vec<DistinctObservation>,
+}
+
+/// Records errors into a buffer once per distinct kind and message, counting repeat observations.
+///
+/// Each record is `length | observation count | last timestamp | first timestamp | encoded error`,
+/// aligned to 8 bytes. The length is written last so an [`crate::ErrorLogReader`] in another
+/// process only sees complete records. The buffer must be zeroed before first use.
+pub struct DistinctErrorLog {
+    state: Mutex<ErrorLogState>,
+    clock: Arc<dyn EpochClock>,
+}
+
+impl DistinctErrorLog {
+    pub fn new(buffer: AtomicBuffer, clock: Arc<dyn EpochClock>) -> Self {
+        Self {
+            state: Mutex::new(ErrorLogState {
+                buffer,
+                next_offset: 0,
+                observations: Vec::new(),
+            }),
+            clock,
+        }
+    }
+
+    pub fn encode(error: &AgronaError) -> String {
+        format!("{}: {}", error.kind(), error)
+    }
+
+    /// Records an observation of `error`, returning `Ok(false)` if a new distinct error does not fit.
+    pub fn record(&self, error: &AgronaError) -> Result<bool> {
+        let timestamp = self.clock.time();
+        let encoded_error = Self::encode(error);
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(offset) = state
+            .observations
+            .iter()
+            .find(|observation| observation.encoded_error == encoded_error)
+            .map(|observation| observation.offset)
+        {
+            let buffer = &mut state.buffer;
+            let count = buffer.get_volatile_u32(offset + OBSERVATION_COUNT_OFFSET)?;
+            buffer.put_ordered_u32(offset + OBSERVATION_COUNT_OFFSET, count.saturating_add(1))?;
+            buffer.put_ordered_u64(offset + LAST_OBSERVATION_TIMESTAMP_OFFSET, timestamp as u64)?;
+            return Ok(true);
+        }
+
+        let offset = state.next_offset;
+        let length = ENCODED_ERROR_OFFSET + encoded_error.len();
+        if offset + length > state.buffer.capacity() {
+            return Ok(false);
+        }
+
+        let buffer = &mut state.buffer;
+        buffer.put_bytes(offset + ENCODED_ERROR_OFFSET, encoded_error.as_bytes())?;
+        buffer.put_u64(offset + FIRST_OBSERVATION_TIMESTAMP_OFFSET, timestamp as u64)?;
+        buffer.put_u64(offset + LAST_OBSERVATION_TIMESTAMP_OFFSET, timestamp as u64)?;
+        buffer.put_u32(offset + OBSERVATION_COUNT_OFFSET, 1)?;
+        buffer.put_ordered_u32(offset + LENGTH_OFFSET, length as u32)?;
+
+        state.next_offset = align(offset + length, RECORD_ALIGNMENT);
+        state.observations.push(DistinctObservation {
+            encoded_error,
+            offset,
+        });
+
+        Ok(true)
+    }
+}
+
+impl ErrorHandler for DistinctErrorLog {
+    fn on_error(&self, error: &AgronaError) {
+        if !matches!(self.record(error), Ok(true)) {
+            eprintln!("error log full, dropping: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use std::time::Duration;
+
+    fn zeroed_buffer(capacity: usize) -> AtomicBuffer {
+        let mut buffer = AtomicBuffer::new(capacity).unwrap();
+        buffer.set_memory(0, capacity, 0).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_deduplicates_repeated_errors() {
+        let clock = Arc::new(TestClock::new(7_000_000));
+        let log = DistinctErrorLog::new(zeroed_buffer(1024), clock.clone());
+        let error = AgronaError::IllegalState("duty cycle failed".to_string());
+
+        assert!(log.record(&error).unwrap());
+        clock.advance(Duration::from_millis(5));
+        assert!(log.record(&error).unwrap());
+        assert!(log.record(&AgronaError::IllegalArgument("duty cycle failed".to_string())).unwrap());
+
+        let state = log.state.lock().unwrap();
+        assert_eq!(state.observations.len(), 2);
+        let buffer = &state.buffer;
+        assert_eq!(buffer.get_u32(OBSERVATION_COUNT_OFFSET).unwrap(), 2);
+        assert_eq!(buffer.get_u64(FIRST_OBSERVATION_TIMESTAMP_OFFSET).unwrap(), 7);
+        assert_eq!(buffer.get_u64(LAST_OBSERVATION_TIMESTAMP_OFFSET).unwrap(), 12);
+        assert_eq!(state.observations[1].offset % RECORD_ALIGNMENT, 0);
+    }
+
+    #[test]
+    fn test_observation_count_saturates() {
+        let log = DistinctErrorLog::new(zeroed_buffer(1024), Arc::new(TestClock::new(0)));
+        let error = AgronaError::IllegalState("hot loop".to_string());
+
+        assert!(log.record(&error).unwrap());
+        log.state.lock().unwrap().buffer.put_u32(OBSERVATION_COUNT_OFFSET, u32::MAX).unwrap();
+        assert!(log.record(&error).unwrap());
+
+        let state = log.state.lock().unwrap();
+        assert_eq!(state.buffer.get_u32(OBSERVATION_COUNT_OFFSET).unwrap(), u32::MAX);
+    }
+
+    #[test]
+    fn test_rejects_error_when_full() {
+        let log = DistinctErrorLog::new(zeroed_buffer(64), Arc::new(TestClock::new(0)));
+
+        assert!(log.record(&AgronaError::Io("short".to_string())).unwrap());
+        assert!(!log.record(&AgronaError::Io("x".repeat(64))).unwrap());
+        assert!(log.record(&AgronaError::Io("short".to_string())).unwrap());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::atomic_buffer::AtomicBuffer;
+use crate::errors::distinct_error_log::*;
+use agrona_core::bit_util::align;
+use agrona_core::buffer::DirectBuffer;
+use agrona_core::error::Result;
+
+/// Reads the records of a [`crate::DistinctErrorLog`] buffer, possibly from another process.
+pub struct ErrorLogReader;
+
+impl ErrorLogReader {
+    pub fn has_errors(buffer: &AtomicBuffer) -> Result<bool> {
+        Ok(buffer.capacity() >= ENCODED_ERROR_OFFSET && buffer.get_volatile_u32(LENGTH_OFFSET)? > 0)
+    }
+
+    /// Calls `consumer(observation_count, first_timestamp, last_timestamp, encoded_error)` for each
+    /// distinct error, returning how many were read.
+    pub fn read<F>(buffer: &AtomicBuffer, consumer: F) -> Result<usize>
+    where
+        F: FnMut(i32, i64, i64, &str),
+    {
+        Self::read_since(buffer, i64::MIN, consumer)
+    }
+
+    /// Like [`ErrorLogReader::read`], limited to errors last observed at or after `since_timestamp`.
+    pub fn read_since<F>(buffer: &AtomicBuffer, since_timestamp: i64, mut consumer: F) -> Result<usize>
+    where
+        F: FnMut(i32, i64, i64, &str),
+    {
+        let capacity = buffer.capacity();
+        let mut entries = 0;
+        let mut offset = 0;
+
+        while offset + ENCODED_ERROR_OFFSET <= capacity {
+            let length = buffer.get_volatile_u32(offset + LENGTH_OFFSET)? as usize;
+            if length == 0 {
+                break;
+            }
+
+            // A corrupt or partly written length ends the log rather than being trusted
+            if length < ENCODED_ERROR_OFFSET || length > capacity - offset {
+                break;
+            }
+
+            let last_timestamp = buffer.get_volatile_u64(offset + LAST_OBSERVATION_TIMESTAMP_OFFSET)? as i64;
+            if last_timestamp >= since_timestamp {
+                let observation_count = buffer.get_volatile_u32(offset + OBSERVATION_COUNT_OFFSET)? as i32;
+                let first_timestamp = buffer.get_u64(offset + FIRST_OBSERVATION_TIMESTAMP_OFFSET)? as i64;
+                let encoded_error = buffer.get_string_utf8_with_length(
+                    offset + ENCODED_ERROR_OFFSET,
+                    length - ENCODED_ERROR_OFFSET,
+                )?;
+
+                entries += 1;
+                consumer(observation_count, first_timestamp, last_timestamp, &encoded_error);
+            }
+
+            offset += align(length, RECORD_ALIGNMENT);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use agrona_core::buffer::MutableBuffer;
+    use agrona_core::error::AgronaError;
+    use agrona_core::MappedFile;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reads_log_from_separate_mapping() {
+        let path = std::env::temp_dir().join(format!("agrona-error-log-{}", std::process::id()));
+        let capacity = 4096;
+        let mut writer_file = MappedFile::create(&path, capacity).unwrap();
+        let clock = Arc::new(TestClock::new(1_000_000_000));
+        let log = DistinctErrorLog::new(AtomicBuffer::wrap(writer_file.as_mut_ptr(), capacity), clock.clone());
+
+        let mut reader_file = MappedFile::open(&path).unwrap();
+        let reader_buffer = AtomicBuffer::wrap(reader_file.as_mut_ptr(), capacity);
+        assert!(!ErrorLogReader::has_errors(&reader_buffer).unwrap());
+
+        for _ in 0..3 {
+            log.record(&AgronaError::IllegalState("stalled".to_string())).unwrap();
+            clock.advance(Duration::from_millis(10));
+        }
+        log.record(&AgronaError::Io("disk full".to_string())).unwrap();
+
+        let mut records = Vec::new();
+        let count = ErrorLogReader::read(&reader_buffer, |observations, first, last, error| {
+            records.push((observations, first, last, error.to_string()));
+        })
+        .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            records[0],
+            (3, 1_000, 1_020, "IllegalState: Illegal state: stalled".to_string())
+        );
+        assert_eq!(records[1].3, "Io: I/O error: disk full");
+
+        let recent = ErrorLogReader::read_since(&reader_buffer, 1_025, |_, _, _, _| {}).unwrap();
+        assert_eq!(recent, 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_stops_at_corrupt_record() {
+        let capacity = 1024;
+        let mut buffer = AtomicBuffer::new(capacity).unwrap();
+        buffer.set_memory(0, capacity, 0).unwrap();
+        let clock = Arc::new(TestClock::new(1_000_000_000));
+        let log = DistinctErrorLog::new(AtomicBuffer::wrap(buffer.as_mut_ptr(), capacity), clock);
+        log.record(&AgronaError::IllegalState("first".to_string())).unwrap();
+        log.record(&AgronaError::Io("second".to_string())).unwrap();
+
+        let second_offset = align(buffer.get_volatile_u32(LENGTH_OFFSET).unwrap() as usize, RECORD_ALIGNMENT);
+
+        // Shorter than a record header, then running past the end of the buffer
+        for corrupt_length in [ENCODED_ERROR_OFFSET as u32 - 1, capacity as u32] {
+            buffer.put_ordered_u32(second_offset + LENGTH_OFFSET, corrupt_length).unwrap();
+
+            let mut errors = Vec::new();
+            let count = ErrorLogReader::read(&buffer, |_, _, _, error| errors.push(error.to_string())).unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(errors, vec!["IllegalState: Illegal state: first".to_string()]);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod distinct_error_log;
+pub mod error_log_reader;
+
+pub use distinct_error_log::*;
+pub use error_log_reader::*;
@@ -1,10 +1,68 @@
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::thread;
+use crate::clock::{NanoClock, SystemNanoClock};
+use crate::status::{AtomicStatusIndicator, StatusIndicator};
+use agrona_core::error::{AgronaError, Result};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, Thread};
 use std::time::Duration;
 
+/// What an idle strategy did on its most recent `idle(0)` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdlePhase {
+    None,
+    Spin,
+    Yield,
+    Park,
+}
+
 pub trait IdleStrategy {
     fn idle(&mut self, work_count: usize);
     fn reset(&mut self);
+
+    /// Phase entered by the most recent `idle` call that had no work, used for instrumentation.
+    fn idle_phase(&self) -> IdlePhase {
+        IdlePhase::None
+    }
+}
+
+impl<S: IdleStrategy + ?Sized> IdleStrategy for Box<S> {
+    #[inline]
+    fn idle(&mut self, work_count: usize) {
+        (**self).idle(work_count)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    #[inline]
+    fn idle_phase(&self) -> IdlePhase {
+        (**self).idle_phase()
+    }
+}
+
+pub struct NoOpIdleStrategy;
+
+impl NoOpIdleStrategy {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NoOpIdleStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdleStrategy for NoOpIdleStrategy {
+    #[inline]
+    fn idle(&mut self, _work_count: usize) {
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+    }
 }
 
 pub struct BusySpinIdleStrategy;
@@ -30,6 +88,43 @@ impl IdleStrategy for BusySpinIdleStrategy {
     #[inline]
     fn reset(&mut self) {
     }
+
+    #[inline]
+    fn idle_phase(&self) -> IdlePhase {
+        IdlePhase::Spin
+    }
+}
+
+pub struct YieldingIdleStrategy;
+
+impl YieldingIdleStrategy {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for YieldingIdleStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdleStrategy for YieldingIdleStrategy {
+    #[inline]
+    fn idle(&mut self, work_count: usize) {
+        if work_count == 0 {
+            thread::yield_now();
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+    }
+
+    #[inline]
+    fn idle_phase(&self) -> IdlePhase {
+        IdlePhase::Yield
+    }
 }
 
 pub struct BackoffIdleStrategy {
@@ -40,6 +135,8 @@ pub struct BackoffIdleStrategy {
     yields: u64,
     spins: u64,
     park_duration: Duration,
+    phase: IdlePhase,
+    clock: Arc<dyn NanoClock>,
 }
 
 impl BackoffIdleStrategy {
@@ -48,6 +145,23 @@ impl BackoffIdleStrategy {
         max_yields: u64,
         min_park_duration: Duration,
         max_park_duration: Duration,
+    ) -> Self {
+        Self::with_clock(
+            max_spins,
+            max_yields,
+            min_park_duration,
+            max_park_duration,
+            Arc::new(SystemNanoClock),
+        )
+    }
+
+    /// Parks through `clock`, so a [`crate::TestClock`] can observe the backoff schedule.
+    pub fn with_clock(
+        max_spins: u64,
+        max_yields: u64,
+        min_park_duration: Duration,
+        max_park_duration: Duration,
+        clock: Arc<dyn NanoClock>,
     ) -> Self {
         Self {
             max_yields,
@@ -57,6 +171,8 @@ impl BackoffIdleStrategy {
             yields: 0,
             spins: 0,
             park_duration: min_park_duration,
+            phase: IdlePhase::None,
+            clock,
         }
     }
 }
@@ -78,12 +194,15 @@ impl IdleStrategy for BackoffIdleStrategy {
             self.reset();
         } else if self.spins < self.max_spins {
             self.spins += 1;
+            self.phase = IdlePhase::Spin;
             core::hint::spin_loop();
         } else if self.yields < self.max_yields {
             self.yields += 1;
+            self.phase = IdlePhase::Yield;
             thread::yield_now();
         } else {
-            thread::sleep(self.park_duration);
+            self.phase = IdlePhase::Park;
+            self.clock.sleep(self.park_duration);
 
             self.park_duration = std::cmp::min(
                 Duration::from_nanos(self.park_duration.as_nanos() as u64 * 2),
@@ -97,15 +216,27 @@ impl IdleStrategy for BackoffIdleStrategy {
         self.yields = 0;
         self.park_duration = self.min_park_duration;
     }
+
+    fn idle_phase(&self) -> IdlePhase {
+        self.phase
+    }
 }
 
 pub struct SleepingIdleStrategy {
     sleep_duration: Duration,
+    clock: Arc<dyn NanoClock>,
 }
 
 impl SleepingIdleStrategy {
     pub fn new(sleep_duration: Duration) -> Self {
-        Self { sleep_duration }
+        Self::with_clock(sleep_duration, Arc::new(SystemNanoClock))
+    }
+
+    pub fn with_clock(sleep_duration: Duration, clock: Arc<dyn NanoClock>) -> Self {
+        Self {
+            sleep_duration,
+            clock,
+        }
     }
 }
 
@@ -118,40 +249,185 @@ impl Default for SleepingIdleStrategy {
 impl IdleStrategy for SleepingIdleStrategy {
     fn idle(&mut self, work_count: usize) {
         if work_count == 0 {
-            thread::sleep(self.sleep_duration);
+            self.clock.sleep(self.sleep_duration);
         }
     }
 
     fn reset(&mut self) {
     }
+
+    fn idle_phase(&self) -> IdlePhase {
+        IdlePhase::Park
+    }
 }
 
-pub struct ControllableIdleStrategy {
-    status: AtomicU64,
-    busy_spin_strategy: BusySpinIdleStrategy,
-    backoff_strategy: BackoffIdleStrategy,
+pub struct SleepingMillisIdleStrategy {
+    sleep_period_ms: u64,
+    clock: Arc<dyn NanoClock>,
+}
+
+impl SleepingMillisIdleStrategy {
+    pub fn new(sleep_period_ms: u64) -> Self {
+        Self::with_clock(sleep_period_ms, Arc::new(SystemNanoClock))
+    }
+
+    pub fn with_clock(sleep_period_ms: u64, clock: Arc<dyn NanoClock>) -> Self {
+        Self {
+            sleep_period_ms,
+            clock,
+        }
+    }
+
+    pub fn sleep_period_ms(&self) -> u64 {
+        self.sleep_period_ms
+    }
+}
+
+impl Default for SleepingMillisIdleStrategy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl IdleStrategy for SleepingMillisIdleStrategy {
+    fn idle(&mut self, work_count: usize) {
+        if work_count == 0 {
+            self.clock.sleep(Duration::from_millis(self.sleep_period_ms));
+        }
+    }
+
+    fn reset(&mut self) {
+    }
+
+    fn idle_phase(&self) -> IdlePhase {
+        IdlePhase::Park
+    }
+}
+
+/// Mode a [`ControllableIdleStrategy`] idles in, as stored in its status indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum ControlMode {
+    Running = 0,
+    Spinning = 1,
+    Yielding = 2,
+    Parking = 3,
+}
+
+impl ControlMode {
+    /// Unknown values park, so a corrupt status never leaves the agent burning a core.
+    pub fn from_status(status: i64) -> Self {
+        match status {
+            0 => ControlMode::Running,
+            1 => ControlMode::Spinning,
+            2 => ControlMode::Yielding,
+            _ => ControlMode::Parking,
+        }
+    }
+}
+
+/// Shared handle used to switch the mode of one or more [`ControllableIdleStrategy`]s.
+///
+/// Switching mode unparks any thread that parked through this controller. When the status
+/// indicator is a counter written by another process the parked thread only notices the change
+/// once its park period elapses.
+pub struct IdleStrategyController {
+    status: Arc<dyn StatusIndicator>,
+    parked_threads: Mutex<Vec<Thread>>,
+}
+
+impl IdleStrategyController {
+    pub fn new() -> Self {
+        Self::with_status_indicator(Arc::new(AtomicStatusIndicator::new(ControlMode::Running as i64)))
+    }
+
+    pub fn with_status_indicator(status: Arc<dyn StatusIndicator>) -> Self {
+        Self {
+            status,
+            parked_threads: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn status_indicator(&self) -> &Arc<dyn StatusIndicator> {
+        &self.status
+    }
+
+    pub fn mode(&self) -> ControlMode {
+        ControlMode::from_status(self.status.get_volatile())
+    }
+
+    pub fn set_mode(&self, mode: ControlMode) {
+        self.status.set_ordered(mode as i64);
+        // Woken threads register again if they park again, so the list only holds current parkers
+        for thread in self.parked_threads.lock().unwrap().drain(..) {
+            thread.unpark();
+        }
+    }
+
+    pub fn park(&self) {
+        self.set_mode(ControlMode::Parking);
+    }
+
+    pub fn unpark(&self) {
+        self.set_mode(ControlMode::Running);
+    }
+
+    fn register_current_thread(&self) {
+        let current = thread::current();
+        let mut threads = self.parked_threads.lock().unwrap();
+        if !threads.iter().any(|t| t.id() == current.id()) {
+            threads.push(current);
+        }
+    }
+
+    fn deregister_current_thread(&self) {
+        let current = thread::current().id();
+        self.parked_threads.lock().unwrap().retain(|t| t.id() != current);
+    }
 }
 
-const RUNNING: u64 = 0;
-const SPINNING: u64 = 1;
-const YIELDING: u64 = 2;
-const PARKING: u64 = 3;
+impl Default for IdleStrategyController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Idle strategy whose behaviour is chosen at runtime through an [`IdleStrategyController`].
+pub struct ControllableIdleStrategy {
+    controller: Arc<IdleStrategyController>,
+    park_period: Duration,
+    phase: IdlePhase,
+}
 
 impl ControllableIdleStrategy {
+    pub const DEFAULT_PARK_PERIOD: Duration = Duration::from_millis(1);
+
     pub fn new() -> Self {
+        Self::with_controller(Arc::new(IdleStrategyController::new()))
+    }
+
+    pub fn with_controller(controller: Arc<IdleStrategyController>) -> Self {
+        Self::with_park_period(controller, Self::DEFAULT_PARK_PERIOD)
+    }
+
+    pub fn with_park_period(controller: Arc<IdleStrategyController>, park_period: Duration) -> Self {
         Self {
-            status: AtomicU64::new(RUNNING),
-            busy_spin_strategy: BusySpinIdleStrategy::new(),
-            backoff_strategy: BackoffIdleStrategy::default(),
+            controller,
+            park_period,
+            phase: IdlePhase::None,
         }
     }
 
+    pub fn controller(&self) -> &Arc<IdleStrategyController> {
+        &self.controller
+    }
+
     pub fn park(&self) {
-        self.status.store(PARKING, Ordering::Release);
+        self.controller.park();
     }
 
     pub fn unpark(&self) {
-        self.status.store(RUNNING, Ordering::Release);
+        self.controller.unpark();
     }
 }
 
@@ -163,28 +439,218 @@ impl Default for ControllableIdleStrategy {
 
 impl IdleStrategy for ControllableIdleStrategy {
     fn idle(&mut self, work_count: usize) {
-        match self.status.load(Ordering::Acquire) {
-            RUNNING => {
-                self.busy_spin_strategy.idle(work_count);
+        if work_count > 0 {
+            return;
+        }
+
+        let mode = self.controller.mode();
+        self.phase = match mode {
+            ControlMode::Running | ControlMode::Spinning => IdlePhase::Spin,
+            ControlMode::Yielding => IdlePhase::Yield,
+            ControlMode::Parking => IdlePhase::Park,
+        };
+
+        match mode {
+            // Running busy-spins, as the uncontrolled strategy always has
+            ControlMode::Running | ControlMode::Spinning => std::hint::spin_loop(),
+            ControlMode::Yielding => thread::yield_now(),
+            ControlMode::Parking => {
+                // Register before re-checking the mode so a concurrent switch either is seen
+                // here or finds this thread to unpark.
+                self.controller.register_current_thread();
+                if self.controller.mode() == ControlMode::Parking {
+                    thread::park_timeout(self.park_period);
+                }
+                self.controller.deregister_current_thread();
             }
-            PARKING => {
-                thread::park();
+        }
+    }
+
+    fn reset(&mut self) {
+    }
+
+    fn idle_phase(&self) -> IdlePhase {
+        self.phase
+    }
+}
+
+pub const NOOP_ALIAS: &str = "noop";
+pub const BUSY_SPIN_ALIAS: &str = "spin";
+pub const YIELDING_ALIAS: &str = "yield";
+pub const SLEEPING_ALIAS: &str = "sleep-ns";
+pub const SLEEPING_MILLIS_ALIAS: &str = "sleep-ms";
+pub const BACKOFF_ALIAS: &str = "backoff";
+
+/// Builds an idle strategy from a config string of the form `alias[:name=value,...]`.
+///
+/// Aliases are `noop`, `spin`, `yield`, `sleep-ns:period=<duration>`, `sleep-ms:period=<duration>`
+/// and `backoff:spins=<n>,yields=<n>,min=<duration>,max=<duration>`. Durations take an `ns`, `us`,
+/// `ms` or `s` suffix and default to nanoseconds; omitted parameters keep their defaults.
+pub fn idle_strategy_from_str(config: &str) -> Result<Box<dyn IdleStrategy + Send>> {
+    idle_strategy_from_str_with_clock(config, Arc::new(SystemNanoClock))
+}
+
+/// As [`idle_strategy_from_str`], with the parking strategies sleeping through `clock`.
+pub fn idle_strategy_from_str_with_clock(
+    config: &str,
+    clock: Arc<dyn NanoClock>,
+) -> Result<Box<dyn IdleStrategy + Send>> {
+    let config = config.trim();
+    let (alias, params) = match config.split_once(':') {
+        Some((alias, params)) => (alias.trim(), parse_params(params)?),
+        None => (config, Vec::new()),
+    };
+
+    match alias {
+        NOOP_ALIAS => {
+            check_param_names(alias, &params, &[])?;
+            Ok(Box::new(NoOpIdleStrategy::new()))
+        }
+        BUSY_SPIN_ALIAS => {
+            check_param_names(alias, &params, &[])?;
+            Ok(Box::new(BusySpinIdleStrategy::new()))
+        }
+        YIELDING_ALIAS => {
+            check_param_names(alias, &params, &[])?;
+            Ok(Box::new(YieldingIdleStrategy::new()))
+        }
+        SLEEPING_ALIAS => {
+            check_param_names(alias, &params, &["period"])?;
+            let period = duration_param(&params, "period")?.unwrap_or(Duration::from_micros(1));
+            check_positive(alias, "period", period)?;
+            Ok(Box::new(SleepingIdleStrategy::with_clock(period, clock)))
+        }
+        SLEEPING_MILLIS_ALIAS => {
+            check_param_names(alias, &params, &["period"])?;
+            let period = duration_param(&params, "period")?.unwrap_or(Duration::from_millis(1));
+            if period.as_nanos() % 1_000_000 != 0 || period.is_zero() {
+                return Err(AgronaError::IllegalArgument(format!(
+                    "{}: period must be a positive whole number of milliseconds: {:?}",
+                    alias, period
+                )));
             }
-            _ => {
-                self.backoff_strategy.idle(work_count);
+            Ok(Box::new(SleepingMillisIdleStrategy::with_clock(
+                period.as_millis() as u64,
+                clock,
+            )))
+        }
+        BACKOFF_ALIAS => {
+            check_param_names(alias, &params, &["spins", "yields", "min", "max"])?;
+            let spins = count_param(&params, "spins")?.unwrap_or(10);
+            let yields = count_param(&params, "yields")?.unwrap_or(5);
+            let min = duration_param(&params, "min")?.unwrap_or(Duration::from_nanos(1));
+            let max = duration_param(&params, "max")?.unwrap_or(Duration::from_millis(1));
+
+            check_positive(alias, "min", min)?;
+            if max < min {
+                return Err(AgronaError::IllegalArgument(format!(
+                    "{}: max {:?} must not be less than min {:?}",
+                    alias, max, min
+                )));
             }
+
+            Ok(Box::new(BackoffIdleStrategy::with_clock(spins, yields, min, max, clock)))
         }
+        _ => Err(AgronaError::IllegalArgument(format!(
+            "unknown idle strategy alias: '{}'",
+            alias
+        ))),
     }
+}
 
-    fn reset(&mut self) {
-        self.busy_spin_strategy.reset();
-        self.backoff_strategy.reset();
+fn parse_params(params: &str) -> Result<Vec<(&str, &str)>> {
+    params
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            param
+                .split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .ok_or_else(|| {
+                    AgronaError::IllegalArgument(format!(
+                        "idle strategy parameter must be name=value: '{}'",
+                        param
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn check_param_names(alias: &str, params: &[(&str, &str)], allowed: &[&str]) -> Result<()> {
+    for (name, _) in params {
+        if !allowed.contains(name) {
+            return Err(AgronaError::IllegalArgument(format!(
+                "{}: unknown parameter '{}', expected one of {:?}",
+                alias, name, allowed
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_positive(alias: &str, name: &str, duration: Duration) -> Result<()> {
+    if duration.is_zero() {
+        return Err(AgronaError::IllegalArgument(format!(
+            "{}: {} must be greater than zero",
+            alias, name
+        )));
+    }
+    Ok(())
+}
+
+fn find_param<'a>(params: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .rev()
+        .find(|(param_name, _)| *param_name == name)
+        .map(|(_, value)| *value)
+}
+
+fn count_param(params: &[(&str, &str)], name: &str) -> Result<Option<u64>> {
+    find_param(params, name)
+        .map(|value| {
+            value.parse::<u64>().map_err(|_| {
+                AgronaError::IllegalArgument(format!(
+                    "{} must be a non-negative integer: '{}'",
+                    name, value
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn duration_param(params: &[(&str, &str)], name: &str) -> Result<Option<Duration>> {
+    find_param(params, name).map(parse_duration).transpose()
+}
+
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+
+    let amount = digits.parse::<u64>().map_err(|_| {
+        AgronaError::IllegalArgument(format!("invalid duration: '{}'", value))
+    })?;
+
+    match unit.to_ascii_lowercase().as_str() {
+        "" | "ns" => Ok(Duration::from_nanos(amount)),
+        "us" => Ok(Duration::from_micros(amount)),
+        "ms" => Ok(Duration::from_millis(amount)),
+        "s" => Ok(Duration::from_secs(amount)),
+        _ => Err(AgronaError::IllegalArgument(format!(
+            "invalid duration unit '{}' in '{}', expected ns, us, ms or s",
+            unit, value
+        ))),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::TestClock;
     use std::time::Instant;
 
     #[test]
@@ -208,6 +674,46 @@ mod tests {
         strategy.idle(0);
     }
 
+    #[test]
+    fn test_backoff_park_schedule_against_test_clock() {
+        let clock = Arc::new(TestClock::new(0));
+        let mut strategy = BackoffIdleStrategy::with_clock(
+            2,
+            1,
+            Duration::from_nanos(100),
+            Duration::from_nanos(400),
+            clock.clone(),
+        );
+
+        for _ in 0..3 {
+            strategy.idle(0);
+        }
+        assert_eq!(clock.nano_time(), 0);
+
+        for _ in 0..4 {
+            strategy.idle(0);
+        }
+        assert_eq!(clock.nano_time(), 100 + 200 + 400 + 400);
+
+        strategy.idle(1);
+        for _ in 0..4 {
+            strategy.idle(0);
+        }
+        assert_eq!(clock.nano_time(), 1_100 + 100);
+    }
+
+    #[test]
+    fn test_sleeping_strategy_against_test_clock() {
+        let clock = Arc::new(TestClock::new(0));
+        let mut strategy = SleepingIdleStrategy::with_clock(Duration::from_micros(5), clock.clone());
+
+        strategy.idle(1);
+        strategy.idle(0);
+        strategy.idle(0);
+
+        assert_eq!(clock.nano_time(), 10_000);
+    }
+
     #[test]
     fn test_sleeping_strategy() {
         let mut strategy = SleepingIdleStrategy::new(Duration::from_nanos(1));
@@ -230,4 +736,147 @@ mod tests {
         strategy.unpark();
         strategy.idle(1);
     }
+
+    #[test]
+    fn test_controllable_strategy_switched_from_another_thread() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let controller = Arc::new(IdleStrategyController::new());
+        let cycles = Arc::new(AtomicUsize::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let idler = {
+            let controller = Arc::clone(&controller);
+            let cycles = Arc::clone(&cycles);
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut strategy = ControllableIdleStrategy::with_park_period(controller, Duration::from_secs(60));
+                while !stop.load(Ordering::Acquire) {
+                    strategy.idle(0);
+                    cycles.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        };
+
+        let wait_for_cycles = || {
+            let target = cycles.load(Ordering::Relaxed) + 100;
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while cycles.load(Ordering::Relaxed) < target {
+                assert!(Instant::now() < deadline, "idler did not make progress");
+                thread::yield_now();
+            }
+        };
+
+        for mode in [ControlMode::Spinning, ControlMode::Yielding, ControlMode::Running] {
+            controller.set_mode(mode);
+            assert_eq!(controller.mode(), mode);
+            wait_for_cycles();
+        }
+
+        controller.park();
+        thread::sleep(Duration::from_millis(50));
+        let parked_at = cycles.load(Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(50));
+        assert!(cycles.load(Ordering::Relaxed) <= parked_at + 1);
+
+        // Far shorter than the park period, so progress means the switch woke the thread.
+        controller.unpark();
+        wait_for_cycles();
+        assert!(controller.parked_threads.lock().unwrap().is_empty());
+
+        stop.store(true, Ordering::Release);
+        idler.join().unwrap();
+    }
+
+    #[test]
+    fn test_controllable_strategy_over_counter() {
+        use crate::status::{CountersManager, COUNTER_LENGTH, METADATA_LENGTH};
+        use crate::AtomicBuffer;
+
+        let mut manager = CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH * 4).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * 4).unwrap(),
+        )
+        .unwrap();
+        let counter = unsafe { manager.new_counter("idle-control", 0, &[]) }.unwrap();
+        let controller = Arc::new(IdleStrategyController::with_status_indicator(Arc::new(counter)));
+        let mut strategy = ControllableIdleStrategy::with_park_period(Arc::clone(&controller), Duration::from_micros(1));
+
+        let id = controller.status_indicator().id();
+        manager.set_counter_value(id, ControlMode::Yielding as i64).unwrap();
+        assert_eq!(strategy.controller().mode(), ControlMode::Yielding);
+        strategy.idle(0);
+
+        manager.set_counter_value(id, 42).unwrap();
+        assert_eq!(strategy.controller().mode(), ControlMode::Parking);
+        strategy.idle(0);
+    }
+
+    #[test]
+    fn test_idle_strategy_from_str() {
+        let clock = Arc::new(TestClock::new(0));
+        let parse = |config: &str| idle_strategy_from_str_with_clock(config, clock.clone()).unwrap();
+        let parked_ns = |strategy: &mut Box<dyn IdleStrategy + Send>| {
+            let start = clock.nano_time();
+            strategy.idle(0);
+            clock.nano_time() - start
+        };
+
+        let mut strategy = parse("noop");
+        assert_eq!(parked_ns(&mut strategy), 0);
+        assert_eq!(strategy.idle_phase(), IdlePhase::None);
+        let mut strategy = parse("spin");
+        assert_eq!(parked_ns(&mut strategy), 0);
+        assert_eq!(strategy.idle_phase(), IdlePhase::Spin);
+        let mut strategy = parse(" yield ");
+        assert_eq!(parked_ns(&mut strategy), 0);
+        assert_eq!(strategy.idle_phase(), IdlePhase::Yield);
+
+        assert_eq!(parked_ns(&mut parse("sleep-ns:period=100us")), 100_000);
+        assert_eq!(parked_ns(&mut parse("sleep-ms:period=2ms")), 2_000_000);
+
+        let mut strategy = parse("backoff:spins=10,yields=5,min=1us,max=4us");
+        for _ in 0..10 {
+            assert_eq!(parked_ns(&mut strategy), 0);
+            assert_eq!(strategy.idle_phase(), IdlePhase::Spin);
+        }
+        for _ in 0..5 {
+            assert_eq!(parked_ns(&mut strategy), 0);
+            assert_eq!(strategy.idle_phase(), IdlePhase::Yield);
+        }
+        let parks: Vec<_> = (0..4).map(|_| parked_ns(&mut strategy)).collect();
+        assert_eq!(parks, vec![1_000, 2_000, 4_000, 4_000]);
+
+        // Omitted parameters fall back to 10 spins, 5 yields and a 1ns minimum park
+        let mut strategy = parse("backoff");
+        let parks: Vec<_> = (0..16).map(|_| parked_ns(&mut strategy)).collect();
+        assert_eq!(parks[..15], [0; 15]);
+        assert_eq!(parks[15], 1);
+    }
+
+    #[test]
+    fn test_idle_strategy_from_str_rejects_bad_config() {
+        let error = |config: &str| match idle_strategy_from_str(config) {
+            Err(AgronaError::IllegalArgument(message)) => message,
+            _ => panic!("expected IllegalArgument for '{}'", config),
+        };
+
+        assert!(error("parking").contains("unknown idle strategy alias"));
+        assert!(error("spin:spins=1").contains("unknown parameter 'spins'"));
+        assert!(error("backoff:spins=-1").contains("spins must be a non-negative integer"));
+        assert!(error("backoff:min=1ms,max=1us").contains("must not be less than min"));
+        assert!(error("backoff:min=0").contains("min must be greater than zero"));
+        assert!(error("backoff:min=5h").contains("invalid duration unit"));
+        assert!(error("backoff:spins").contains("name=value"));
+        assert!(error("sleep-ms:period=1500us").contains("whole number of milliseconds"));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("250").unwrap(), Duration::from_nanos(250));
+        assert_eq!(parse_duration("3us").unwrap(), Duration::from_micros(3));
+        assert_eq!(parse_duration("2MS").unwrap(), Duration::from_millis(2));
+        assert_eq!(parse_duration("1s").unwrap(), Duration::from_secs(1));
+        assert!(parse_duration("ms").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,336 @@
+use crate::clock::{NanoClock, SystemNanoClock};
+use crate::idle_strategy::{IdlePhase, IdleStrategy};
+use crate::status::{AtomicCounter, CountersManager};
+use agrona_core::error::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const IDLE_RUN_BUCKETS: usize = 16;
+pub const IDLE_STRATEGY_STATS_TYPE_ID: i32 = 20;
+
+/// Bucket 0 counts work that arrived without idling, bucket `i` counts runs of
+/// `[2^(i-1), 2^i)` idle cycles and the last bucket is open ended.
+#[inline]
+pub fn idle_run_bucket(idle_cycles: u64) -> usize {
+    if idle_cycles == 0 {
+        0
+    } else {
+        std::cmp::min((u64::BITS - idle_cycles.leading_zeros()) as usize, IDLE_RUN_BUCKETS - 1)
+    }
+}
+
+#[inline]
+pub fn idle_run_bucket_lower_bound(bucket: usize) -> u64 {
+    if bucket == 0 {
+        0
+    } else {
+        1 << (bucket - 1)
+    }
+}
+
+/// Statistics shared between an [`InstrumentedIdleStrategy`] and whoever reads them.
+///
+/// Only the idling thread writes, so readers see each value atomically but a snapshot is not a
+/// consistent cut across values.
+#[derive(Debug, Default)]
+pub struct IdleStrategyStats {
+    spins: AtomicU64,
+    yields: AtomicU64,
+    parks: AtomicU64,
+    parked_ns: AtomicU64,
+    idle_cycles: AtomicU64,
+    work_cycles: AtomicU64,
+    idle_runs: [AtomicU64; IDLE_RUN_BUCKETS],
+}
+
+impl IdleStrategyStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> IdleStrategySnapshot {
+        let mut idle_runs = [0; IDLE_RUN_BUCKETS];
+        for (bucket, count) in idle_runs.iter_mut().zip(self.idle_runs.iter()) {
+            *bucket = count.load(Ordering::Acquire);
+        }
+
+        IdleStrategySnapshot {
+            spins: self.spins.load(Ordering::Acquire),
+            yields: self.yields.load(Ordering::Acquire),
+            parks: self.parks.load(Ordering::Acquire),
+            parked_time: Duration::from_nanos(self.parked_ns.load(Ordering::Acquire)),
+            idle_cycles: self.idle_cycles.load(Ordering::Acquire),
+            work_cycles: self.work_cycles.load(Ordering::Acquire),
+            idle_runs,
+        }
+    }
+
+    #[inline]
+    fn increment(counter: &AtomicU64, delta: u64) {
+        counter.store(counter.load(Ordering::Relaxed) + delta, Ordering::Release);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IdleStrategySnapshot {
+    pub spins: u64,
+    pub yields: u64,
+    pub parks: u64,
+    pub parked_time: Duration,
+    pub idle_cycles: u64,
+    pub work_cycles: u64,
+    pub idle_runs: [u64; IDLE_RUN_BUCKETS],
+}
+
+impl IdleStrategySnapshot {
+    /// Fraction of duty cycles that found work, or 0 before the first cycle.
+    pub fn work_ratio(&self) -> f64 {
+        let total = self.idle_cycles + self.work_cycles;
+        if total == 0 {
+            0.0
+        } else {
+            self.work_cycles as f64 / total as f64
+        }
+    }
+}
+
+/// Wraps an idle strategy and records which phase each idle cycle entered, how long it parked
+/// and how many consecutive idle cycles passed before work arrived.
+pub struct InstrumentedIdleStrategy<S: IdleStrategy> {
+    delegate: S,
+    clock: Arc<dyn NanoClock>,
+    stats: Arc<IdleStrategyStats>,
+    idle_run: u64,
+}
+
+impl<S: IdleStrategy> InstrumentedIdleStrategy<S> {
+    pub fn new(delegate: S) -> Self {
+        Self::with_clock(delegate, Arc::new(SystemNanoClock))
+    }
+
+    pub fn with_clock(delegate: S, clock: Arc<dyn NanoClock>) -> Self {
+        Self {
+            delegate,
+            clock,
+            stats: Arc::new(IdleStrategyStats::new()),
+            idle_run: 0,
+        }
+    }
+
+    /// Handle for reading the statistics after the strategy has moved to the agent thread.
+    pub fn stats(&self) -> Arc<IdleStrategyStats> {
+        Arc::clone(&self.stats)
+    }
+
+    pub fn snapshot(&self) -> IdleStrategySnapshot {
+        self.stats.snapshot()
+    }
+
+    pub fn delegate(&self) -> &S {
+        &self.delegate
+    }
+
+    pub fn into_inner(self) -> S {
+        self.delegate
+    }
+}
+
+impl<S: IdleStrategy> IdleStrategy for InstrumentedIdleStrategy<S> {
+    fn idle(&mut self, work_count: usize) {
+        let stats = &self.stats;
+        if work_count > 0 {
+            IdleStrategyStats::increment(&stats.idle_runs[idle_run_bucket(self.idle_run)], 1);
+            IdleStrategyStats::increment(&stats.work_cycles, 1);
+            self.idle_run = 0;
+            self.delegate.idle(work_count);
+            return;
+        }
+
+        self.idle_run += 1;
+        IdleStrategyStats::increment(&stats.idle_cycles, 1);
+
+        let start = self.clock.nano_time();
+        self.delegate.idle(0);
+
+        match self.delegate.idle_phase() {
+            IdlePhase::None => {}
+            IdlePhase::Spin => IdleStrategyStats::increment(&stats.spins, 1),
+            IdlePhase::Yield => IdleStrategyStats::increment(&stats.yields, 1),
+            IdlePhase::Park => {
+                let parked_ns = self.clock.nano_time().saturating_sub(start).max(0) as u64;
+                IdleStrategyStats::increment(&stats.parks, 1);
+                IdleStrategyStats::increment(&stats.parked_ns, parked_ns);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.delegate.reset();
+    }
+
+    fn idle_phase(&self) -> IdlePhase {
+        self.delegate.idle_phase()
+    }
+}
+
+/// Counters an [`IdleStrategySnapshot`] is published into so other processes can observe it.
+pub struct IdleStrategyCounters {
+    spins: AtomicCounter,
+    yields: AtomicCounter,
+    parks: AtomicCounter,
+    parked_ns: AtomicCounter,
+    idle_cycles: AtomicCounter,
+    work_cycles: AtomicCounter,
+    idle_runs: Vec<AtomicCounter>,
+}
+
+impl IdleStrategyCounters {
+    /// Allocates one counter per statistic, labelled with `name`, keyed by statistic index.
+    ///
+    /// # Safety
+    ///
+    /// The counters point into `manager`'s values buffer, which must remain valid for as long as
+    /// they are used. See [`CountersManager::new_counter`].
+    pub unsafe fn allocate(manager: &mut CountersManager, name: &str) -> Result<Self> {
+        let mut index = 0i32;
+        let mut next = |label: String| {
+            let key = index.to_le_bytes();
+            index += 1;
+            manager.new_counter(&label, IDLE_STRATEGY_STATS_TYPE_ID, &key)
+        };
+
+        let spins = next(format!("idle spins: {}", name))?;
+        let yields = next(format!("idle yields: {}", name))?;
+        let parks = next(format!("idle parks: {}", name))?;
+        let parked_ns = next(format!("idle parked ns: {}", name))?;
+        let idle_cycles = next(format!("idle cycles: {}", name))?;
+        let work_cycles = next(format!("work cycles: {}", name))?;
+        let idle_runs = (0..IDLE_RUN_BUCKETS)
+            .map(|bucket| {
+                let label = if bucket == IDLE_RUN_BUCKETS - 1 {
+                    format!("idle run {}+: {}", idle_run_bucket_lower_bound(bucket), name)
+                } else if bucket == 0 {
+                    format!("idle run 0: {}", name)
+                } else {
+                    format!(
+                        "idle run {}-{}: {}",
+                        idle_run_bucket_lower_bound(bucket),
+                        idle_run_bucket_lower_bound(bucket + 1) - 1,
+                        name
+                    )
+                };
+                next(label)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            spins,
+            yields,
+            parks,
+            parked_ns,
+            idle_cycles,
+            work_cycles,
+            idle_runs,
+        })
+    }
+
+    pub fn publish(&self, snapshot: &IdleStrategySnapshot) {
+        self.spins.set_ordered(snapshot.spins as i64);
+        self.yields.set_ordered(snapshot.yields as i64);
+        self.parks.set_ordered(snapshot.parks as i64);
+        self.parked_ns.set_ordered(snapshot.parked_time.as_nanos() as i64);
+        self.idle_cycles.set_ordered(snapshot.idle_cycles as i64);
+        self.work_cycles.set_ordered(snapshot.work_cycles as i64);
+        for (counter, &count) in self.idle_runs.iter().zip(snapshot.idle_runs.iter()) {
+            counter.set_ordered(count as i64);
+        }
+    }
+
+    /// Counter ids in allocation order, so they can be freed with the manager that allocated them.
+    pub fn counter_ids(&self) -> Vec<i32> {
+        [&self.spins, &self.yields, &self.parks, &self.parked_ns, &self.idle_cycles, &self.work_cycles]
+            .into_iter()
+            .chain(self.idle_runs.iter())
+            .map(AtomicCounter::id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use crate::idle_strategy::{BackoffIdleStrategy, YieldingIdleStrategy};
+    use crate::status::{COUNTER_LENGTH, METADATA_LENGTH};
+    use crate::AtomicBuffer;
+
+    #[test]
+    fn test_idle_run_buckets() {
+        assert_eq!(idle_run_bucket(0), 0);
+        assert_eq!(idle_run_bucket(1), 1);
+        assert_eq!(idle_run_bucket(2), 2);
+        assert_eq!(idle_run_bucket(3), 2);
+        assert_eq!(idle_run_bucket(4), 3);
+        assert_eq!(idle_run_bucket(u64::MAX), IDLE_RUN_BUCKETS - 1);
+
+        for bucket in 0..IDLE_RUN_BUCKETS {
+            assert_eq!(idle_run_bucket(idle_run_bucket_lower_bound(bucket)), bucket);
+        }
+    }
+
+    #[test]
+    fn test_records_backoff_phases_and_parked_time() {
+        let clock = Arc::new(TestClock::new(0));
+        let backoff = BackoffIdleStrategy::with_clock(
+            2,
+            1,
+            Duration::from_micros(1),
+            Duration::from_micros(4),
+            clock.clone(),
+        );
+        let mut strategy = InstrumentedIdleStrategy::with_clock(backoff, clock);
+        let stats = strategy.stats();
+
+        for _ in 0..5 {
+            strategy.idle(0);
+        }
+        strategy.idle(1);
+        strategy.idle(1);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.spins, 2);
+        assert_eq!(snapshot.yields, 1);
+        assert_eq!(snapshot.parks, 2);
+        assert_eq!(snapshot.parked_time, Duration::from_micros(3));
+        assert_eq!(snapshot.idle_cycles, 5);
+        assert_eq!(snapshot.work_cycles, 2);
+        assert_eq!(snapshot.idle_runs[0], 1);
+        assert_eq!(snapshot.idle_runs[idle_run_bucket(5)], 1);
+        assert_eq!(snapshot.work_ratio(), 2.0 / 7.0);
+    }
+
+    #[test]
+    fn test_publishes_into_counters() {
+        let mut manager = CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH * 32).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * 32).unwrap(),
+        )
+        .unwrap();
+        let counters = unsafe { IdleStrategyCounters::allocate(&mut manager, "sender") }.unwrap();
+
+        let mut strategy = InstrumentedIdleStrategy::new(YieldingIdleStrategy::new());
+        strategy.idle(0);
+        strategy.idle(0);
+        strategy.idle(3);
+        counters.publish(&strategy.snapshot());
+
+        let ids = counters.counter_ids();
+        assert_eq!(ids.len(), 6 + IDLE_RUN_BUCKETS);
+        assert_eq!(manager.counter_label(ids[1]).unwrap(), "idle yields: sender");
+        assert_eq!(manager.counter_value(ids[1]).unwrap(), 2);
+        assert_eq!(manager.counter_value(ids[5]).unwrap(), 1);
+        assert_eq!(manager.counter_label(ids[6 + 2]).unwrap(), "idle run 2-3: sender");
+        assert_eq!(manager.counter_value(ids[6 + 2]).unwrap(), 1);
+    }
+}
@@ -0,0 +1,282 @@
+use agrona_core::error::{AgronaError, Result};
+use std::time::Duration;
+
+pub const DEFAULT_SUB_BUCKET_BITS: u32 = 7;
+pub const MAX_SUB_BUCKET_BITS: u32 = 16;
+
+/// Log-linear histogram over the full `u64` range, in the style of HdrHistogram.
+///
+/// Values below `2^sub_bucket_bits` are counted exactly. Above that each power of two is split
+/// into `2^(sub_bucket_bits - 1)` linear buckets, bounding the relative error by
+/// `2^(1 - sub_bucket_bits)`. All storage is allocated up front so recording never allocates.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    sub_bucket_bits: u32,
+    counts: Box<[u64]>,
+    total_count: u64,
+    min_value: u64,
+    max_value: u64,
+    total_value: u128,
+}
+
+impl LatencyHistogram {
+    pub fn new(sub_bucket_bits: u32) -> Result<Self> {
+        if !(1..=MAX_SUB_BUCKET_BITS).contains(&sub_bucket_bits) {
+            return Err(AgronaError::IllegalArgument(format!(
+                "sub bucket bits must be in 1..={}: {}",
+                MAX_SUB_BUCKET_BITS, sub_bucket_bits
+            )));
+        }
+
+        let sub_bucket_count = 1usize << sub_bucket_bits;
+        let half_count = sub_bucket_count >> 1;
+        let length = sub_bucket_count + (u64::BITS - sub_bucket_bits) as usize * half_count;
+
+        Ok(Self {
+            sub_bucket_bits,
+            counts: vec![0; length].into_boxed_slice(),
+            total_count: 0,
+            min_value: u64::MAX,
+            max_value: 0,
+            total_value: 0,
+        })
+    }
+
+    #[inline]
+    pub fn sub_bucket_bits(&self) -> u32 {
+        self.sub_bucket_bits
+    }
+
+    #[inline]
+    pub fn bucket_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[inline]
+    fn index_of(&self, value: u64) -> usize {
+        let bits = self.sub_bucket_bits;
+        if value < (1 << bits) {
+            return value as usize;
+        }
+
+        let shift = (u64::BITS - value.leading_zeros()) - bits;
+        let half_count = 1usize << (bits - 1);
+        let sub_bucket = (value >> shift) as usize - half_count;
+
+        (1usize << bits) + (shift as usize - 1) * half_count + sub_bucket
+    }
+
+    /// Lowest and highest values counted in the bucket at `index`.
+    fn bucket_range(&self, index: usize) -> (u64, u64) {
+        let bits = self.sub_bucket_bits;
+        let sub_bucket_count = 1usize << bits;
+        if index < sub_bucket_count {
+            return (index as u64, index as u64);
+        }
+
+        let half_count = sub_bucket_count >> 1;
+        let offset = index - sub_bucket_count;
+        let shift = (offset / half_count) as u32 + 1;
+        let sub_bucket = (offset % half_count + half_count) as u64;
+        let lowest = sub_bucket << shift;
+
+        (lowest, lowest + ((1u64 << shift) - 1))
+    }
+
+    #[inline]
+    pub fn record(&mut self, value: u64) {
+        self.record_n(value, 1);
+    }
+
+    pub fn record_n(&mut self, value: u64, count: u64) {
+        if count == 0 {
+            return;
+        }
+
+        let index = self.index_of(value);
+        self.counts[index] += count;
+        self.total_count += count;
+        self.total_value += value as u128 * count as u128;
+        self.min_value = self.min_value.min(value);
+        self.max_value = self.max_value.max(value);
+    }
+
+    /// Records a duration in nanoseconds, saturating at `u64::MAX`.
+    #[inline]
+    pub fn record_duration(&mut self, duration: Duration) {
+        self.record(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX));
+    }
+
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.total_count
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.total_count == 0
+    }
+
+    /// Smallest value recorded, or 0 when empty.
+    pub fn min(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.min_value
+        }
+    }
+
+    #[inline]
+    pub fn max(&self) -> u64 {
+        self.max_value
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            self.total_value as f64 / self.total_count as f64
+        }
+    }
+
+    /// Value at or below which `percentile` percent of recorded values fall, reported as the
+    /// highest value of the bucket and clamped to the recorded min and max.
+    pub fn value_at_percentile(&self, percentile: f64) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let percentile = percentile.clamp(0.0, 100.0);
+        let target = ((percentile / 100.0) * self.total_count as f64).ceil() as u64;
+        let target = target.clamp(1, self.total_count);
+
+        let mut cumulative = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                let (_, highest) = self.bucket_range(index);
+                return highest.clamp(self.min_value, self.max_value);
+            }
+        }
+
+        self.max_value
+    }
+
+    /// Visits each non-empty bucket as `(lowest, highest, count)` in ascending value order.
+    pub fn for_each_bucket<F>(&self, mut f: F)
+    where
+        F: FnMut(u64, u64, u64),
+    {
+        for (index, &count) in self.counts.iter().enumerate() {
+            if count > 0 {
+                let (lowest, highest) = self.bucket_range(index);
+                f(lowest, highest, count);
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) -> Result<()> {
+        if other.sub_bucket_bits != self.sub_bucket_bits {
+            return Err(AgronaError::IllegalArgument(format!(
+                "cannot merge histograms with {} and {} sub bucket bits",
+                self.sub_bucket_bits, other.sub_bucket_bits
+            )));
+        }
+
+        if other.is_empty() {
+            return Ok(());
+        }
+
+        for (count, &other_count) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other_count;
+        }
+        self.total_count += other.total_count;
+        self.total_value += other.total_value;
+        self.min_value = self.min_value.min(other.min_value);
+        self.max_value = self.max_value.max(other.max_value);
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+        self.total_count = 0;
+        self.min_value = u64::MAX;
+        self.max_value = 0;
+        self.total_value = 0;
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new(DEFAULT_SUB_BUCKET_BITS).expect("default sub bucket bits are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_ranges_cover_every_value() {
+        let histogram = LatencyHistogram::new(3).unwrap();
+
+        let mut expected_lowest = 0u64;
+        for index in 0..histogram.bucket_count() {
+            let (lowest, highest) = histogram.bucket_range(index);
+            assert_eq!(lowest, expected_lowest);
+            assert_eq!(histogram.index_of(lowest), index);
+            assert_eq!(histogram.index_of(highest), index);
+            expected_lowest = highest.wrapping_add(1);
+        }
+        assert_eq!(expected_lowest, 0);
+        assert_eq!(histogram.index_of(u64::MAX), histogram.bucket_count() - 1);
+    }
+
+    #[test]
+    fn test_percentiles_within_precision() {
+        let mut histogram = LatencyHistogram::default();
+        for value in 1..=10_000u64 {
+            histogram.record(value);
+        }
+
+        assert_eq!(histogram.count(), 10_000);
+        assert_eq!(histogram.min(), 1);
+        assert_eq!(histogram.max(), 10_000);
+        assert_eq!(histogram.mean(), 5_000.5);
+        assert_eq!(histogram.value_at_percentile(0.0), 1);
+        assert_eq!(histogram.value_at_percentile(100.0), 10_000);
+
+        for (percentile, exact) in [(50.0, 5_000u64), (90.0, 9_000), (99.0, 9_900), (99.9, 9_990)] {
+            let value = histogram.value_at_percentile(percentile);
+            let error = value.abs_diff(exact) as f64 / exact as f64;
+            assert!(error <= 1.0 / 64.0, "p{} = {} expected ~{}", percentile, value, exact);
+        }
+    }
+
+    #[test]
+    fn test_merge_and_reset() {
+        let mut a = LatencyHistogram::default();
+        let mut b = LatencyHistogram::default();
+        a.record_n(100, 3);
+        b.record_duration(Duration::from_micros(5));
+        b.record(7);
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.count(), 5);
+        assert_eq!(a.min(), 7);
+        assert_eq!(a.max(), 5_000);
+
+        let mut buckets = Vec::new();
+        a.for_each_bucket(|_, _, count| buckets.push(count));
+        assert_eq!(buckets, vec![1, 3, 1]);
+
+        let other = LatencyHistogram::new(4).unwrap();
+        assert!(matches!(a.merge(&other), Err(AgronaError::IllegalArgument(_))));
+        assert!(LatencyHistogram::new(0).is_err());
+
+        a.reset();
+        assert!(a.is_empty());
+        assert_eq!(a.value_at_percentile(99.0), 0);
+    }
+}
@@ -1,7 +1,49 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod agent;
+pub mod agent_invoker;
+pub mod agent_runner;
 pub mod atomic_buffer;
+pub mod broadcast;
+pub mod clock;
+pub mod composite_agent;
+pub mod deadline_timer_wheel;
+pub mod duty_cycle_tracker;
+pub mod dynamic_composite_agent;
+pub mod error_handler;
+pub mod errors;
 pub mod idle_strategy;
+pub mod instrumented_idle_strategy;
+pub mod latency_histogram;
+pub mod mark_file;
+pub mod ringbuffer;
+pub mod shared_buffer;
+#[cfg(unix)]
+pub mod shutdown_signal_barrier;
+pub mod snowflake_id_generator;
+pub mod status;
+#[cfg(target_os = "linux")]
+pub mod thread_affinity;
 
+pub use agent::*;
+pub use agent_invoker::*;
+pub use agent_runner::*;
 pub use atomic_buffer::*;
-pub use idle_strategy::*;
\ No newline at end of file
+pub use broadcast::{BroadcastReceiver, BroadcastTransmitter, CopyBroadcastReceiver};
+pub use clock::*;
+pub use composite_agent::*;
+pub use deadline_timer_wheel::*;
+pub use duty_cycle_tracker::*;
+pub use dynamic_composite_agent::*;
+pub use error_handler::*;
+pub use errors::*;
+pub use idle_strategy::*;
+pub use instrumented_idle_strategy::*;
+pub use latency_histogram::*;
+pub use mark_file::*;
+pub use ringbuffer::{ManyToOneRingBuffer, OneToOneRingBuffer, RingBuffer};
+pub use shared_buffer::*;
+#[cfg(unix)]
+pub use shutdown_signal_barrier::*;
+pub use snowflake_id_generator::*;
+pub use status::*;
\ No newline at end of file
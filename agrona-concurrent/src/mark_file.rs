@@ -0,0 +1,247 @@
+use crate::atomic_buffer::AtomicBuffer;
+use crate::clock::{EpochClock, SystemEpochClock};
+use agrona_core::bit_util::CACHE_LINE_LENGTH;
+use agrona_core::error::{AgronaError, Result};
+use agrona_core::MappedFile;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+pub const MARK_FILE_VERSION_OFFSET: usize = 0;
+pub const MARK_FILE_ACTIVITY_TIMESTAMP_OFFSET: usize = 8;
+pub const MARK_FILE_PID_OFFSET: usize = 16;
+pub const MARK_FILE_HEADER_LENGTH: usize = CACHE_LINE_LENGTH;
+
+/// Version value of a mark file whose owner has not yet signalled it is ready.
+pub const MARK_FILE_NOT_READY: i32 = 0;
+
+/// Memory mapped file announcing that a process is alive and which layout version it uses.
+///
+/// The header holds the layout version, an activity timestamp in epoch milliseconds and the
+/// owner's pid. The version is written last with an ordered store, so a reader that sees a
+/// non-zero version also sees the rest of the file as initialised by its owner. The space after
+/// [`MARK_FILE_HEADER_LENGTH`] is free for application data.
+pub struct MarkFile {
+    buffer: AtomicBuffer,
+    mapped_file: MappedFile,
+}
+
+impl MarkFile {
+    /// Creates (or truncates) the mark file at `path` as not ready, recording this process's pid.
+    ///
+    /// Fails with `IllegalState` rather than taking over a file whose owner is still active, i.e.
+    /// has updated its activity timestamp within `timeout`.
+    pub fn create<P: AsRef<Path>>(path: P, length: usize, timeout: Duration) -> Result<Self> {
+        Self::create_at(path, length, &SystemEpochClock, timeout)
+    }
+
+    pub fn create_at<P: AsRef<Path>>(
+        path: P,
+        length: usize,
+        clock: &dyn EpochClock,
+        timeout: Duration,
+    ) -> Result<Self> {
+        if Self::is_file_active(path.as_ref(), clock, timeout)? {
+            return Err(AgronaError::IllegalState(format!(
+                "active mark file detected: {}",
+                path.as_ref().display()
+            )));
+        }
+
+        if length < MARK_FILE_HEADER_LENGTH {
+            return Err(AgronaError::IllegalArgument(format!(
+                "mark file length {} is less than header length {}",
+                length, MARK_FILE_HEADER_LENGTH
+            )));
+        }
+
+        let mut mark_file = Self::wrap(MappedFile::create(path, length)?);
+        mark_file
+            .buffer
+            .put_volatile_u64(MARK_FILE_PID_OFFSET, std::process::id() as u64)?;
+
+        Ok(mark_file)
+    }
+
+    /// Maps an existing mark file, e.g. to check from another process whether its owner is active.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mapped_file = MappedFile::open(path)?;
+        if mapped_file.capacity() < MARK_FILE_HEADER_LENGTH {
+            return Err(AgronaError::IllegalState(format!(
+                "mark file {} length {} is less than header length {}",
+                mapped_file.path().display(),
+                mapped_file.capacity(),
+                MARK_FILE_HEADER_LENGTH
+            )));
+        }
+
+        Ok(Self::wrap(mapped_file))
+    }
+
+    fn wrap(mut mapped_file: MappedFile) -> Self {
+        let buffer = AtomicBuffer::wrap(mapped_file.as_mut_ptr(), mapped_file.capacity());
+        Self { buffer, mapped_file }
+    }
+
+    /// Publishes `version`, marking the file ready for readers. Call once the body is initialised.
+    pub fn signal_ready(&mut self, version: i32) -> Result<()> {
+        if version == MARK_FILE_NOT_READY {
+            return Err(AgronaError::IllegalArgument(format!(
+                "version must not be {}",
+                MARK_FILE_NOT_READY
+            )));
+        }
+
+        self.buffer
+            .put_ordered_u32(MARK_FILE_VERSION_OFFSET, version as u32)
+    }
+
+    pub fn version(&self) -> i32 {
+        self.buffer
+            .get_volatile_u32(MARK_FILE_VERSION_OFFSET)
+            .expect("mark file header is mapped") as i32
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.version() != MARK_FILE_NOT_READY
+    }
+
+    pub fn update_activity_timestamp(&mut self, epoch_ms: i64) -> Result<()> {
+        self.buffer
+            .put_ordered_u64(MARK_FILE_ACTIVITY_TIMESTAMP_OFFSET, epoch_ms as u64)
+    }
+
+    pub fn activity_timestamp(&self) -> i64 {
+        self.buffer
+            .get_volatile_u64(MARK_FILE_ACTIVITY_TIMESTAMP_OFFSET)
+            .expect("mark file header is mapped") as i64
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.buffer
+            .get_volatile_u64(MARK_FILE_PID_OFFSET)
+            .expect("mark file header is mapped") as u32
+    }
+
+    /// Whether the owner is ready and has updated its activity timestamp within `timeout`.
+    pub fn is_active(&self, timeout: Duration) -> bool {
+        self.is_active_at(&SystemEpochClock, timeout)
+    }
+
+    pub fn is_active_at(&self, clock: &dyn EpochClock, timeout: Duration) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+
+        let timeout_ms = i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX);
+        clock.time().saturating_sub(self.activity_timestamp()) <= timeout_ms
+    }
+
+    /// Checks the file at `path` without keeping it mapped; a missing file is not active.
+    pub fn is_file_active<P: AsRef<Path>>(path: P, clock: &dyn EpochClock, timeout: Duration) -> Result<bool> {
+        match std::fs::metadata(path.as_ref()) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error.into()),
+            Ok(metadata) if (metadata.len() as usize) < MARK_FILE_HEADER_LENGTH => Ok(false),
+            Ok(_) => Ok(Self::open(path)?.is_active_at(clock, timeout)),
+        }
+    }
+
+    /// Buffer over the whole file, header included.
+    pub fn buffer(&self) -> &AtomicBuffer {
+        &self.buffer
+    }
+
+    pub fn buffer_mut(&mut self) -> &mut AtomicBuffer {
+        &mut self.buffer
+    }
+
+    pub fn path(&self) -> &Path {
+        self.mapped_file.path()
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.mapped_file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("agrona-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_ready_and_heartbeat_seen_by_other_mapping() {
+        let path = temp_path("mark-file");
+        let clock = TestClock::new(10_000 * 1_000_000);
+        let timeout = Duration::from_millis(500);
+
+        let mut owner = MarkFile::create_at(&path, 4096, &clock, timeout).unwrap();
+        let observer = MarkFile::open(&path).unwrap();
+        assert_eq!(observer.pid(), std::process::id());
+        assert!(!observer.is_ready());
+        assert!(!observer.is_active_at(&clock, timeout));
+
+        owner.buffer_mut().put_volatile_u64(MARK_FILE_HEADER_LENGTH, 42).unwrap();
+        owner.update_activity_timestamp(clock.time()).unwrap();
+        owner.signal_ready(3).unwrap();
+
+        assert_eq!(observer.version(), 3);
+        assert_eq!(observer.buffer().get_volatile_u64(MARK_FILE_HEADER_LENGTH).unwrap(), 42);
+        assert!(observer.is_active_at(&clock, timeout));
+        assert!(MarkFile::is_file_active(&path, &clock, timeout).unwrap());
+
+        clock.advance(Duration::from_millis(501));
+        assert!(!observer.is_active_at(&clock, timeout));
+
+        owner.update_activity_timestamp(clock.time()).unwrap();
+        assert!(observer.is_active_at(&clock, timeout));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(!MarkFile::is_file_active(&path, &clock, timeout).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        let path = temp_path("short-mark-file");
+        let timeout = Duration::from_secs(1);
+        assert!(matches!(MarkFile::create(&path, 16, timeout), Err(AgronaError::IllegalArgument(_))));
+
+        MappedFile::create(&path, 16).unwrap();
+        assert!(matches!(MarkFile::open(&path), Err(AgronaError::IllegalState(_))));
+        assert!(!MarkFile::is_file_active(&path, &SystemEpochClock, timeout).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let mut mark_file = MarkFile::create(&path, MARK_FILE_HEADER_LENGTH, timeout).unwrap();
+        assert!(mark_file.signal_ready(MARK_FILE_NOT_READY).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_create_refuses_active_file() {
+        let path = temp_path("active-mark-file");
+        let clock = TestClock::new(10_000 * 1_000_000);
+        let timeout = Duration::from_millis(500);
+
+        let mut owner = MarkFile::create_at(&path, 4096, &clock, timeout).unwrap();
+        owner.update_activity_timestamp(clock.time()).unwrap();
+        owner.signal_ready(1).unwrap();
+
+        assert!(matches!(
+            MarkFile::create_at(&path, 4096, &clock, timeout),
+            Err(AgronaError::IllegalState(_))
+        ));
+        assert_eq!(owner.version(), 1);
+
+        // Once the owner stops heartbeating the file can be taken over
+        clock.advance(Duration::from_millis(501));
+        let replacement = MarkFile::create_at(&path, 4096, &clock, timeout).unwrap();
+        assert!(!replacement.is_ready());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use crate::ringbuffer::record_descriptor::{
+    check_type_id, encoded_msg_offset, length_offset, type_offset, ALIGNMENT, HEADER_LENGTH, PADDING_MSG_TYPE_ID,
+};
+use crate::ringbuffer::ring_buffer_descriptor::{
+    check_capacity, HEAD_CACHE_POSITION_OFFSET, HEAD_POSITION_OFFSET, TAIL_POSITION_OFFSET,
+};
+use crate::ringbuffer::{check_msg_length, read_records, RingBuffer};
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::align;
+use agrona_core::error::Result;
+
+/// [`RingBuffer`] for many concurrent producers, in any number of threads or processes, and a
+/// single consumer. Producers claim space by a CAS on the tail position.
+pub struct ManyToOneRingBuffer {
+    buffer: SharedBuffer,
+    capacity: usize,
+    max_msg_length: usize,
+    tail_position_index: usize,
+    head_cache_position_index: usize,
+    head_position_index: usize,
+}
+
+impl ManyToOneRingBuffer {
+    /// Attaches to `buffer`, validating its layout but leaving its contents untouched.
+    pub fn new(buffer: impl Into<SharedBuffer>) -> Result<Self> {
+        let buffer = buffer.into();
+        let capacity = check_capacity(&buffer)?;
+
+        Ok(Self {
+            buffer,
+            capacity,
+            max_msg_length: capacity >> 3,
+            tail_position_index: capacity + TAIL_POSITION_OFFSET,
+            head_cache_position_index: capacity + HEAD_CACHE_POSITION_OFFSET,
+            head_position_index: capacity + HEAD_POSITION_OFFSET,
+        })
+    }
+
+    /// Reserves `required` bytes, writing a padding record if the claim wraps, and returns the
+    /// index of the claimed record or `None` when there is not enough capacity.
+    fn claim_capacity(&self, required: i64) -> Option<usize> {
+        let buffer = &self.buffer;
+        let capacity = self.capacity as i64;
+        let mask = capacity - 1;
+
+        let mut head = buffer.get_i64_volatile(self.head_cache_position_index);
+        let (tail, padding) = loop {
+            let tail = buffer.get_i64_volatile(self.tail_position_index);
+            if required > capacity - (tail - head) {
+                head = buffer.get_i64_volatile(self.head_position_index);
+                if required > capacity - (tail - head) {
+                    return None;
+                }
+                buffer.put_i64_ordered(self.head_cache_position_index, head);
+            }
+
+            let mut padding = 0;
+            let to_buffer_end = capacity - (tail & mask);
+            if required > to_buffer_end {
+                let mut head_index = head & mask;
+                if required > head_index {
+                    head = buffer.get_i64_volatile(self.head_position_index);
+                    head_index = head & mask;
+                    if required > head_index {
+                        return None;
+                    }
+                    buffer.put_i64_ordered(self.head_cache_position_index, head);
+                }
+                padding = to_buffer_end;
+            }
+
+            if buffer.compare_and_set_i64(self.tail_position_index, tail, tail + required + padding) {
+                break (tail, padding);
+            }
+        };
+
+        let tail_index = (tail & mask) as usize;
+        if padding != 0 {
+            buffer.put_i32(type_offset(tail_index), PADDING_MSG_TYPE_ID);
+            buffer.put_i32_ordered(length_offset(tail_index), padding as i32);
+            return Some(0);
+        }
+
+        Some(tail_index)
+    }
+}
+
+impl RingBuffer for ManyToOneRingBuffer {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    fn max_msg_length(&self) -> usize {
+        self.max_msg_length
+    }
+
+    fn write(&self, msg_type_id: i32, src: &[u8]) -> Result<bool> {
+        check_type_id(msg_type_id)?;
+        check_msg_length(src.len(), self.max_msg_length)?;
+
+        let record_length = src.len() + HEADER_LENGTH;
+        let Some(record_index) = self.claim_capacity(align(record_length, ALIGNMENT) as i64) else {
+            return Ok(false);
+        };
+
+        let buffer = &self.buffer;
+        buffer.put_i32_ordered(length_offset(record_index), -(record_length as i32));
+        buffer.put_bytes(encoded_msg_offset(record_index), src);
+        buffer.put_i32(type_offset(record_index), msg_type_id);
+        buffer.put_i32_ordered(length_offset(record_index), record_length as i32);
+
+        Ok(true)
+    }
+
+    fn read<F>(&self, handler: F, message_count_limit: usize) -> usize
+    where
+        F: FnMut(i32, &[u8]),
+    {
+        read_records(&self.buffer, self.capacity, handler, message_count_limit)
+    }
+
+    #[inline]
+    fn buffer(&self) -> &SharedBuffer {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringbuffer::ring_buffer_descriptor::TRAILER_LENGTH;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_producers() {
+        const PRODUCERS: u32 = 4;
+        const MESSAGES: u32 = 20_000;
+
+        let ring_buffer = Arc::new(ManyToOneRingBuffer::new(SharedBuffer::new(4096 + TRAILER_LENGTH).unwrap()).unwrap());
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let ring_buffer = Arc::clone(&ring_buffer);
+                thread::spawn(move || {
+                    for sequence in 0..MESSAGES {
+                        let mut message = [0u8; 8];
+                        message[..4].copy_from_slice(&producer.to_le_bytes());
+                        message[4..].copy_from_slice(&sequence.to_le_bytes());
+                        while !ring_buffer.write(1 + producer as i32, &message).unwrap() {
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut next_sequence = [0u32; PRODUCERS as usize];
+        let mut received = 0;
+        while received < PRODUCERS * MESSAGES {
+            received += ring_buffer.read(
+                |msg_type_id, payload| {
+                    let producer = u32::from_le_bytes(payload[..4].try_into().unwrap());
+                    let sequence = u32::from_le_bytes(payload[4..].try_into().unwrap());
+                    assert_eq!(msg_type_id, 1 + producer as i32);
+                    assert_eq!(sequence, next_sequence[producer as usize]);
+                    next_sequence[producer as usize] += 1;
+                },
+                100,
+            ) as u32;
+        }
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert_eq!(ring_buffer.size(), 0);
+        assert!(next_sequence.iter().all(|&sequence| sequence == MESSAGES));
+    }
+
+    #[test]
+    fn test_wraps_with_padding() {
+        let ring_buffer = ManyToOneRingBuffer::new(SharedBuffer::new(256 + TRAILER_LENGTH).unwrap()).unwrap();
+
+        assert!(ring_buffer.write(1, &[1; 32]).unwrap());
+        assert!(ring_buffer.write(1, &[2; 32]).unwrap());
+        assert!(ring_buffer.write(1, &[3; 32]).unwrap());
+        assert_eq!(ring_buffer.read(|_, _| {}, usize::MAX), 3);
+
+        // The three 40 byte records consumed 120 bytes, which leaves room for four 32 byte records
+        // (24 byte payloads) before the end, so the fifth pads the last 8 bytes and wraps to the
+        // start
+        let mut written = 0;
+        while ring_buffer.write(2, &[written as u8; 24]).unwrap() {
+            written += 1;
+        }
+        assert_eq!(written, 7);
+        assert_eq!(ring_buffer.producer_position(), 120 + 7 * 32 + 8);
+
+        let mut payloads = Vec::new();
+        while payloads.len() < written {
+            ring_buffer.read(|_, payload| payloads.push(payload[0]), usize::MAX);
+        }
+        assert_eq!(payloads, (0..7).collect::<Vec<u8>>());
+    }
+}
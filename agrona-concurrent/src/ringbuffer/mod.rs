@@ -0,0 +1,142 @@
+pub mod many_to_one_ring_buffer;
+pub mod one_to_one_ring_buffer;
+pub mod record_descriptor;
+pub mod ring_buffer_descriptor;
+
+pub use many_to_one_ring_buffer::*;
+pub use one_to_one_ring_buffer::*;
+
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::align;
+use agrona_core::error::{AgronaError, Result};
+use record_descriptor::{encoded_msg_offset, length_offset, type_offset, ALIGNMENT, HEADER_LENGTH, PADDING_MSG_TYPE_ID};
+use ring_buffer_descriptor::{
+    CONSUMER_HEARTBEAT_OFFSET, CORRELATION_COUNTER_OFFSET, HEAD_POSITION_OFFSET, TAIL_POSITION_OFFSET,
+};
+
+/// Ring buffer of variable length messages over a [`SharedBuffer`], usable between processes.
+///
+/// The buffer is a power-of-two record region followed by a
+/// [`ring_buffer_descriptor::TRAILER_LENGTH`] trailer and must be zeroed when first created.
+/// Only one consumer may read at a time.
+pub trait RingBuffer {
+    /// Capacity of the record region in bytes.
+    fn capacity(&self) -> usize;
+
+    fn max_msg_length(&self) -> usize;
+
+    /// Writes a message, returning `false` when there is not enough free capacity.
+    fn write(&self, msg_type_id: i32, src: &[u8]) -> Result<bool>;
+
+    /// Reads up to `message_count_limit` messages, passing each type id and payload to
+    /// `handler`, and returns how many were read.
+    fn read<F>(&self, handler: F, message_count_limit: usize) -> usize
+    where
+        F: FnMut(i32, &[u8]);
+
+    fn buffer(&self) -> &SharedBuffer;
+
+    /// Unique id for correlating requests with responses across users of this buffer.
+    fn next_correlation_id(&self) -> i64 {
+        self.buffer()
+            .get_and_add_i64(self.capacity() + CORRELATION_COUNTER_OFFSET, 1)
+    }
+
+    fn producer_position(&self) -> i64 {
+        self.buffer()
+            .get_i64_volatile(self.capacity() + TAIL_POSITION_OFFSET)
+    }
+
+    fn consumer_position(&self) -> i64 {
+        self.buffer()
+            .get_i64_volatile(self.capacity() + HEAD_POSITION_OFFSET)
+    }
+
+    /// Bytes currently occupied by records, including padding.
+    fn size(&self) -> usize {
+        let buffer = self.buffer();
+        let head_index = self.capacity() + HEAD_POSITION_OFFSET;
+        let tail_index = self.capacity() + TAIL_POSITION_OFFSET;
+
+        let mut head_after = buffer.get_i64_volatile(head_index);
+        loop {
+            let head_before = head_after;
+            let tail = buffer.get_i64_volatile(tail_index);
+            head_after = buffer.get_i64_volatile(head_index);
+
+            if head_before == head_after {
+                return (tail - head_after).clamp(0, self.capacity() as i64) as usize;
+            }
+        }
+    }
+
+    fn set_consumer_heartbeat_time(&self, time: i64) {
+        self.buffer()
+            .put_i64_ordered(self.capacity() + CONSUMER_HEARTBEAT_OFFSET, time);
+    }
+
+    fn consumer_heartbeat_time(&self) -> i64 {
+        self.buffer()
+            .get_i64_volatile(self.capacity() + CONSUMER_HEARTBEAT_OFFSET)
+    }
+}
+
+fn check_msg_length(length: usize, max_msg_length: usize) -> Result<()> {
+    if length > max_msg_length {
+        return Err(AgronaError::IllegalArgument(format!(
+            "encoded message exceeds max message length of {}, length={}",
+            max_msg_length, length
+        )));
+    }
+
+    Ok(())
+}
+
+/// Consumer side shared by all ring buffers: reads the contiguous records from the head, then
+/// zeroes them so producers find clean memory when they wrap around.
+fn read_records<F>(buffer: &SharedBuffer, capacity: usize, mut handler: F, message_count_limit: usize) -> usize
+where
+    F: FnMut(i32, &[u8]),
+{
+    let head_position_index = capacity + HEAD_POSITION_OFFSET;
+    let head = buffer.get_i64(head_position_index);
+    let head_index = head as usize & (capacity - 1);
+    let contiguous_block_length = capacity - head_index;
+
+    let mut bytes_read = 0;
+    let mut messages_read = 0;
+    while bytes_read < contiguous_block_length && messages_read < message_count_limit {
+        let record_index = head_index + bytes_read;
+        let record_length = buffer.get_i32_volatile(length_offset(record_index));
+        if record_length <= 0 {
+            break;
+        }
+
+        // The length comes from memory other processes write, so a corrupt one stops the read
+        // rather than being trusted to index the buffer
+        let aligned_length = align(record_length as usize, ALIGNMENT);
+        if (record_length as usize) < HEADER_LENGTH || aligned_length > contiguous_block_length - bytes_read {
+            break;
+        }
+
+        bytes_read += aligned_length;
+
+        let msg_type_id = buffer.get_i32(type_offset(record_index));
+        if msg_type_id == PADDING_MSG_TYPE_ID {
+            continue;
+        }
+
+        handler(
+            msg_type_id,
+            buffer.slice(encoded_msg_offset(record_index), record_length as usize - HEADER_LENGTH),
+        );
+        messages_read += 1;
+    }
+
+    if bytes_read > 0 {
+        buffer.set_memory(head_index, bytes_read, 0);
+        buffer.put_i64_ordered(head_position_index, head + bytes_read as i64);
+    }
+
+    messages_read
+}
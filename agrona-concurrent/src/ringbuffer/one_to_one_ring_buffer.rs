@@ -0,0 +1,214 @@
+use crate::ringbuffer::record_descriptor::{
+    check_type_id, encoded_msg_offset, length_offset, type_offset, ALIGNMENT, HEADER_LENGTH, PADDING_MSG_TYPE_ID,
+};
+use crate::ringbuffer::ring_buffer_descriptor::{
+    check_capacity, HEAD_CACHE_POSITION_OFFSET, HEAD_POSITION_OFFSET, TAIL_POSITION_OFFSET,
+};
+use crate::ringbuffer::{check_msg_length, read_records, RingBuffer};
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::align;
+use agrona_core::error::Result;
+
+/// [`RingBuffer`] for a single producer and a single consumer, each of which may be in a
+/// different process.
+pub struct OneToOneRingBuffer {
+    buffer: SharedBuffer,
+    capacity: usize,
+    max_msg_length: usize,
+    tail_position_index: usize,
+    head_cache_position_index: usize,
+    head_position_index: usize,
+}
+
+impl OneToOneRingBuffer {
+    /// Attaches to `buffer`, validating its layout but leaving its contents untouched.
+    pub fn new(buffer: impl Into<SharedBuffer>) -> Result<Self> {
+        let buffer = buffer.into();
+        let capacity = check_capacity(&buffer)?;
+
+        Ok(Self {
+            buffer,
+            capacity,
+            max_msg_length: capacity >> 3,
+            tail_position_index: capacity + TAIL_POSITION_OFFSET,
+            head_cache_position_index: capacity + HEAD_CACHE_POSITION_OFFSET,
+            head_position_index: capacity + HEAD_POSITION_OFFSET,
+        })
+    }
+}
+
+impl RingBuffer for OneToOneRingBuffer {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    fn max_msg_length(&self) -> usize {
+        self.max_msg_length
+    }
+
+    fn write(&self, msg_type_id: i32, src: &[u8]) -> Result<bool> {
+        check_type_id(msg_type_id)?;
+        check_msg_length(src.len(), self.max_msg_length)?;
+
+        let buffer = &self.buffer;
+        let record_length = src.len() + HEADER_LENGTH;
+        let required = align(record_length, ALIGNMENT) as i64;
+        let capacity = self.capacity as i64;
+        let mask = capacity - 1;
+
+        let mut head = buffer.get_i64(self.head_cache_position_index);
+        let tail = buffer.get_i64(self.tail_position_index);
+        if required > capacity - (tail - head) {
+            head = buffer.get_i64_volatile(self.head_position_index);
+            if required > capacity - (tail - head) {
+                return Ok(false);
+            }
+            buffer.put_i64(self.head_cache_position_index, head);
+        }
+
+        let mut padding = 0;
+        let mut record_index = (tail & mask) as usize;
+        let to_buffer_end = capacity - record_index as i64;
+        if required > to_buffer_end {
+            let mut head_index = head & mask;
+            if required > head_index {
+                head = buffer.get_i64_volatile(self.head_position_index);
+                head_index = head & mask;
+                if required > head_index {
+                    return Ok(false);
+                }
+                buffer.put_i64(self.head_cache_position_index, head);
+            }
+            padding = to_buffer_end;
+        }
+
+        if padding != 0 {
+            buffer.put_i32(type_offset(record_index), PADDING_MSG_TYPE_ID);
+            buffer.put_i32_ordered(length_offset(record_index), padding as i32);
+            record_index = 0;
+        }
+
+        buffer.put_bytes(encoded_msg_offset(record_index), src);
+        buffer.put_i32(type_offset(record_index), msg_type_id);
+        buffer.put_i32_ordered(length_offset(record_index), record_length as i32);
+        buffer.put_i64_ordered(self.tail_position_index, tail + required + padding);
+
+        Ok(true)
+    }
+
+    fn read<F>(&self, handler: F, message_count_limit: usize) -> usize
+    where
+        F: FnMut(i32, &[u8]),
+    {
+        read_records(&self.buffer, self.capacity, handler, message_count_limit)
+    }
+
+    #[inline]
+    fn buffer(&self) -> &SharedBuffer {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringbuffer::ring_buffer_descriptor::TRAILER_LENGTH;
+    use agrona_core::error::AgronaError;
+
+    const CAPACITY: usize = 1024;
+
+    fn ring_buffer() -> OneToOneRingBuffer {
+        OneToOneRingBuffer::new(SharedBuffer::new(CAPACITY + TRAILER_LENGTH).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_validates_layout() {
+        let too_short = SharedBuffer::new(TRAILER_LENGTH - 8).unwrap();
+        assert!(matches!(OneToOneRingBuffer::new(too_short), Err(AgronaError::InvalidLayout(_))));
+
+        let not_power_of_two = SharedBuffer::new(1000 + TRAILER_LENGTH).unwrap();
+        assert!(matches!(
+            OneToOneRingBuffer::new(not_power_of_two),
+            Err(AgronaError::InvalidCapacity { capacity: 1000 })
+        ));
+
+        let ring_buffer = ring_buffer();
+        assert_eq!(ring_buffer.capacity(), CAPACITY);
+        assert_eq!(ring_buffer.max_msg_length(), CAPACITY / 8);
+        assert!(matches!(ring_buffer.write(0, b"x"), Err(AgronaError::IllegalArgument(_))));
+        assert!(matches!(
+            ring_buffer.write(1, &[0; CAPACITY / 8 + 1]),
+            Err(AgronaError::IllegalArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_and_read_with_wrap() {
+        let ring_buffer = ring_buffer();
+        let mut received = Vec::new();
+
+        for round in 0..100u32 {
+            let message = round.to_le_bytes().repeat(1 + round as usize % 25);
+            assert!(ring_buffer.write(7, &message).unwrap());
+            assert!(ring_buffer.size() > 0);
+
+            // A read stops at the end of the buffer, so a wrapped message takes a second read
+            let mut read = 0;
+            for _ in 0..2 {
+                read += ring_buffer.read(
+                    |msg_type_id, payload| {
+                        assert_eq!(msg_type_id, 7);
+                        received.push(payload.to_vec());
+                    },
+                    10,
+                );
+            }
+            assert_eq!(read, 1);
+            assert_eq!(received.last().unwrap(), &message);
+        }
+
+        assert_eq!(ring_buffer.size(), 0);
+        assert_eq!(ring_buffer.producer_position(), ring_buffer.consumer_position());
+        assert!(ring_buffer.producer_position() > CAPACITY as i64);
+    }
+
+    #[test]
+    fn test_reports_full_and_limits_reads() {
+        let ring_buffer = ring_buffer();
+        let message = [1u8; 120];
+
+        let mut written = 0;
+        while ring_buffer.write(3, &message).unwrap() {
+            written += 1;
+        }
+        assert_eq!(written, CAPACITY / 128);
+        assert_eq!(ring_buffer.size(), CAPACITY);
+
+        assert_eq!(ring_buffer.read(|_, _| {}, 3), 3);
+        assert_eq!(ring_buffer.read(|_, _| {}, usize::MAX), written - 3);
+        assert!(ring_buffer.write(3, &message).unwrap());
+
+        assert_eq!(ring_buffer.next_correlation_id(), 0);
+        assert_eq!(ring_buffer.next_correlation_id(), 1);
+        ring_buffer.set_consumer_heartbeat_time(99);
+        assert_eq!(ring_buffer.consumer_heartbeat_time(), 99);
+    }
+
+    #[test]
+    fn test_corrupt_record_length_stops_read() {
+        use crate::ringbuffer::record_descriptor::length_offset;
+
+        let ring_buffer = ring_buffer();
+        let mut handled = 0;
+
+        // Shorter than a header, then longer than the space left before the end of the buffer
+        for corrupt_length in [5, CAPACITY as i32 + 8] {
+            ring_buffer.buffer.put_i32_ordered(length_offset(0), corrupt_length);
+            assert_eq!(ring_buffer.read(|_, _| handled += 1, usize::MAX), 0);
+            assert_eq!(ring_buffer.consumer_position(), 0);
+        }
+        assert_eq!(handled, 0);
+    }
+}
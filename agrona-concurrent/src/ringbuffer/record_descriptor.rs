@@ -0,0 +1,37 @@
+//! Layout of a ring buffer record: a 4-byte length, which includes the header, followed by a
+//! 4-byte message type id and the encoded message, with records aligned to [`ALIGNMENT`].
+//!
+//! A zero length marks the end of the written records and a negative length a record still being
+//! written by a many-to-one producer.
+
+use agrona_core::error::{AgronaError, Result};
+
+pub const HEADER_LENGTH: usize = 8;
+pub const ALIGNMENT: usize = HEADER_LENGTH;
+pub const PADDING_MSG_TYPE_ID: i32 = -1;
+
+#[inline]
+pub const fn length_offset(record_offset: usize) -> usize {
+    record_offset
+}
+
+#[inline]
+pub const fn type_offset(record_offset: usize) -> usize {
+    record_offset + 4
+}
+
+#[inline]
+pub const fn encoded_msg_offset(record_offset: usize) -> usize {
+    record_offset + HEADER_LENGTH
+}
+
+pub fn check_type_id(msg_type_id: i32) -> Result<()> {
+    if msg_type_id < 1 {
+        return Err(AgronaError::IllegalArgument(format!(
+            "message type id must be greater than zero: {}",
+            msg_type_id
+        )));
+    }
+
+    Ok(())
+}
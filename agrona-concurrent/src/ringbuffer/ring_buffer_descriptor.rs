@@ -0,0 +1,34 @@
+//! Layout of the trailer that follows the records of a ring buffer. Each counter sits on its own
+//! pair of cache lines to avoid false sharing between producers and the consumer.
+
+use crate::shared_buffer::SharedBuffer;
+use agrona_core::bit_util::{is_power_of_two, CACHE_LINE_LENGTH, SIZE_OF_I64};
+use agrona_core::error::{AgronaError, Result};
+
+pub const TAIL_POSITION_OFFSET: usize = CACHE_LINE_LENGTH * 2;
+pub const HEAD_CACHE_POSITION_OFFSET: usize = CACHE_LINE_LENGTH * 4;
+pub const HEAD_POSITION_OFFSET: usize = CACHE_LINE_LENGTH * 6;
+pub const CORRELATION_COUNTER_OFFSET: usize = CACHE_LINE_LENGTH * 8;
+pub const CONSUMER_HEARTBEAT_OFFSET: usize = CACHE_LINE_LENGTH * 10;
+pub const TRAILER_LENGTH: usize = CACHE_LINE_LENGTH * 12;
+
+/// Validates that `buffer` is a power-of-two record region followed by the trailer and returns
+/// the capacity of the record region.
+pub fn check_capacity(buffer: &SharedBuffer) -> Result<usize> {
+    let length = buffer.capacity();
+    if length < TRAILER_LENGTH {
+        return Err(AgronaError::InvalidLayout(format!(
+            "ring buffer length {} is less than trailer length {}",
+            length, TRAILER_LENGTH
+        )));
+    }
+
+    let capacity = length - TRAILER_LENGTH;
+    if !is_power_of_two(capacity as u64) || capacity > i32::MAX as usize {
+        return Err(AgronaError::InvalidCapacity { capacity });
+    }
+
+    buffer.check_aligned(capacity, TRAILER_LENGTH, SIZE_OF_I64)?;
+
+    Ok(capacity)
+}
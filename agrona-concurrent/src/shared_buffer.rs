@@ -0,0 +1,231 @@
+use crate::atomic_buffer::AtomicBuffer;
+use agrona_core::buffer::{DirectBuffer, MutableBuffer};
+use agrona_core::error::{AgronaError, Result};
+use agrona_core::MappedFile;
+use core::ptr::{self, NonNull};
+use std::path::Path;
+use std::sync::atomic::{AtomicI32, AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// Owner of the memory, held only to keep it alive for as long as any handle exists.
+#[allow(dead_code)]
+enum Backing {
+    Atomic(AtomicBuffer),
+    Mapped(MappedFile),
+}
+
+/// Memory shared between threads or processes that ring and broadcast buffers access through
+/// `&self`, so each side can hold its own handle over the same bytes.
+///
+/// Clones are handles onto the same memory, which stays alive until the last one is dropped.
+#[derive(Clone)]
+pub struct SharedBuffer {
+    ptr: NonNull<u8>,
+    capacity: usize,
+    _backing: Arc<Backing>,
+}
+
+unsafe impl Send for SharedBuffer {}
+unsafe impl Sync for SharedBuffer {}
+
+impl SharedBuffer {
+    /// Allocates `capacity` zeroed bytes on the heap.
+    pub fn new(capacity: usize) -> Result<Self> {
+        let mut buffer = AtomicBuffer::new(capacity)?;
+        buffer.set_memory(0, capacity, 0)?;
+        Ok(Self::from(buffer))
+    }
+
+    /// Creates (or truncates) a zeroed file of `length` bytes at `path` and maps it.
+    pub fn create_file<P: AsRef<Path>>(path: P, length: usize) -> Result<Self> {
+        Ok(Self::from(MappedFile::create(path, length)?))
+    }
+
+    /// Maps an existing file, such as one created by another process.
+    pub fn open_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::from(MappedFile::open(path)?))
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Errors unless `[index, index + length)` is in bounds and `index` is aligned to `alignment`.
+    pub(crate) fn check_aligned(&self, index: usize, length: usize, alignment: usize) -> Result<()> {
+        if index.checked_add(length).is_none_or(|end| end > self.capacity) {
+            return Err(AgronaError::IndexOutOfBounds {
+                index,
+                length,
+                capacity: self.capacity,
+            });
+        }
+
+        if !(self.ptr.as_ptr() as usize + index).is_multiple_of(alignment) {
+            return Err(AgronaError::InvalidLayout(format!(
+                "offset {} is not aligned to {} bytes",
+                index, alignment
+            )));
+        }
+
+        Ok(())
+    }
+
+    // Callers validate the layout once on construction, so the accessors below only
+    // debug-assert their bounds.
+
+    #[inline(always)]
+    fn atomic_i32(&self, index: usize) -> &AtomicI32 {
+        debug_assert!(index + 4 <= self.capacity);
+        unsafe { &*(self.ptr.as_ptr().add(index) as *const AtomicI32) }
+    }
+
+    #[inline(always)]
+    fn atomic_i64(&self, index: usize) -> &AtomicI64 {
+        debug_assert!(index + 8 <= self.capacity);
+        unsafe { &*(self.ptr.as_ptr().add(index) as *const AtomicI64) }
+    }
+
+    #[inline]
+    pub(crate) fn get_i32(&self, index: usize) -> i32 {
+        self.atomic_i32(index).load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn get_i32_volatile(&self, index: usize) -> i32 {
+        self.atomic_i32(index).load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn put_i32(&self, index: usize, value: i32) {
+        self.atomic_i32(index).store(value, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn put_i32_ordered(&self, index: usize, value: i32) {
+        self.atomic_i32(index).store(value, Ordering::Release);
+    }
+
+    #[inline]
+    pub(crate) fn get_i64(&self, index: usize) -> i64 {
+        self.atomic_i64(index).load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn get_i64_volatile(&self, index: usize) -> i64 {
+        self.atomic_i64(index).load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn put_i64(&self, index: usize, value: i64) {
+        self.atomic_i64(index).store(value, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn put_i64_ordered(&self, index: usize, value: i64) {
+        self.atomic_i64(index).store(value, Ordering::Release);
+    }
+
+    #[inline]
+    pub(crate) fn compare_and_set_i64(&self, index: usize, expected: i64, update: i64) -> bool {
+        self.atomic_i64(index)
+            .compare_exchange(expected, update, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    #[inline]
+    pub(crate) fn get_and_add_i64(&self, index: usize, delta: i64) -> i64 {
+        self.atomic_i64(index).fetch_add(delta, Ordering::AcqRel)
+    }
+
+    #[inline]
+    pub(crate) fn put_bytes(&self, index: usize, src: &[u8]) {
+        self.check_bounds(index, src.len());
+        unsafe { ptr::copy_nonoverlapping(src.as_ptr(), self.ptr.as_ptr().add(index), src.len()) };
+    }
+
+    #[inline]
+    pub(crate) fn get_bytes(&self, index: usize, dst: &mut [u8]) {
+        self.check_bounds(index, dst.len());
+        unsafe { ptr::copy_nonoverlapping(self.ptr.as_ptr().add(index), dst.as_mut_ptr(), dst.len()) };
+    }
+
+    #[inline]
+    pub(crate) fn set_memory(&self, index: usize, length: usize, value: u8) {
+        self.check_bounds(index, length);
+        unsafe { ptr::write_bytes(self.ptr.as_ptr().add(index), value, length) };
+    }
+
+    /// Slice over bytes the caller knows no other party writes while it is borrowed.
+    #[inline]
+    pub(crate) fn slice(&self, index: usize, length: usize) -> &[u8] {
+        self.check_bounds(index, length);
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr().add(index), length) }
+    }
+
+    /// Range checks kept in release builds, since lengths may come from memory another process
+    /// writes.
+    #[inline]
+    fn check_bounds(&self, index: usize, length: usize) {
+        assert!(
+            index.checked_add(length).is_some_and(|end| end <= self.capacity),
+            "index={} length={} out of bounds for capacity={}",
+            index,
+            length,
+            self.capacity
+        );
+    }
+}
+
+impl From<AtomicBuffer> for SharedBuffer {
+    fn from(mut buffer: AtomicBuffer) -> Self {
+        let capacity = buffer.capacity();
+        let ptr = NonNull::new(buffer.as_mut_ptr()).expect("buffer pointer is null");
+        Self {
+            ptr,
+            capacity,
+            _backing: Arc::new(Backing::Atomic(buffer)),
+        }
+    }
+}
+
+impl From<MappedFile> for SharedBuffer {
+    fn from(mut mapped_file: MappedFile) -> Self {
+        let capacity = mapped_file.capacity();
+        let ptr = NonNull::new(mapped_file.as_mut_ptr()).expect("mapping pointer is null");
+        Self {
+            ptr,
+            capacity,
+            _backing: Arc::new(Backing::Mapped(mapped_file)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_access_and_alignment() {
+        let buffer = SharedBuffer::new(64).unwrap();
+        assert_eq!(buffer.get_i64(8), 0);
+
+        buffer.clone().put_i64_ordered(8, 5);
+        assert!(buffer.compare_and_set_i64(8, 5, 7));
+        assert!(!buffer.compare_and_set_i64(8, 5, 9));
+        assert_eq!(buffer.get_and_add_i64(8, 3), 7);
+        assert_eq!(buffer.get_i64_volatile(8), 10);
+
+        buffer.put_bytes(16, b"abc");
+        assert_eq!(buffer.slice(16, 3), b"abc");
+
+        assert!(buffer.check_aligned(8, 8, 8).is_ok());
+        assert!(matches!(buffer.check_aligned(4, 8, 8), Err(AgronaError::InvalidLayout(_))));
+        assert!(matches!(buffer.check_aligned(60, 8, 8), Err(AgronaError::IndexOutOfBounds { .. })));
+    }
+}
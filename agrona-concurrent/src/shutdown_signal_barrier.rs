@@ -0,0 +1,221 @@
+use agrona_core::error::{AgronaError, Result};
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, Weak};
+use std::thread;
+use std::time::Duration;
+
+pub const SHUTDOWN_SIGNALS: [c_int; 2] = [libc::SIGINT, libc::SIGTERM];
+
+type ShutdownHook = Box<dyn FnOnce() + Send>;
+
+static INSTALLED: OnceLock<Result<()>> = OnceLock::new();
+static SIGNAL_PIPE_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+static BARRIERS: Mutex<Vec<Weak<BarrierState>>> = Mutex::new(Vec::new());
+
+struct BarrierState {
+    signalled: Mutex<bool>,
+    condvar: Condvar,
+    hooks: Mutex<Vec<ShutdownHook>>,
+}
+
+impl BarrierState {
+    fn signal(&self) {
+        *self.signalled.lock().unwrap() = true;
+        self.condvar.notify_all();
+    }
+}
+
+/// Blocks the main thread until SIGINT/SIGTERM arrives or the barrier is signalled in code.
+///
+/// The signal handler only writes to a pipe, which is async-signal-safe; a watcher thread then
+/// releases every live barrier.
+pub struct ShutdownSignalBarrier {
+    state: Arc<BarrierState>,
+}
+
+impl ShutdownSignalBarrier {
+    pub fn new() -> Result<Self> {
+        install_signal_handlers()?;
+
+        let state = Arc::new(BarrierState {
+            signalled: Mutex::new(false),
+            condvar: Condvar::new(),
+            hooks: Mutex::new(Vec::new()),
+        });
+
+        let mut barriers = BARRIERS.lock().unwrap();
+        barriers.retain(|barrier| barrier.strong_count() > 0);
+        barriers.push(Arc::downgrade(&state));
+
+        Ok(Self { state })
+    }
+
+    /// Registers a hook to run, in registration order, on the thread returning from `await_shutdown`.
+    pub fn on_shutdown<F>(&self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.state.hooks.lock().unwrap().push(Box::new(hook));
+    }
+
+    pub fn signal(&self) {
+        self.state.signal();
+    }
+
+    /// Releases every barrier in the process, as a shutdown signal would.
+    pub fn signal_all() {
+        signal_all_barriers();
+    }
+
+    pub fn is_signalled(&self) -> bool {
+        *self.state.signalled.lock().unwrap()
+    }
+
+    pub fn await_shutdown(&self) {
+        let mut signalled = self.state.signalled.lock().unwrap();
+        while !*signalled {
+            signalled = self.state.condvar.wait(signalled).unwrap();
+        }
+        drop(signalled);
+
+        self.run_hooks();
+    }
+
+    /// Waits up to `timeout`, returning whether the barrier was signalled.
+    pub fn await_shutdown_timeout(&self, timeout: Duration) -> bool {
+        let signalled = self.state.signalled.lock().unwrap();
+        let (signalled, _) = self
+            .state
+            .condvar
+            .wait_timeout_while(signalled, timeout, |signalled| !*signalled)
+            .unwrap();
+        let is_signalled = *signalled;
+        drop(signalled);
+
+        if is_signalled {
+            self.run_hooks();
+        }
+        is_signalled
+    }
+
+    fn run_hooks(&self) {
+        let hooks = std::mem::take(&mut *self.state.hooks.lock().unwrap());
+        for hook in hooks {
+            hook();
+        }
+    }
+}
+
+fn signal_all_barriers() {
+    let barriers: Vec<Arc<BarrierState>> = BARRIERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+
+    for barrier in barriers {
+        barrier.signal();
+    }
+}
+
+extern "C" fn on_signal(_signal: c_int) {
+    let fd = SIGNAL_PIPE_WRITE_FD.load(Ordering::Relaxed);
+    if fd >= 0 {
+        let byte = 1u8;
+        unsafe {
+            libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+        }
+    }
+}
+
+fn install_signal_handlers() -> Result<()> {
+    INSTALLED.get_or_init(install).clone()
+}
+
+fn install() -> Result<()> {
+    let mut fds = [0 as c_int; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    let [read_fd, write_fd] = fds;
+
+    thread::Builder::new()
+        .name("shutdown-signal".to_string())
+        .spawn(move || watch_signal_pipe(read_fd))?;
+    SIGNAL_PIPE_WRITE_FD.store(write_fd, Ordering::Relaxed);
+
+    for signal in SHUTDOWN_SIGNALS {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_signal as extern "C" fn(c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            if libc::sigaction(signal, &action, std::ptr::null_mut()) != 0 {
+                return Err(AgronaError::Io(format!(
+                    "unable to install handler for signal {}: {}",
+                    signal,
+                    std::io::Error::last_os_error()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn watch_signal_pipe(read_fd: c_int) {
+    let mut byte = 0u8;
+    loop {
+        let read = unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+        if read > 0 {
+            signal_all_barriers();
+        } else if read == 0 || std::io::Error::last_os_error().kind() != std::io::ErrorKind::Interrupted {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_programmatic_signal_runs_hooks() {
+        let barrier = Arc::new(ShutdownSignalBarrier::new().unwrap());
+        let hook_calls = Arc::new(AtomicUsize::new(0));
+        for _ in 0..2 {
+            let hook_calls = Arc::clone(&hook_calls);
+            barrier.on_shutdown(move || {
+                hook_calls.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        let waiter = {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || barrier.await_shutdown())
+        };
+
+        barrier.signal();
+        waiter.join().unwrap();
+
+        assert!(barrier.is_signalled());
+        assert_eq!(hook_calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_process_signals_release_all_barriers() {
+        for signal in SHUTDOWN_SIGNALS {
+            let first = ShutdownSignalBarrier::new().unwrap();
+            let second = ShutdownSignalBarrier::new().unwrap();
+
+            assert_eq!(unsafe { libc::kill(libc::getpid(), signal) }, 0);
+
+            assert!(first.await_shutdown_timeout(Duration::from_secs(5)));
+            assert!(second.await_shutdown_timeout(Duration::from_secs(5)));
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use crate::clock::{EpochClock, SystemEpochClock};
+use agrona_core::error::{AgronaError, Result};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub const DEFAULT_NODE_ID_BITS: u32 = 10;
+pub const DEFAULT_SEQUENCE_BITS: u32 = 12;
+pub const MAX_NODE_ID_AND_SEQUENCE_BITS: u32 = 22;
+pub const UNUSED_BITS: u32 = 1;
+
+/// What [`SnowflakeIdGenerator::next_id`] does once every sequence in a millisecond is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceRolloverPolicy {
+    /// Spin until the clock moves on to a later millisecond.
+    SpinWait,
+    /// Fail with [`AgronaError::IllegalState`] so the caller can back off.
+    Error,
+}
+
+/// Lock-free generator of cluster unique 64-bit ids in the Twitter Snowflake layout.
+///
+/// From the most significant bit an id is one unused sign bit, a millisecond timestamp relative
+/// to the timestamp offset, the node id and a sequence within the millisecond. If the clock goes
+/// backwards the last timestamp keeps being used, so ids stay unique and increasing.
+pub struct SnowflakeIdGenerator {
+    node_id_and_sequence_bits: u32,
+    sequence_bits: u32,
+    max_node_id: u64,
+    max_sequence: u64,
+    node_bits: u64,
+    timestamp_offset_ms: i64,
+    rollover_policy: SequenceRolloverPolicy,
+    clock: Arc<dyn EpochClock>,
+    timestamp_sequence: AtomicU64,
+}
+
+impl SnowflakeIdGenerator {
+    /// Default layout with timestamps counted from the Unix epoch.
+    pub fn new(node_id: u64) -> Result<Self> {
+        Self::with_settings(
+            DEFAULT_NODE_ID_BITS,
+            DEFAULT_SEQUENCE_BITS,
+            node_id,
+            0,
+            Arc::new(SystemEpochClock),
+        )
+    }
+
+    pub fn with_settings(
+        node_id_bits: u32,
+        sequence_bits: u32,
+        node_id: u64,
+        timestamp_offset_ms: i64,
+        clock: Arc<dyn EpochClock>,
+    ) -> Result<Self> {
+        let node_id_and_sequence_bits = node_id_bits + sequence_bits;
+        if node_id_and_sequence_bits > MAX_NODE_ID_AND_SEQUENCE_BITS {
+            return Err(AgronaError::IllegalArgument(format!(
+                "node id bits {} plus sequence bits {} exceeds {}",
+                node_id_bits, sequence_bits, MAX_NODE_ID_AND_SEQUENCE_BITS
+            )));
+        }
+
+        let max_node_id = (1u64 << node_id_bits) - 1;
+        if node_id > max_node_id {
+            return Err(AgronaError::IllegalArgument(format!(
+                "node id {} exceeds maximum of {} for {} bits",
+                node_id, max_node_id, node_id_bits
+            )));
+        }
+
+        let now_ms = clock.time();
+        if timestamp_offset_ms < 0 || timestamp_offset_ms > now_ms {
+            return Err(AgronaError::IllegalArgument(format!(
+                "timestamp offset {}ms must be in 0..={}",
+                timestamp_offset_ms, now_ms
+            )));
+        }
+
+        let node_bits = node_id << sequence_bits;
+
+        Ok(Self {
+            node_id_and_sequence_bits,
+            sequence_bits,
+            max_node_id,
+            max_sequence: (1u64 << sequence_bits) - 1,
+            node_bits,
+            timestamp_offset_ms,
+            rollover_policy: SequenceRolloverPolicy::SpinWait,
+            clock,
+            // Carries the node id even for ids generated in the offset's own millisecond
+            timestamp_sequence: AtomicU64::new(node_bits),
+        })
+    }
+
+    pub fn with_rollover_policy(mut self, rollover_policy: SequenceRolloverPolicy) -> Self {
+        self.rollover_policy = rollover_policy;
+        self
+    }
+
+    /// Generates the next id; safe to call from any number of threads.
+    pub fn next_id(&self) -> Result<i64> {
+        loop {
+            let old_timestamp_sequence = self.timestamp_sequence.load(Ordering::Acquire);
+            let timestamp_ms = self.clock.time() - self.timestamp_offset_ms;
+            let old_timestamp_ms = (old_timestamp_sequence >> self.node_id_and_sequence_bits) as i64;
+
+            if timestamp_ms > old_timestamp_ms {
+                let new_timestamp_sequence = ((timestamp_ms as u64) << self.node_id_and_sequence_bits) | self.node_bits;
+                if self.compare_and_set(old_timestamp_sequence, new_timestamp_sequence) {
+                    return Ok(new_timestamp_sequence as i64);
+                }
+            } else {
+                let old_sequence = old_timestamp_sequence & self.max_sequence;
+                if old_sequence < self.max_sequence {
+                    let new_timestamp_sequence = old_timestamp_sequence + 1;
+                    if self.compare_and_set(old_timestamp_sequence, new_timestamp_sequence) {
+                        return Ok(new_timestamp_sequence as i64);
+                    }
+                } else if self.rollover_policy == SequenceRolloverPolicy::Error {
+                    return Err(AgronaError::IllegalState(format!(
+                        "sequence exhausted for timestamp {}ms",
+                        old_timestamp_ms + self.timestamp_offset_ms
+                    )));
+                }
+            }
+
+            std::hint::spin_loop();
+        }
+    }
+
+    #[inline]
+    fn compare_and_set(&self, expected: u64, update: u64) -> bool {
+        self.timestamp_sequence
+            .compare_exchange(expected, update, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Timestamp of `id` in milliseconds since the Unix epoch.
+    #[inline]
+    pub fn extract_timestamp(&self, id: i64) -> i64 {
+        (id >> self.node_id_and_sequence_bits) + self.timestamp_offset_ms
+    }
+
+    #[inline]
+    pub fn extract_node_id(&self, id: i64) -> u64 {
+        (id as u64 >> self.sequence_bits) & self.max_node_id
+    }
+
+    #[inline]
+    pub fn extract_sequence(&self, id: i64) -> u64 {
+        id as u64 & self.max_sequence
+    }
+
+    #[inline]
+    pub fn node_id(&self) -> u64 {
+        self.node_bits >> self.sequence_bits
+    }
+
+    #[inline]
+    pub fn max_node_id(&self) -> u64 {
+        self.max_node_id
+    }
+
+    #[inline]
+    pub fn max_sequence(&self) -> u64 {
+        self.max_sequence
+    }
+
+    #[inline]
+    pub fn timestamp_offset_ms(&self) -> i64 {
+        self.timestamp_offset_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use std::collections::HashSet;
+    use std::thread;
+    use std::time::Duration;
+
+    const START_MS: i64 = 1_700_000_000_000;
+    const OFFSET_MS: i64 = 1_600_000_000_000;
+
+    fn clock_at(time_ms: i64) -> Arc<TestClock> {
+        Arc::new(TestClock::new(time_ms * 1_000_000))
+    }
+
+    #[test]
+    fn test_id_layout() {
+        let clock = clock_at(START_MS);
+        let generator = SnowflakeIdGenerator::with_settings(4, 8, 9, OFFSET_MS, clock.clone()).unwrap();
+
+        let first = generator.next_id().unwrap();
+        let second = generator.next_id().unwrap();
+        assert!(first > 0 && second > first);
+        assert_eq!(generator.extract_timestamp(first), START_MS);
+        assert_eq!(generator.extract_node_id(first), 9);
+        assert_eq!(generator.extract_sequence(first), 0);
+        assert_eq!(generator.extract_sequence(second), 1);
+
+        clock.advance(Duration::from_millis(1));
+        let third = generator.next_id().unwrap();
+        assert_eq!(generator.extract_timestamp(third), START_MS + 1);
+        assert_eq!(generator.extract_sequence(third), 0);
+    }
+
+    #[test]
+    fn test_nodes_unique_when_offset_is_now() {
+        let clock = clock_at(START_MS);
+        let first = SnowflakeIdGenerator::with_settings(4, 8, 1, START_MS, clock.clone()).unwrap();
+        let second = SnowflakeIdGenerator::with_settings(4, 8, 2, START_MS, clock.clone()).unwrap();
+
+        let first_ids: HashSet<i64> = (0..10).map(|_| first.next_id().unwrap()).collect();
+        let second_ids: HashSet<i64> = (0..10).map(|_| second.next_id().unwrap()).collect();
+
+        assert!(first_ids.is_disjoint(&second_ids));
+        assert!(first_ids.iter().all(|&id| first.extract_node_id(id) == 1));
+        assert!(second_ids.iter().all(|&id| second.extract_node_id(id) == 2));
+        assert!(first_ids.iter().all(|&id| first.extract_timestamp(id) == START_MS));
+    }
+
+    #[test]
+    fn test_rejects_invalid_settings() {
+        let clock = clock_at(START_MS);
+        assert!(SnowflakeIdGenerator::with_settings(12, 11, 0, 0, clock.clone()).is_err());
+        assert!(SnowflakeIdGenerator::with_settings(4, 8, 16, 0, clock.clone()).is_err());
+        assert!(SnowflakeIdGenerator::with_settings(4, 8, 0, -1, clock.clone()).is_err());
+        assert!(SnowflakeIdGenerator::with_settings(4, 8, 0, START_MS + 1, clock).is_err());
+        assert!(SnowflakeIdGenerator::new(1023).is_ok());
+    }
+
+    #[test]
+    fn test_clock_regression_keeps_ids_increasing() {
+        let clock = clock_at(START_MS);
+        let generator = SnowflakeIdGenerator::with_settings(4, 8, 1, 0, clock.clone()).unwrap();
+
+        let before = generator.next_id().unwrap();
+        clock.set((START_MS - 1_000) * 1_000_000);
+        let after = generator.next_id().unwrap();
+
+        assert!(after > before);
+        assert_eq!(generator.extract_timestamp(after), START_MS);
+        assert_eq!(generator.extract_sequence(after), 1);
+    }
+
+    #[test]
+    fn test_sequence_exhaustion() {
+        let clock = clock_at(START_MS);
+        let generator = SnowflakeIdGenerator::with_settings(4, 2, 1, 0, clock.clone())
+            .unwrap()
+            .with_rollover_policy(SequenceRolloverPolicy::Error);
+
+        for sequence in 0..=generator.max_sequence() {
+            assert_eq!(generator.extract_sequence(generator.next_id().unwrap()), sequence);
+        }
+        assert!(matches!(generator.next_id(), Err(AgronaError::IllegalState(_))));
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(generator.extract_timestamp(generator.next_id().unwrap()), START_MS + 1);
+
+        let generator = SnowflakeIdGenerator::with_settings(4, 2, 1, 0, clock.clone()).unwrap();
+        for _ in 0..=generator.max_sequence() {
+            generator.next_id().unwrap();
+        }
+
+        let ticker = {
+            let clock = clock.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                clock.advance(Duration::from_millis(1));
+            })
+        };
+        let id = generator.next_id().unwrap();
+        ticker.join().unwrap();
+        assert_eq!(generator.extract_timestamp(id), START_MS + 2);
+    }
+
+    #[test]
+    fn test_unique_across_threads() {
+        let generator = Arc::new(SnowflakeIdGenerator::new(7).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..10_000).map(|_| generator.next_id().unwrap()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut ids = HashSet::new();
+        for handle in handles {
+            let thread_ids = handle.join().unwrap();
+            assert!(thread_ids.windows(2).all(|pair| pair[0] < pair[1]));
+            ids.extend(thread_ids);
+        }
+        assert_eq!(ids.len(), 40_000);
+    }
+}
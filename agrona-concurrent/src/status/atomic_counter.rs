@@ -0,0 +1,198 @@
+use crate::status::counters_reader::CountersReader;
+use agrona_core::error::Result;
+use core::ptr::NonNull;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Handle to a single 64-bit counter in a counters values buffer.
+///
+/// The handle points directly into the values buffer without borrowing it, so the memory must
+/// outlive the handle; see [`wrap`](Self::wrap).
+pub struct AtomicCounter {
+    counter_id: i32,
+    value: NonNull<AtomicI64>,
+}
+
+unsafe impl Send for AtomicCounter {}
+unsafe impl Sync for AtomicCounter {}
+
+impl AtomicCounter {
+    /// Handle to counter `counter_id` in `reader`'s values buffer.
+    ///
+    /// # Safety
+    ///
+    /// The memory behind `reader.values_buffer()` must remain valid for as long as the returned
+    /// handle is used, e.g. the owning `CountersManager` or mapped file must not be dropped first.
+    pub unsafe fn wrap(reader: &CountersReader, counter_id: i32) -> Result<Self> {
+        reader.validate_counter_id(counter_id)?;
+        let offset = CountersReader::counter_offset(counter_id);
+        let ptr = unsafe { reader.values_buffer().as_ptr().add(offset) } as *mut AtomicI64;
+
+        Ok(Self {
+            counter_id,
+            value: NonNull::new(ptr).expect("values buffer pointer is null"),
+        })
+    }
+
+    #[inline(always)]
+    fn value(&self) -> &AtomicI64 {
+        unsafe { self.value.as_ref() }
+    }
+
+    #[inline]
+    pub fn id(&self) -> i32 {
+        self.counter_id
+    }
+
+    #[inline]
+    pub fn increment(&self) -> i64 {
+        self.value().fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Single-writer increment that publishes with release semantics and no locked instruction.
+    #[inline]
+    pub fn increment_ordered(&self) -> i64 {
+        let current = self.value().load(Ordering::Relaxed);
+        self.value().store(current + 1, Ordering::Release);
+        current
+    }
+
+    #[inline]
+    pub fn decrement(&self) -> i64 {
+        self.value().fetch_sub(1, Ordering::SeqCst)
+    }
+
+    #[inline]
+    pub fn get_and_add(&self, delta: i64) -> i64 {
+        self.value().fetch_add(delta, Ordering::SeqCst)
+    }
+
+    #[inline]
+    pub fn get_and_add_ordered(&self, delta: i64) -> i64 {
+        let current = self.value().load(Ordering::Relaxed);
+        self.value().store(current + delta, Ordering::Release);
+        current
+    }
+
+    #[inline]
+    pub fn get_and_set(&self, value: i64) -> i64 {
+        self.value().swap(value, Ordering::SeqCst)
+    }
+
+    #[inline]
+    pub fn compare_and_set(&self, expected: i64, update: i64) -> bool {
+        self.value()
+            .compare_exchange(expected, update, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    #[inline]
+    pub fn set(&self, value: i64) {
+        self.value().store(value, Ordering::SeqCst);
+    }
+
+    #[inline]
+    pub fn set_ordered(&self, value: i64) {
+        self.value().store(value, Ordering::Release);
+    }
+
+    #[inline]
+    pub fn set_weak(&self, value: i64) {
+        self.value().store(value, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn get(&self) -> i64 {
+        self.value().load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub fn get_weak(&self) -> i64 {
+        self.value().load(Ordering::Relaxed)
+    }
+
+    /// Single-writer update to `proposed_value` if it is greater than the current value.
+    #[inline]
+    pub fn propose_max(&self, proposed_value: i64) -> bool {
+        if self.value().load(Ordering::Relaxed) < proposed_value {
+            self.value().store(proposed_value, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    pub fn propose_max_ordered(&self, proposed_value: i64) -> bool {
+        if self.value().load(Ordering::Relaxed) < proposed_value {
+            self.value().store(proposed_value, Ordering::Release);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::status::CountersManager;
+    use crate::AtomicBuffer;
+    use crate::status::{COUNTER_LENGTH, METADATA_LENGTH};
+
+    fn manager() -> CountersManager {
+        CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH * 8).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * 8).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_increment_and_set() {
+        let mut manager = manager();
+        let counter = unsafe { manager.new_counter("test", 1, &[]) }.unwrap();
+
+        assert_eq!(counter.increment(), 0);
+        assert_eq!(counter.increment_ordered(), 1);
+        assert_eq!(counter.get_and_add(10), 2);
+        assert_eq!(counter.get(), 12);
+
+        counter.set_ordered(100);
+        assert_eq!(manager.counter_value(counter.id()).unwrap(), 100);
+
+        assert!(counter.compare_and_set(100, 200));
+        assert!(!counter.compare_and_set(100, 300));
+        assert_eq!(counter.get_weak(), 200);
+    }
+
+    #[test]
+    fn test_propose_max() {
+        let mut manager = manager();
+        let counter = unsafe { manager.new_counter("max", 1, &[]) }.unwrap();
+
+        assert!(counter.propose_max(5));
+        assert!(!counter.propose_max(3));
+        assert!(counter.propose_max_ordered(7));
+        assert_eq!(counter.get(), 7);
+    }
+
+    #[test]
+    fn test_concurrent_increments() {
+        let mut manager = manager();
+        let counter = std::sync::Arc::new(unsafe { manager.new_counter("shared", 1, &[]) }.unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let counter = std::sync::Arc::clone(&counter);
+                std::thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        counter.increment();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(manager.counter_value(counter.id()).unwrap(), 40_000);
+    }
+}
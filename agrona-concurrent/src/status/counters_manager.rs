@@ -0,0 +1,293 @@
+use crate::atomic_buffer::AtomicBuffer;
+use crate::clock::{EpochClock, SystemEpochClock};
+use crate::status::atomic_counter::AtomicCounter;
+use crate::status::counters_reader::*;
+use crate::status::position::UnsafeBufferPosition;
+use agrona_core::bit_util::SIZE_OF_I32;
+use agrona_core::buffer::{DirectBuffer, MutableBuffer};
+use agrona_core::error::{AgronaError, Result};
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Allocates and frees counters in a values buffer, describing each in the metadata buffer.
+///
+/// Not thread safe: a single owner allocates, while [`AtomicCounter`] handles and
+/// [`CountersReader`]s over the same memory may be used from other threads or processes.
+pub struct CountersManager {
+    reader: CountersReader,
+    epoch_clock: Arc<dyn EpochClock>,
+    free_to_reuse_timeout_ms: i64,
+    high_water_mark_id: i32,
+    free_list: VecDeque<i32>,
+}
+
+impl CountersManager {
+    pub fn new(metadata_buffer: AtomicBuffer, values_buffer: AtomicBuffer) -> Result<Self> {
+        Self::with_reuse_timeout(metadata_buffer, values_buffer, Arc::new(SystemEpochClock), 0)
+    }
+
+    /// Freed counter ids are not reused until `free_to_reuse_timeout_ms` has elapsed on `epoch_clock`,
+    /// giving readers time to notice the counter has gone.
+    pub fn with_reuse_timeout(
+        mut metadata_buffer: AtomicBuffer,
+        mut values_buffer: AtomicBuffer,
+        epoch_clock: Arc<dyn EpochClock>,
+        free_to_reuse_timeout_ms: i64,
+    ) -> Result<Self> {
+        let metadata_capacity = metadata_buffer.capacity();
+        let values_capacity = values_buffer.capacity();
+        metadata_buffer.set_memory(0, metadata_capacity, 0)?;
+        values_buffer.set_memory(0, values_capacity, 0)?;
+
+        Ok(Self {
+            reader: CountersReader::new(metadata_buffer, values_buffer)?,
+            epoch_clock,
+            free_to_reuse_timeout_ms,
+            high_water_mark_id: NULL_COUNTER_ID,
+            free_list: VecDeque::new(),
+        })
+    }
+
+    pub fn reader(&self) -> &CountersReader {
+        &self.reader
+    }
+
+    pub fn free_to_reuse_timeout_ms(&self) -> i64 {
+        self.free_to_reuse_timeout_ms
+    }
+
+    pub fn allocate(&mut self, label: &str, type_id: i32, key: &[u8]) -> Result<i32> {
+        if key.len() > MAX_KEY_LENGTH {
+            return Err(AgronaError::IllegalArgument(format!(
+                "key length {} exceeds max {}",
+                key.len(),
+                MAX_KEY_LENGTH
+            )));
+        }
+
+        let counter_id = self.next_counter_id()?;
+        let offset = CountersReader::metadata_offset(counter_id);
+        let label = truncate_label(label);
+
+        let metadata = self.reader.metadata_buffer_mut();
+        metadata.put_i32(offset + TYPE_ID_OFFSET, type_id)?;
+        metadata.put_ordered_u64(offset + FREE_FOR_REUSE_DEADLINE_OFFSET, NOT_FREE_TO_REUSE as u64)?;
+        metadata.set_memory(offset + KEY_OFFSET, MAX_KEY_LENGTH, 0)?;
+        metadata.put_bytes(offset + KEY_OFFSET, key)?;
+        metadata.put_i32(offset + LABEL_OFFSET, label.len() as i32)?;
+        metadata.put_bytes(offset + LABEL_OFFSET + SIZE_OF_I32, label.as_bytes())?;
+        metadata.put_ordered_u32(offset + STATE_OFFSET, RECORD_ALLOCATED as u32)?;
+
+        Ok(counter_id)
+    }
+
+    /// Allocates a counter and returns a handle to its value.
+    ///
+    /// # Safety
+    ///
+    /// The handle does not borrow the manager, so the values buffer memory must remain valid
+    /// for as long as the handle is used. See [`AtomicCounter::wrap`].
+    pub unsafe fn new_counter(&mut self, label: &str, type_id: i32, key: &[u8]) -> Result<AtomicCounter> {
+        let counter_id = self.allocate(label, type_id, key)?;
+        AtomicCounter::wrap(&self.reader, counter_id)
+    }
+
+    /// Allocates a counter and returns it as a [`Position`](crate::status::Position).
+    ///
+    /// # Safety
+    ///
+    /// As for [`new_counter`](Self::new_counter).
+    pub unsafe fn new_position(&mut self, label: &str, type_id: i32, key: &[u8]) -> Result<UnsafeBufferPosition> {
+        let counter_id = self.allocate(label, type_id, key)?;
+        UnsafeBufferPosition::wrap(&self.reader, counter_id)
+    }
+
+    pub fn free(&mut self, counter_id: i32) -> Result<()> {
+        if self.reader.counter_state(counter_id)? != RECORD_ALLOCATED {
+            return Err(AgronaError::IllegalState(format!(
+                "counter {} is not allocated",
+                counter_id
+            )));
+        }
+
+        let deadline = self.epoch_clock.time().saturating_add(self.free_to_reuse_timeout_ms);
+        let offset = CountersReader::metadata_offset(counter_id);
+
+        let metadata = self.reader.metadata_buffer_mut();
+        metadata.put_ordered_u64(offset + FREE_FOR_REUSE_DEADLINE_OFFSET, deadline as u64)?;
+        metadata.put_ordered_u32(offset + STATE_OFFSET, RECORD_RECLAIMED as u32)?;
+        self.free_list.push_back(counter_id);
+
+        Ok(())
+    }
+
+    pub fn set_counter_value(&mut self, counter_id: i32, value: i64) -> Result<()> {
+        self.reader.validate_counter_id(counter_id)?;
+        self.reader
+            .values_buffer_mut()
+            .put_ordered_u64(CountersReader::counter_offset(counter_id), value as u64)
+    }
+
+    fn next_counter_id(&mut self) -> Result<i32> {
+        let now_ms = self.epoch_clock.time();
+
+        for i in 0..self.free_list.len() {
+            let counter_id = self.free_list[i];
+            if now_ms >= self.reader.free_for_reuse_deadline(counter_id)? {
+                self.free_list.remove(i);
+                self.reader
+                    .values_buffer_mut()
+                    .set_memory(CountersReader::counter_offset(counter_id), COUNTER_LENGTH, 0)?;
+                return Ok(counter_id);
+            }
+        }
+
+        if self.high_water_mark_id >= self.reader.max_counter_id() {
+            return Err(AgronaError::IllegalState(format!(
+                "unable to allocate counter, values buffer is full: max counter id {}",
+                self.reader.max_counter_id()
+            )));
+        }
+
+        self.high_water_mark_id += 1;
+        Ok(self.high_water_mark_id)
+    }
+}
+
+impl Deref for CountersManager {
+    type Target = CountersReader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.reader
+    }
+}
+
+fn truncate_label(label: &str) -> &str {
+    if label.len() <= MAX_LABEL_LENGTH {
+        return label;
+    }
+
+    let mut end = MAX_LABEL_LENGTH;
+    while !label.is_char_boundary(end) {
+        end -= 1;
+    }
+    &label[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::CachedEpochClock;
+    use agrona_core::MappedFile;
+
+    fn manager(counters: usize) -> CountersManager {
+        CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH * counters).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * counters).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_allocate_and_describe() {
+        let mut manager = manager(4);
+
+        let first = manager.allocate("first", 7, b"key-one").unwrap();
+        let second = manager.allocate("second", 8, &[]).unwrap();
+        assert_eq!((first, second), (0, 1));
+
+        assert_eq!(manager.counter_state(first).unwrap(), RECORD_ALLOCATED);
+        assert_eq!(manager.counter_type_id(second).unwrap(), 8);
+        assert_eq!(manager.counter_label(first).unwrap(), "first");
+
+        let mut key = [0u8; MAX_KEY_LENGTH];
+        manager.counter_key(first, &mut key).unwrap();
+        assert_eq!(&key[..7], b"key-one");
+
+        let mut seen = Vec::new();
+        manager
+            .for_each(|id, type_id, _, label| seen.push((id, type_id, label.to_string())))
+            .unwrap();
+        assert_eq!(seen, vec![(0, 7, "first".to_string()), (1, 8, "second".to_string())]);
+    }
+
+    #[test]
+    fn test_allocation_fails_when_full() {
+        let mut manager = manager(2);
+        manager.allocate("a", 0, &[]).unwrap();
+        manager.allocate("b", 0, &[]).unwrap();
+
+        assert!(matches!(manager.allocate("c", 0, &[]), Err(AgronaError::IllegalState(_))));
+        assert!(manager.allocate("key", 0, &[0u8; MAX_KEY_LENGTH + 1]).is_err());
+    }
+
+    #[test]
+    fn test_long_label_is_truncated() {
+        let mut manager = manager(1);
+        let label = "x".repeat(MAX_LABEL_LENGTH + 10);
+        let id = manager.allocate(&label, 0, &[]).unwrap();
+
+        assert_eq!(manager.counter_label(id).unwrap().len(), MAX_LABEL_LENGTH);
+    }
+
+    #[test]
+    fn test_free_and_reuse_after_timeout() {
+        let clock = Arc::new(CachedEpochClock::new());
+        clock.update(1_000);
+        let mut manager = CountersManager::with_reuse_timeout(
+            AtomicBuffer::new(METADATA_LENGTH * 4).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * 4).unwrap(),
+            clock.clone(),
+            100,
+        )
+        .unwrap();
+
+        let counter = unsafe { manager.new_counter("reused", 1, &[]) }.unwrap();
+        counter.set(42);
+        manager.free(counter.id()).unwrap();
+        assert_eq!(manager.counter_state(0).unwrap(), RECORD_RECLAIMED);
+        assert_eq!(manager.free_for_reuse_deadline(0).unwrap(), 1_100);
+        assert!(manager.free(0).is_err());
+
+        assert_eq!(manager.allocate("fresh", 1, &[]).unwrap(), 1);
+
+        clock.update(1_100);
+        assert_eq!(manager.allocate("recycled", 1, &[]).unwrap(), 0);
+        assert_eq!(manager.counter_value(0).unwrap(), 0);
+        assert_eq!(manager.counter_label(0).unwrap(), "recycled");
+    }
+
+    #[test]
+    fn test_reader_over_mapped_file() {
+        let path = std::env::temp_dir().join(format!("agrona-counters-{}", std::process::id()));
+        let metadata_length = METADATA_LENGTH * 8;
+        let values_length = COUNTER_LENGTH * 8;
+
+        let mut owner_file = MappedFile::create(&path, metadata_length + values_length).unwrap();
+        let owner_ptr = owner_file.as_mut_ptr();
+        let mut manager = CountersManager::new(
+            AtomicBuffer::wrap(owner_ptr, metadata_length),
+            AtomicBuffer::wrap(unsafe { owner_ptr.add(metadata_length) }, values_length),
+        )
+        .unwrap();
+
+        let counter = unsafe { manager.new_counter("orders-sent", 3, &[]) }.unwrap();
+        counter.get_and_add(17);
+
+        let mut observer_file = MappedFile::open(&path).unwrap();
+        let observer_ptr = observer_file.as_mut_ptr();
+        let reader = CountersReader::new(
+            AtomicBuffer::wrap(observer_ptr, metadata_length),
+            AtomicBuffer::wrap(unsafe { observer_ptr.add(metadata_length) }, values_length),
+        )
+        .unwrap();
+
+        let mut values = Vec::new();
+        reader.for_each_counter_value(|id, value| values.push((id, value))).unwrap();
+        assert_eq!(values, vec![(counter.id(), 17)]);
+        assert_eq!(reader.counter_label(counter.id()).unwrap(), "orders-sent");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
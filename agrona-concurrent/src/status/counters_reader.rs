@@ -0,0 +1,230 @@
+use crate::atomic_buffer::AtomicBuffer;
+use agrona_core::bit_util::{CACHE_LINE_LENGTH, SIZE_OF_I32, SIZE_OF_I64};
+use agrona_core::buffer::DirectBuffer;
+use agrona_core::error::{AgronaError, Result};
+
+pub const NULL_COUNTER_ID: i32 = -1;
+pub const DEFAULT_TYPE_ID: i32 = 0;
+
+pub const RECORD_UNUSED: i32 = 0;
+pub const RECORD_ALLOCATED: i32 = 1;
+pub const RECORD_RECLAIMED: i32 = -1;
+
+pub const NOT_FREE_TO_REUSE: i64 = i64::MAX;
+
+/// Each counter value occupies two cache lines so neighbouring counters never share one.
+pub const COUNTER_LENGTH: usize = CACHE_LINE_LENGTH * 2;
+pub const METADATA_LENGTH: usize = CACHE_LINE_LENGTH * 8;
+
+pub const STATE_OFFSET: usize = 0;
+pub const TYPE_ID_OFFSET: usize = STATE_OFFSET + SIZE_OF_I32;
+pub const FREE_FOR_REUSE_DEADLINE_OFFSET: usize = TYPE_ID_OFFSET + SIZE_OF_I32;
+pub const KEY_OFFSET: usize = FREE_FOR_REUSE_DEADLINE_OFFSET + SIZE_OF_I64;
+pub const MAX_KEY_LENGTH: usize = CACHE_LINE_LENGTH * 2 - KEY_OFFSET;
+pub const LABEL_OFFSET: usize = CACHE_LINE_LENGTH * 2;
+pub const FULL_LABEL_LENGTH: usize = CACHE_LINE_LENGTH * 6;
+pub const MAX_LABEL_LENGTH: usize = FULL_LABEL_LENGTH - SIZE_OF_I32;
+
+/// Read-only view over a counters values buffer and its metadata buffer.
+///
+/// The buffers may wrap memory shared with another process, e.g. a [`agrona_core::MappedFile`].
+pub struct CountersReader {
+    metadata_buffer: AtomicBuffer,
+    values_buffer: AtomicBuffer,
+    max_counter_id: i32,
+}
+
+impl CountersReader {
+    pub fn new(metadata_buffer: AtomicBuffer, values_buffer: AtomicBuffer) -> Result<Self> {
+        let values_capacity = values_buffer.capacity();
+        if values_capacity < COUNTER_LENGTH || !values_capacity.is_multiple_of(COUNTER_LENGTH) {
+            return Err(AgronaError::InvalidCapacity { capacity: values_capacity });
+        }
+
+        if !(values_buffer.as_ptr() as usize).is_multiple_of(SIZE_OF_I64) {
+            return Err(AgronaError::IllegalArgument(
+                "values buffer must be 8 byte aligned".to_string(),
+            ));
+        }
+
+        let counter_count = values_capacity / COUNTER_LENGTH;
+        let required_metadata_capacity = counter_count * METADATA_LENGTH;
+        if metadata_buffer.capacity() < required_metadata_capacity {
+            return Err(AgronaError::IllegalArgument(format!(
+                "metadata buffer capacity {} is less than required {}",
+                metadata_buffer.capacity(),
+                required_metadata_capacity
+            )));
+        }
+
+        Ok(Self {
+            metadata_buffer,
+            values_buffer,
+            max_counter_id: counter_count as i32 - 1,
+        })
+    }
+
+    #[inline]
+    pub fn max_counter_id(&self) -> i32 {
+        self.max_counter_id
+    }
+
+    pub fn metadata_buffer(&self) -> &AtomicBuffer {
+        &self.metadata_buffer
+    }
+
+    pub fn values_buffer(&self) -> &AtomicBuffer {
+        &self.values_buffer
+    }
+
+    pub(crate) fn metadata_buffer_mut(&mut self) -> &mut AtomicBuffer {
+        &mut self.metadata_buffer
+    }
+
+    pub(crate) fn values_buffer_mut(&mut self) -> &mut AtomicBuffer {
+        &mut self.values_buffer
+    }
+
+    #[inline]
+    pub const fn counter_offset(counter_id: i32) -> usize {
+        counter_id as usize * COUNTER_LENGTH
+    }
+
+    #[inline]
+    pub const fn metadata_offset(counter_id: i32) -> usize {
+        counter_id as usize * METADATA_LENGTH
+    }
+
+    pub fn validate_counter_id(&self, counter_id: i32) -> Result<()> {
+        if counter_id < 0 || counter_id > self.max_counter_id {
+            return Err(AgronaError::IllegalArgument(format!(
+                "counter id {} out of range: 0 - {}",
+                counter_id, self.max_counter_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn counter_value(&self, counter_id: i32) -> Result<i64> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.values_buffer.get_volatile_u64(Self::counter_offset(counter_id))? as i64)
+    }
+
+    pub fn counter_state(&self, counter_id: i32) -> Result<i32> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.metadata_buffer.get_volatile_u32(Self::metadata_offset(counter_id) + STATE_OFFSET)? as i32)
+    }
+
+    pub fn counter_type_id(&self, counter_id: i32) -> Result<i32> {
+        self.validate_counter_id(counter_id)?;
+        self.metadata_buffer.get_i32(Self::metadata_offset(counter_id) + TYPE_ID_OFFSET)
+    }
+
+    pub fn free_for_reuse_deadline(&self, counter_id: i32) -> Result<i64> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self
+            .metadata_buffer
+            .get_volatile_u64(Self::metadata_offset(counter_id) + FREE_FOR_REUSE_DEADLINE_OFFSET)? as i64)
+    }
+
+    pub fn counter_key(&self, counter_id: i32, dst: &mut [u8; MAX_KEY_LENGTH]) -> Result<()> {
+        self.validate_counter_id(counter_id)?;
+        self.metadata_buffer.get_bytes(Self::metadata_offset(counter_id) + KEY_OFFSET, dst)
+    }
+
+    pub fn counter_label(&self, counter_id: i32) -> Result<String> {
+        self.validate_counter_id(counter_id)?;
+        self.metadata_buffer.get_string_utf8(Self::metadata_offset(counter_id) + LABEL_OFFSET)
+    }
+
+    /// Visits every allocated counter with `(counter_id, type_id, key, label)` without allocating.
+    pub fn for_each<F>(&self, mut consumer: F) -> Result<()>
+    where
+        F: FnMut(i32, i32, &[u8], &str),
+    {
+        let mut key = [0u8; MAX_KEY_LENGTH];
+        let mut label = [0u8; MAX_LABEL_LENGTH];
+
+        for counter_id in 0..=self.max_counter_id {
+            let offset = Self::metadata_offset(counter_id);
+            let state = self.metadata_buffer.get_volatile_u32(offset + STATE_OFFSET)? as i32;
+            if state == RECORD_UNUSED {
+                break;
+            }
+            if state != RECORD_ALLOCATED {
+                continue;
+            }
+
+            let type_id = self.metadata_buffer.get_i32(offset + TYPE_ID_OFFSET)?;
+            self.metadata_buffer.get_bytes(offset + KEY_OFFSET, &mut key)?;
+
+            let label_length = (self.metadata_buffer.get_i32(offset + LABEL_OFFSET)? as usize).min(MAX_LABEL_LENGTH);
+            let label = &mut label[..label_length];
+            self.metadata_buffer.get_bytes(offset + LABEL_OFFSET + SIZE_OF_I32, label)?;
+
+            consumer(counter_id, type_id, &key, core::str::from_utf8(label)?);
+        }
+
+        Ok(())
+    }
+
+    /// Visits every allocated counter with `(counter_id, value)`.
+    pub fn for_each_counter_value<F>(&self, mut consumer: F) -> Result<()>
+    where
+        F: FnMut(i32, i64),
+    {
+        for counter_id in 0..=self.max_counter_id {
+            let state = self
+                .metadata_buffer
+                .get_volatile_u32(Self::metadata_offset(counter_id) + STATE_OFFSET)? as i32;
+            if state == RECORD_UNUSED {
+                break;
+            }
+            if state == RECORD_ALLOCATED {
+                consumer(counter_id, self.counter_value(counter_id)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agrona_core::buffer::MutableBuffer;
+
+    #[test]
+    fn test_layout_constants() {
+        assert_eq!(COUNTER_LENGTH, 128);
+        assert_eq!(METADATA_LENGTH, 512);
+        assert_eq!(KEY_OFFSET, 16);
+        assert_eq!(MAX_KEY_LENGTH, 112);
+        assert_eq!(LABEL_OFFSET + FULL_LABEL_LENGTH, METADATA_LENGTH);
+    }
+
+    #[test]
+    fn test_rejects_undersized_metadata_buffer() {
+        let metadata = AtomicBuffer::new(METADATA_LENGTH).unwrap();
+        let values = AtomicBuffer::new(COUNTER_LENGTH * 2).unwrap();
+
+        assert!(matches!(
+            CountersReader::new(metadata, values),
+            Err(AgronaError::IllegalArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_reader() {
+        let mut metadata = AtomicBuffer::new(METADATA_LENGTH * 4).unwrap();
+        metadata.set_memory(0, METADATA_LENGTH * 4, 0).unwrap();
+        let values = AtomicBuffer::new(COUNTER_LENGTH * 4).unwrap();
+        let reader = CountersReader::new(metadata, values).unwrap();
+
+        assert_eq!(reader.max_counter_id(), 3);
+        let mut count = 0;
+        reader.for_each(|_, _, _, _| count += 1).unwrap();
+        assert_eq!(count, 0);
+        assert!(reader.counter_value(4).is_err());
+    }
+}
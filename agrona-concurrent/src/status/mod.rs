@@ -0,0 +1,11 @@
+pub mod atomic_counter;
+pub mod counters_manager;
+pub mod counters_reader;
+pub mod position;
+pub mod status_indicator;
+
+pub use atomic_counter::*;
+pub use counters_manager::*;
+pub use counters_reader::*;
+pub use position::*;
+pub use status_indicator::*;
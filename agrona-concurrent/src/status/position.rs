@@ -0,0 +1,130 @@
+use crate::status::counters_reader::CountersReader;
+use agrona_core::error::Result;
+use core::ptr::NonNull;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+pub trait ReadablePosition {
+    fn id(&self) -> i32;
+    fn get_volatile(&self) -> i64;
+}
+
+pub trait Position: ReadablePosition {
+    fn get(&self) -> i64;
+    fn set(&self, value: i64);
+    fn set_ordered(&self, value: i64);
+    fn set_volatile(&self, value: i64);
+    fn propose_max(&self, proposed_value: i64) -> bool;
+    fn propose_max_ordered(&self, proposed_value: i64) -> bool;
+}
+
+/// [`Position`] backed by a counter slot in a counters values buffer, which must outlive it; see
+/// [`wrap`](Self::wrap).
+pub struct UnsafeBufferPosition {
+    counter_id: i32,
+    value: NonNull<AtomicI64>,
+}
+
+unsafe impl Send for UnsafeBufferPosition {}
+unsafe impl Sync for UnsafeBufferPosition {}
+
+impl UnsafeBufferPosition {
+    /// # Safety
+    ///
+    /// The memory behind `reader.values_buffer()` must remain valid for as long as the returned
+    /// position is used.
+    pub unsafe fn wrap(reader: &CountersReader, counter_id: i32) -> Result<Self> {
+        reader.validate_counter_id(counter_id)?;
+        let offset = CountersReader::counter_offset(counter_id);
+        let ptr = unsafe { reader.values_buffer().as_ptr().add(offset) } as *mut AtomicI64;
+
+        Ok(Self {
+            counter_id,
+            value: NonNull::new(ptr).expect("values buffer pointer is null"),
+        })
+    }
+
+    #[inline(always)]
+    fn value(&self) -> &AtomicI64 {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl ReadablePosition for UnsafeBufferPosition {
+    #[inline]
+    fn id(&self) -> i32 {
+        self.counter_id
+    }
+
+    #[inline]
+    fn get_volatile(&self) -> i64 {
+        self.value().load(Ordering::Acquire)
+    }
+}
+
+impl Position for UnsafeBufferPosition {
+    #[inline]
+    fn get(&self) -> i64 {
+        self.value().load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn set(&self, value: i64) {
+        self.value().store(value, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn set_ordered(&self, value: i64) {
+        self.value().store(value, Ordering::Release);
+    }
+
+    #[inline]
+    fn set_volatile(&self, value: i64) {
+        self.value().store(value, Ordering::SeqCst);
+    }
+
+    #[inline]
+    fn propose_max(&self, proposed_value: i64) -> bool {
+        if self.get() < proposed_value {
+            self.set(proposed_value);
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    fn propose_max_ordered(&self, proposed_value: i64) -> bool {
+        if self.get() < proposed_value {
+            self.set_ordered(proposed_value);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::{CountersManager, COUNTER_LENGTH, METADATA_LENGTH};
+    use crate::AtomicBuffer;
+
+    #[test]
+    fn test_position_visible_through_readable_handle() {
+        let mut manager = CountersManager::new(
+            AtomicBuffer::new(METADATA_LENGTH * 4).unwrap(),
+            AtomicBuffer::new(COUNTER_LENGTH * 4).unwrap(),
+        )
+        .unwrap();
+
+        let position = unsafe { manager.new_position("sub-pos", 2, &[]) }.unwrap();
+        let readable: Box<dyn ReadablePosition> =
+            Box::new(unsafe { UnsafeBufferPosition::wrap(&manager, position.id()) }.unwrap());
+
+        position.set_ordered(1024);
+        assert_eq!(readable.get_volatile(), 1024);
+
+        assert!(position.propose_max_ordered(2048));
+        assert!(!position.propose_max(512));
+        assert_eq!(readable.get_volatile(), 2048);
+        assert_eq!(readable.id(), position.id());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::status::atomic_counter::AtomicCounter;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+pub trait StatusIndicatorReader: Send + Sync {
+    fn id(&self) -> i32;
+    fn get_volatile(&self) -> i64;
+}
+
+pub trait StatusIndicator: StatusIndicatorReader {
+    fn set_ordered(&self, value: i64);
+}
+
+/// Heap allocated [`StatusIndicator`] for control within a single process.
+#[derive(Debug, Default)]
+pub struct AtomicStatusIndicator {
+    value: AtomicI64,
+}
+
+impl AtomicStatusIndicator {
+    pub const ID: i32 = -1;
+
+    pub fn new(initial_value: i64) -> Self {
+        Self {
+            value: AtomicI64::new(initial_value),
+        }
+    }
+}
+
+impl StatusIndicatorReader for AtomicStatusIndicator {
+    #[inline]
+    fn id(&self) -> i32 {
+        Self::ID
+    }
+
+    #[inline]
+    fn get_volatile(&self) -> i64 {
+        self.value.load(Ordering::Acquire)
+    }
+}
+
+impl StatusIndicator for AtomicStatusIndicator {
+    #[inline]
+    fn set_ordered(&self, value: i64) {
+        self.value.store(value, Ordering::Release);
+    }
+}
+
+impl StatusIndicatorReader for AtomicCounter {
+    #[inline]
+    fn id(&self) -> i32 {
+        AtomicCounter::id(self)
+    }
+
+    #[inline]
+    fn get_volatile(&self) -> i64 {
+        self.get()
+    }
+}
+
+impl StatusIndicator for AtomicCounter {
+    #[inline]
+    fn set_ordered(&self, value: i64) {
+        AtomicCounter::set_ordered(self, value);
+    }
+}
@@ -0,0 +1,273 @@
+use agrona_core::error::{AgronaError, Result};
+use std::ffi::{CStr, CString};
+use std::io;
+use std::mem;
+
+/// Highest CPU number plus one that can be expressed in a `cpu_set_t`.
+pub const MAX_CPUS: usize = libc::CPU_SETSIZE as usize;
+
+/// Longest thread name the kernel keeps, excluding the terminating NUL.
+pub const MAX_THREAD_NAME_LENGTH: usize = 15;
+
+/// Parses a core list such as `"2,4-6"` into sorted, de-duplicated CPU numbers.
+pub fn parse_core_list(core_list: &str) -> Result<Vec<usize>> {
+    let mut cores = Vec::new();
+
+    for part in core_list.split(',').map(str::trim) {
+        if part.is_empty() {
+            return Err(AgronaError::IllegalArgument(format!("empty entry in core list: {:?}", core_list)));
+        }
+
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (parse_core(first, core_list)?, parse_core(last, core_list)?),
+            None => {
+                let core = parse_core(part, core_list)?;
+                (core, core)
+            }
+        };
+
+        if first > last {
+            return Err(AgronaError::IllegalArgument(format!(
+                "descending range {} in core list: {:?}",
+                part, core_list
+            )));
+        }
+
+        cores.extend(first..=last);
+    }
+
+    cores.sort_unstable();
+    cores.dedup();
+    Ok(cores)
+}
+
+fn parse_core(value: &str, core_list: &str) -> Result<usize> {
+    let core = value.trim().parse::<usize>().map_err(|_| {
+        AgronaError::IllegalArgument(format!("invalid core {:?} in core list: {:?}", value, core_list))
+    })?;
+
+    if core >= MAX_CPUS {
+        return Err(AgronaError::IllegalArgument(format!(
+            "core {} exceeds maximum of {} in core list: {:?}",
+            core,
+            MAX_CPUS - 1,
+            core_list
+        )));
+    }
+
+    Ok(core)
+}
+
+/// Pins the calling thread to `cores`.
+pub fn set_current_thread_affinity(cores: &[usize]) -> Result<()> {
+    if cores.is_empty() {
+        return Err(AgronaError::IllegalArgument("cpu affinity requires at least one core".to_string()));
+    }
+
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    for &core in cores {
+        if core >= MAX_CPUS {
+            return Err(AgronaError::IllegalArgument(format!(
+                "core {} exceeds maximum of {}",
+                core,
+                MAX_CPUS - 1
+            )));
+        }
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+
+    if unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        let error = io::Error::last_os_error();
+        return Err(affinity_error(error.raw_os_error().unwrap_or(0), cores, error));
+    }
+
+    Ok(())
+}
+
+fn affinity_error(errno: i32, cores: &[usize], error: io::Error) -> AgronaError {
+    match errno {
+        libc::EINVAL => AgronaError::IllegalArgument(format!(
+            "none of cores {:?} are online and permitted for this process: {}",
+            cores, error
+        )),
+        libc::EPERM => AgronaError::IllegalState(format!(
+            "insufficient permissions to set cpu affinity to {:?}: {}",
+            cores, error
+        )),
+        _ => AgronaError::Io(format!("sched_setaffinity to {:?} failed: {}", cores, error)),
+    }
+}
+
+/// CPUs the calling thread is allowed to run on.
+pub fn current_thread_affinity() -> Result<Vec<usize>> {
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    if unsafe { libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0 {
+        return Err(AgronaError::Io(format!("sched_getaffinity failed: {}", io::Error::last_os_error())));
+    }
+
+    Ok((0..MAX_CPUS).filter(|&core| unsafe { libc::CPU_ISSET(core, &set) }).collect())
+}
+
+/// CPU the calling thread is running on at the time of the call.
+pub fn current_cpu() -> Result<usize> {
+    let cpu = unsafe { libc::sched_getcpu() };
+    if cpu < 0 {
+        return Err(AgronaError::Io(format!("sched_getcpu failed: {}", io::Error::last_os_error())));
+    }
+
+    Ok(cpu as usize)
+}
+
+/// Switches the calling thread to `SCHED_FIFO` at `priority`.
+///
+/// This needs `CAP_SYS_NICE` or an `RLIMIT_RTPRIO` of at least `priority`.
+pub fn set_current_thread_sched_fifo(priority: i32) -> Result<()> {
+    let (min, max) = unsafe {
+        (
+            libc::sched_get_priority_min(libc::SCHED_FIFO),
+            libc::sched_get_priority_max(libc::SCHED_FIFO),
+        )
+    };
+    if priority < min || priority > max {
+        return Err(AgronaError::IllegalArgument(format!(
+            "SCHED_FIFO priority {} outside of {}..={}",
+            priority, min, max
+        )));
+    }
+
+    let param = libc::sched_param { sched_priority: priority };
+    let result = unsafe { libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param) };
+    if result != 0 {
+        return Err(sched_fifo_error(result, priority));
+    }
+
+    Ok(())
+}
+
+fn sched_fifo_error(errno: i32, priority: i32) -> AgronaError {
+    let error = io::Error::from_raw_os_error(errno);
+    match errno {
+        libc::EPERM => AgronaError::IllegalState(format!(
+            "insufficient permissions to set SCHED_FIFO priority {}, requires CAP_SYS_NICE or RLIMIT_RTPRIO >= {}: {}",
+            priority, priority, error
+        )),
+        _ => AgronaError::Io(format!("pthread_setschedparam SCHED_FIFO {} failed: {}", priority, error)),
+    }
+}
+
+/// Scheduling policy of the calling thread, e.g. `libc::SCHED_OTHER` or `libc::SCHED_FIFO`.
+pub fn current_thread_sched_policy() -> Result<i32> {
+    let mut policy = 0;
+    let mut param = libc::sched_param { sched_priority: 0 };
+    let result = unsafe { libc::pthread_getschedparam(libc::pthread_self(), &mut policy, &mut param) };
+    if result != 0 {
+        return Err(AgronaError::Io(format!(
+            "pthread_getschedparam failed: {}",
+            io::Error::from_raw_os_error(result)
+        )));
+    }
+
+    Ok(policy)
+}
+
+/// Sets the OS name of the calling thread, truncated at a char boundary to what the kernel keeps.
+pub fn set_current_thread_name(name: &str) -> Result<()> {
+    let mut length = name.len().min(MAX_THREAD_NAME_LENGTH);
+    while !name.is_char_boundary(length) {
+        length -= 1;
+    }
+
+    let name = CString::new(&name[..length])
+        .map_err(|_| AgronaError::IllegalArgument(format!("thread name contains NUL: {:?}", name)))?;
+
+    let result = unsafe { libc::pthread_setname_np(libc::pthread_self(), name.as_ptr()) };
+    if result != 0 {
+        return Err(AgronaError::Io(format!(
+            "pthread_setname_np failed: {}",
+            io::Error::from_raw_os_error(result)
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn current_thread_name() -> Result<String> {
+    let mut buffer = [0 as libc::c_char; MAX_THREAD_NAME_LENGTH + 1];
+    let result = unsafe { libc::pthread_getname_np(libc::pthread_self(), buffer.as_mut_ptr(), buffer.len()) };
+    if result != 0 {
+        return Err(AgronaError::Io(format!(
+            "pthread_getname_np failed: {}",
+            io::Error::from_raw_os_error(result)
+        )));
+    }
+
+    Ok(unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_parse_core_list() {
+        assert_eq!(parse_core_list("2,4-6").unwrap(), vec![2, 4, 5, 6]);
+        assert_eq!(parse_core_list(" 3 , 1-2,2 ").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_core_list("0").unwrap(), vec![0]);
+
+        for invalid in ["", "1,,2", "a", "6-4", "1-", "-1", "1024"] {
+            assert!(
+                matches!(parse_core_list(invalid), Err(AgronaError::IllegalArgument(_))),
+                "{:?}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_affinity_and_current_cpu() {
+        thread::spawn(|| {
+            let allowed = current_thread_affinity().unwrap();
+            let core = allowed[0];
+
+            set_current_thread_affinity(&[core]).unwrap();
+            assert_eq!(current_thread_affinity().unwrap(), vec![core]);
+            assert_eq!(current_cpu().unwrap(), core);
+
+            let error = set_current_thread_affinity(&[MAX_CPUS - 1]).unwrap_err();
+            assert!(matches!(error, AgronaError::IllegalArgument(_)), "{}", error);
+            assert!(set_current_thread_affinity(&[]).is_err());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sched_fifo_errors() {
+        assert!(matches!(set_current_thread_sched_fifo(0), Err(AgronaError::IllegalArgument(_))));
+        assert!(matches!(set_current_thread_sched_fifo(1_000), Err(AgronaError::IllegalArgument(_))));
+
+        let error = sched_fifo_error(libc::EPERM, 10);
+        assert!(matches!(error, AgronaError::IllegalState(_)));
+        assert!(error.to_string().contains("insufficient permissions to set SCHED_FIFO priority 10"));
+
+        // Succeeds only with CAP_SYS_NICE or an RLIMIT_RTPRIO, otherwise must report permissions
+        thread::spawn(|| match set_current_thread_sched_fifo(1) {
+            Ok(()) => assert_eq!(current_thread_sched_policy().unwrap(), libc::SCHED_FIFO),
+            Err(error) => assert!(error.to_string().contains("insufficient permissions"), "{}", error),
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_thread_name() {
+        thread::spawn(|| {
+            set_current_thread_name("agent-with-a-very-long-name").unwrap();
+            assert_eq!(current_thread_name().unwrap(), "agent-with-a-ve");
+            assert!(set_current_thread_name("bad\0name").is_err());
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(unix)]
+
+use agrona_concurrent::broadcast::broadcast_buffer_descriptor;
+use agrona_concurrent::ringbuffer::ring_buffer_descriptor;
+use agrona_concurrent::{
+    BroadcastReceiver, BroadcastTransmitter, CopyBroadcastReceiver, ManyToOneRingBuffer, RingBuffer, SharedBuffer,
+};
+use agrona_core::shm_path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MESSAGE_COUNT: u64 = 100_000;
+const CHILD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Forks a child that runs `child` and exits with the code it returns. A panic in the child would
+/// unwind into a copy of the test harness, so it reports failure through its exit code instead.
+fn fork_child(child: impl FnOnce() -> i32) -> libc::pid_t {
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "fork failed");
+    if pid == 0 {
+        let code = child();
+        unsafe { libc::_exit(code) };
+    }
+    pid
+}
+
+fn wait_for_exit_code(pid: libc::pid_t) -> i32 {
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert!(libc::WIFEXITED(status), "child did not exit normally: {}", status);
+    libc::WEXITSTATUS(status)
+}
+
+#[test]
+fn test_ring_buffer_and_broadcast_between_processes() {
+    let ring_path = shm_path(&format!("agrona-ipc-ring-{}", std::process::id()));
+    let broadcast_path = shm_path(&format!("agrona-ipc-broadcast-{}", std::process::id()));
+    let ring_buffer = ManyToOneRingBuffer::new(
+        SharedBuffer::create_file(&ring_path, 64 * 1024 + ring_buffer_descriptor::TRAILER_LENGTH).unwrap(),
+    )
+    .unwrap();
+    let broadcast_buffer =
+        SharedBuffer::create_file(&broadcast_path, 64 * 1024 + broadcast_buffer_descriptor::TRAILER_LENGTH).unwrap();
+
+    // The child attaches by path, as an unrelated process would, consumes the ring buffer and
+    // echoes the final sequence back over the broadcast buffer.
+    let child_ring_path = ring_path.clone();
+    let child_broadcast_path = broadcast_path.clone();
+    let pid = fork_child(move || {
+        let Ok(ring_buffer) = SharedBuffer::open_file(&child_ring_path).and_then(ManyToOneRingBuffer::new) else {
+            return 2;
+        };
+        let Ok(mut transmitter) = SharedBuffer::open_file(&child_broadcast_path).and_then(BroadcastTransmitter::new)
+        else {
+            return 2;
+        };
+
+        let deadline = Instant::now() + CHILD_TIMEOUT;
+        let mut next_sequence = 0u64;
+        let mut in_sequence = true;
+        while next_sequence < MESSAGE_COUNT && Instant::now() < deadline {
+            ring_buffer.read(
+                |msg_type_id, payload| {
+                    let sequence = payload.try_into().map(u64::from_le_bytes);
+                    in_sequence &= msg_type_id == 1 && sequence.ok() == Some(next_sequence);
+                    next_sequence += 1;
+                },
+                256,
+            );
+        }
+
+        if !in_sequence || next_sequence != MESSAGE_COUNT {
+            return 1;
+        }
+        match transmitter.transmit(2, &next_sequence.to_le_bytes()) {
+            Ok(()) => 0,
+            Err(_) => 3,
+        }
+    });
+
+    let mut receiver = CopyBroadcastReceiver::new(BroadcastReceiver::new(broadcast_buffer).unwrap());
+    for sequence in 0..MESSAGE_COUNT {
+        while !ring_buffer.write(1, &sequence.to_le_bytes()).unwrap() {
+            thread::yield_now();
+        }
+    }
+
+    let mut echoed = None;
+    let deadline = Instant::now() + CHILD_TIMEOUT;
+    while echoed.is_none() && Instant::now() < deadline {
+        receiver
+            .receive(|msg_type_id, payload| {
+                assert_eq!(msg_type_id, 2);
+                echoed = Some(u64::from_le_bytes(payload.try_into().unwrap()));
+            })
+            .unwrap();
+        thread::yield_now();
+    }
+
+    assert_eq!(wait_for_exit_code(pid), 0);
+    assert_eq!(echoed, Some(MESSAGE_COUNT));
+    assert_eq!(ring_buffer.size(), 0);
+
+    std::fs::remove_file(ring_path).unwrap();
+    std::fs::remove_file(broadcast_path).unwrap();
+}
@@ -12,6 +12,9 @@ pub enum AgronaError {
     #[error("Invalid capacity: {capacity}")]
     InvalidCapacity { capacity: usize },
 
+    #[error("Invalid layout: {0}")]
+    InvalidLayout(String),
+
     #[error("Buffer overflow: attempted to write {attempted} bytes, available {available}")]
     BufferOverflow {
         attempted: usize,
@@ -23,6 +26,41 @@ pub enum AgronaError {
 
     #[error("UTF-8 encoding error: {0}")]
     Utf8Error(#[from] core::str::Utf8Error),
+
+    #[error("Illegal argument: {0}")]
+    IllegalArgument(String),
+
+    #[error("Illegal state: {0}")]
+    IllegalState(String),
+
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    #[error("Agent terminated: {0}")]
+    AgentTermination(String),
+}
+
+impl AgronaError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AgronaError::IndexOutOfBounds { .. } => "IndexOutOfBounds",
+            AgronaError::InvalidCapacity { .. } => "InvalidCapacity",
+            AgronaError::InvalidLayout(_) => "InvalidLayout",
+            AgronaError::BufferOverflow { .. } => "BufferOverflow",
+            AgronaError::AsciiNumberFormat(_) => "AsciiNumberFormat",
+            AgronaError::Utf8Error(_) => "Utf8Error",
+            AgronaError::IllegalArgument(_) => "IllegalArgument",
+            AgronaError::IllegalState(_) => "IllegalState",
+            AgronaError::Io(_) => "Io",
+            AgronaError::AgentTermination(_) => "AgentTermination",
+        }
+    }
+}
+
+impl From<std::io::Error> for AgronaError {
+    fn from(error: std::io::Error) -> Self {
+        AgronaError::Io(error.to_string())
+    }
 }
 
 pub type Result<T> = core::result::Result<T, AgronaError>;
\ No newline at end of file
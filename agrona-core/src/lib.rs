@@ -4,9 +4,11 @@
 pub mod buffer;
 pub mod bit_util;
 pub mod error;
+pub mod mapped_file;
 
 pub use buffer::*;
 pub use bit_util::*;
 pub use error::*;
+pub use mapped_file::*;
 
 pub const CACHE_LINE_SIZE: usize = 64;
\ No newline at end of file
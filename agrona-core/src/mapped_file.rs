@@ -0,0 +1,121 @@
+use crate::error::{AgronaError, Result};
+use memmap2::{MmapMut, MmapOptions};
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// Path for a shared memory file named `name`: under `/dev/shm` where it exists, so the file
+/// lives in RAM, otherwise in the temp directory.
+pub fn shm_path(name: &str) -> PathBuf {
+    let shm = Path::new("/dev/shm");
+    if shm.is_dir() {
+        shm.join(name)
+    } else {
+        std::env::temp_dir().join(name)
+    }
+}
+
+/// A read-write memory mapping of a file, suitable for sharing buffers between processes.
+///
+/// Buffers wrapping the mapping via [`MappedFile::as_mut_ptr`] must not outlive it.
+pub struct MappedFile {
+    mmap: MmapMut,
+    path: PathBuf,
+}
+
+impl MappedFile {
+    /// Creates (or truncates) the file at `path`, sizes it to `length` zeroed bytes and maps it.
+    pub fn create<P: AsRef<Path>>(path: P, length: usize) -> Result<Self> {
+        if length == 0 {
+            return Err(AgronaError::InvalidCapacity { capacity: length });
+        }
+
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        file.set_len(length as u64)?;
+
+        Self::map(file, path, length)
+    }
+
+    /// Maps an existing file at `path` in its entirety.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let length = file.metadata()?.len() as usize;
+        if length == 0 {
+            return Err(AgronaError::InvalidCapacity { capacity: length });
+        }
+
+        Self::map(file, path, length)
+    }
+
+    fn map(file: File, path: PathBuf, length: usize) -> Result<Self> {
+        let mmap = unsafe { MmapOptions::new().len(length).map_mut(&file)? };
+        Ok(Self { mmap, path })
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.mmap.len()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn as_ptr(&self) -> *const u8 {
+        self.mmap.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.mmap.as_mut_ptr()
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.mmap
+    }
+
+    /// Synchronously flushes outstanding modifications to the underlying file.
+    pub fn flush(&self) -> Result<()> {
+        self.mmap.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("agrona-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_create_and_open_share_contents() {
+        let path = temp_path("mapped-file");
+
+        let mut writer = MappedFile::create(&path, 4096).unwrap();
+        assert_eq!(writer.capacity(), 4096);
+        writer.as_mut_slice()[..4].copy_from_slice(&[1, 2, 3, 4]);
+
+        let reader = MappedFile::open(&path).unwrap();
+        assert_eq!(reader.capacity(), 4096);
+        assert_eq!(&reader.as_slice()[..4], &[1, 2, 3, 4]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let result = MappedFile::open(temp_path("missing-file"));
+        assert!(matches!(result, Err(AgronaError::Io(_))));
+    }
+}
@@ -1,6 +1,7 @@
 use agrona_core::buffer::{DirectBuffer, MutableBuffer, UnsafeBuffer};
 use agrona_collections::{IntHashMap, IntHashSet, MutableInteger};
 use agrona_concurrent::{AtomicBuffer, BusySpinIdleStrategy, BackoffIdleStrategy, IdleStrategy};
+use agrona_concurrent::{DutyCycleTracker, LatencyHistogram, SystemNanoClock};
 use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 use std::sync::{Arc, Mutex};
@@ -525,26 +526,43 @@ fn test_idle_strategy_performance() {
 
     // Test BusySpinIdleStrategy
     let mut busy_spin = BusySpinIdleStrategy::new();
-    let start = Instant::now();
-    let mut busy_spin_cycles = 0;
-    while start.elapsed() < test_duration {
-        busy_spin.idle(0);
-        busy_spin_cycles += 1;
-    }
+    let busy_spin_tracker = run_duty_cycles(&mut busy_spin, test_duration);
 
     // Test BackoffIdleStrategy
     let mut backoff = BackoffIdleStrategy::default();
+    let backoff_tracker = run_duty_cycles(&mut backoff, test_duration);
+
+    print_duty_cycles("BusySpinIdleStrategy:", &busy_spin_tracker);
+    print_duty_cycles("BackoffIdleStrategy: ", &backoff_tracker);
+}
+
+fn run_duty_cycles(idle_strategy: &mut impl IdleStrategy, test_duration: Duration) -> DutyCycleTracker {
+    let mut tracker = DutyCycleTracker::with_histogram(
+        Arc::new(SystemNanoClock),
+        Duration::from_micros(100),
+        LatencyHistogram::default(),
+    );
+
     let start = Instant::now();
-    let mut backoff_cycles = 0;
+    tracker.update();
     while start.elapsed() < test_duration {
-        backoff.idle(0);
-        backoff_cycles += 1;
+        idle_strategy.idle(0);
+        tracker.measure_and_update();
     }
 
-    println!("  BusySpinIdleStrategy: {:>10} cycles/100ms ({:.2} MHz)",
-             busy_spin_cycles, busy_spin_cycles as f64 / 100.0 / 1000.0);
-    println!("  BackoffIdleStrategy:  {:>10} cycles/100ms ({:.2} MHz)",
-             backoff_cycles, backoff_cycles as f64 / 100.0 / 1000.0);
+    tracker
+}
+
+fn print_duty_cycles(name: &str, tracker: &DutyCycleTracker) {
+    let histogram = tracker.histogram().expect("tracker records a histogram");
+    println!("  {} {:>10} cycles/100ms  p50={}ns p99={}ns p99.9={}ns max={:?} stalls(>{:?})={}",
+             name, histogram.count(),
+             histogram.value_at_percentile(50.0),
+             histogram.value_at_percentile(99.0),
+             histogram.value_at_percentile(99.9),
+             tracker.max_cycle_time(),
+             tracker.cycle_time_threshold(),
+             tracker.cycle_time_threshold_exceeded_count());
 }
 
 fn concurrent_performance_tests() {
@@ -621,6 +639,8 @@ fn test_producer_consumer_throughput() {
     let producer_buffer = Arc::clone(&buffer);
     let consumer_buffer = Arc::clone(&buffer);
     let messages_to_send = TEST_ITERATIONS;
+    // Both threads timestamp against the same epoch so latencies are comparable
+    let epoch = Instant::now();
 
     let producer_handle = thread::spawn(move || {
         let start = Instant::now();
@@ -636,7 +656,7 @@ fn test_producer_consumer_throughput() {
                 if next_head != tail {
                     let offset = data_start + (head * message_size);
                     buf.put_u64(offset, messages_sent as u64).unwrap();
-                    buf.put_u64(offset + 8, start.elapsed().as_nanos() as u64).unwrap();
+                    buf.put_u64(offset + 8, epoch.elapsed().as_nanos() as u64).unwrap();
                     buf.put_ordered_u64(head_idx, next_head as u64).unwrap();
                     messages_sent += 1;
                     break;
@@ -653,7 +673,7 @@ fn test_producer_consumer_throughput() {
     let consumer_handle = thread::spawn(move || {
         let start = Instant::now();
         let mut messages_received = 0;
-        let mut total_latency_ns = 0u64;
+        let mut latency_histogram = LatencyHistogram::default();
 
         while messages_received < messages_to_send {
             loop {
@@ -665,11 +685,9 @@ fn test_producer_consumer_throughput() {
                     let offset = data_start + (tail * message_size);
                     let _message_id = buf.get_u64(offset).unwrap();
                     let sent_time_ns = buf.get_u64(offset + 8).unwrap();
-                    let current_time_ns = start.elapsed().as_nanos() as u64;
+                    let current_time_ns = epoch.elapsed().as_nanos() as u64;
 
-                    if current_time_ns > sent_time_ns {
-                        total_latency_ns += current_time_ns - sent_time_ns;
-                    }
+                    latency_histogram.record(current_time_ns.saturating_sub(sent_time_ns));
 
                     let next_tail = (tail + 1) % max_messages;
                     buf.put_ordered_u64(tail_idx, next_tail as u64).unwrap();
@@ -682,16 +700,11 @@ fn test_producer_consumer_throughput() {
             }
         }
 
-        let elapsed = start.elapsed();
-        let avg_latency_ns = if messages_received > 0 {
-            total_latency_ns / messages_received as u64
-        } else { 0 };
-
-        (elapsed, messages_received, avg_latency_ns)
+        (start.elapsed(), messages_received, latency_histogram)
     });
 
     let (producer_time, messages_sent) = producer_handle.join().unwrap();
-    let (consumer_time, messages_received, avg_latency_ns) = consumer_handle.join().unwrap();
+    let (consumer_time, messages_received, latency_histogram) = consumer_handle.join().unwrap();
 
     println!("  Producer: {} messages in {:?} ({:.0} msg/s)",
              messages_sent, producer_time,
@@ -699,7 +712,13 @@ fn test_producer_consumer_throughput() {
     println!("  Consumer: {} messages in {:?} ({:.0} msg/s)",
              messages_received, consumer_time,
              messages_received as f64 / consumer_time.as_secs_f64());
-    println!("  Average latency: {} ns ({:.2} μs)", avg_latency_ns, avg_latency_ns as f64 / 1000.0);
+    println!("  Latency: mean={:.0}ns p50={}ns p90={}ns p99={}ns p99.9={}ns max={}ns",
+             latency_histogram.mean(),
+             latency_histogram.value_at_percentile(50.0),
+             latency_histogram.value_at_percentile(90.0),
+             latency_histogram.value_at_percentile(99.0),
+             latency_histogram.value_at_percentile(99.9),
+             latency_histogram.max());
 }
 
 fn memory_usage_tests() {
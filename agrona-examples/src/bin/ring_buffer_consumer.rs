@@ -0,0 +1,61 @@
+//! Reads the messages written by `ring_buffer_producer` from a shared memory ring buffer,
+//! checking that they arrive in sequence.
+//!
+//! Usage: ring_buffer_consumer [name] [message count]
+
+use agrona_concurrent::ringbuffer::ring_buffer_descriptor::TRAILER_LENGTH;
+use agrona_concurrent::{IdleStrategy, ManyToOneRingBuffer, RingBuffer, SharedBuffer, YieldingIdleStrategy};
+use agrona_core::shm_path;
+use std::env;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_NAME: &str = "agrona-ring-buffer-example";
+const DEFAULT_MESSAGE_COUNT: u64 = 10_000_000;
+const MESSAGE_COUNT_LIMIT: usize = 256;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let name = args.next().unwrap_or_else(|| DEFAULT_NAME.to_string());
+    let message_count = args
+        .next()
+        .map(|count| count.parse().expect("message count must be a number"))
+        .unwrap_or(DEFAULT_MESSAGE_COUNT);
+
+    let path = shm_path(&name);
+    println!("Waiting for producer at {}", path.display());
+    let buffer = loop {
+        match SharedBuffer::open_file(&path) {
+            Ok(buffer) if buffer.capacity() > TRAILER_LENGTH => break buffer,
+            _ => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    let ring_buffer = ManyToOneRingBuffer::new(buffer).expect("Failed to attach ring buffer");
+
+    let mut idle_strategy = YieldingIdleStrategy::new();
+    let mut next_sequence = 0u64;
+    let start = Instant::now();
+    while next_sequence < message_count {
+        let read = ring_buffer.read(
+            |_, payload| {
+                let sequence = u64::from_le_bytes(payload.try_into().expect("unexpected message length"));
+                assert_eq!(sequence, next_sequence, "message out of sequence");
+                next_sequence += 1;
+            },
+            MESSAGE_COUNT_LIMIT,
+        );
+        idle_strategy.idle(read);
+    }
+
+    let elapsed = start.elapsed();
+    println!(
+        "Consumed {} messages in {:?} ({:.0} msgs/sec)",
+        message_count,
+        elapsed,
+        message_count as f64 / elapsed.as_secs_f64()
+    );
+
+    if let Err(error) = std::fs::remove_file(&path) {
+        eprintln!("Failed to remove {}: {}", path.display(), error);
+    }
+}
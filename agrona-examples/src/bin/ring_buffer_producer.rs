@@ -0,0 +1,46 @@
+//! Writes sequenced messages into a shared memory ring buffer for `ring_buffer_consumer` to read
+//! from another process.
+//!
+//! Usage: ring_buffer_producer [name] [message count]
+
+use agrona_concurrent::ringbuffer::ring_buffer_descriptor::TRAILER_LENGTH;
+use agrona_concurrent::{IdleStrategy, ManyToOneRingBuffer, RingBuffer, SharedBuffer, YieldingIdleStrategy};
+use agrona_core::shm_path;
+use std::env;
+use std::time::Instant;
+
+const DEFAULT_NAME: &str = "agrona-ring-buffer-example";
+const DEFAULT_MESSAGE_COUNT: u64 = 10_000_000;
+const CAPACITY: usize = 64 * 1024;
+const MSG_TYPE_ID: i32 = 1;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let name = args.next().unwrap_or_else(|| DEFAULT_NAME.to_string());
+    let message_count = args
+        .next()
+        .map(|count| count.parse().expect("message count must be a number"))
+        .unwrap_or(DEFAULT_MESSAGE_COUNT);
+
+    let path = shm_path(&name);
+    let buffer = SharedBuffer::create_file(&path, CAPACITY + TRAILER_LENGTH).expect("Failed to create shared memory file");
+    let ring_buffer = ManyToOneRingBuffer::new(buffer).expect("Failed to attach ring buffer");
+
+    println!("Producing {} messages into {}", message_count, path.display());
+
+    let mut idle_strategy = YieldingIdleStrategy::new();
+    let start = Instant::now();
+    for sequence in 0..message_count {
+        while !ring_buffer.write(MSG_TYPE_ID, &sequence.to_le_bytes()).unwrap() {
+            idle_strategy.idle(0);
+        }
+    }
+
+    let elapsed = start.elapsed();
+    println!(
+        "Produced {} messages in {:?} ({:.0} msgs/sec)",
+        message_count,
+        elapsed,
+        message_count as f64 / elapsed.as_secs_f64()
+    );
+}